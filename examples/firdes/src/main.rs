@@ -4,6 +4,7 @@ use futuresdr::blocks::Source;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 
+use futuredsp::analysis::FilterResponse;
 use futuredsp::firdes;
 use futuresdr::anyhow::Result;
 
@@ -40,6 +41,14 @@ fn main() -> Result<()> {
     let filter_taps =
         firdes::kaiser::bandpass::<f32>(lower_cutoff, higher_cutoff, transition_bw, max_ripple);
     println!("Filter has {} taps", filter_taps.len());
+    for tone in [TONE_FREQ.0, TONE_FREQ.1, TONE_FREQ.2] {
+        let f = tone as f64 / DOWNSAMPLED_FREQ as f64;
+        println!("Gain at {} Hz: {:.1} dB", tone, filter_taps.magnitude_db(f));
+    }
+    println!(
+        "Group delay: {:.1} samples",
+        filter_taps.group_delay(TONE_FREQ.1 as f64 / DOWNSAMPLED_FREQ as f64)
+    );
 
     let filter_block = match enable_filter {
         true => FirBuilder::new::<f32, f32, _>(filter_taps),
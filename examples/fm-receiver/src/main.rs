@@ -16,10 +16,10 @@ use futuresdr::anyhow::Result;
 use futuresdr::async_io;
 use futuresdr::blocks::audio::AudioSink;
 use futuresdr::blocks::Apply;
+use futuresdr::blocks::ArbitraryResamplerBuilder;
 use futuresdr::blocks::FirBuilder;
 use futuresdr::blocks::SoapySourceBuilder;
 use futuresdr::num_complex::Complex32;
-use futuresdr::num_integer::gcd;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Pmt;
 use futuresdr::runtime::Runtime;
@@ -42,9 +42,9 @@ struct Args {
     #[clap(short, long, default_value = "")]
     soapy: String,
 
-    /// Multiplier for intermedia sample rate
-    #[clap(long)]
-    audio_mult: Option<u32>,
+    /// Intermediate sample rate for demodulation
+    #[clap(long, default_value_t = 250_000.0)]
+    if_rate: f64,
 
     /// Audio Rate
     #[clap(short, long)]
@@ -55,7 +55,6 @@ fn main() -> Result<()> {
     let args = Args::parse();
     println!("Configuration {:?}", args);
 
-    let sample_rate = args.rate as u32;
     let freq_offset = args.rate / 4.0;
     println!("Frequency Offset {:?}", freq_offset);
 
@@ -64,22 +63,14 @@ fn main() -> Result<()> {
     } else {
         let mut audio_rates = AudioSink::supported_sample_rates();
         assert!(!audio_rates.is_empty());
-        audio_rates.sort_by_key(|a| std::cmp::Reverse(gcd(*a, sample_rate)));
         println!("Supported Audio Rates {:?}", audio_rates);
-        audio_rates[0]
-    };
-    println!("Selected Audio Rate {:?}", audio_rate);
-
-    let audio_mult = if let Some(m) = args.audio_mult {
-        m
-    } else {
-        let mut m = 5;
-        while (m * audio_rate) as f64 > freq_offset + 100e3 {
-            m -= 1;
+        if audio_rates.contains(&48_000) {
+            48_000
+        } else {
+            *audio_rates.iter().max().unwrap()
         }
-        m
     };
-    println!("Audio Mult {:?}", audio_mult);
+    println!("Selected Audio Rate {:?}", audio_rate);
 
     // Create the `Flowgraph` where the `Block`s will be added later on
     let mut fg = Flowgraph::new();
@@ -97,11 +88,8 @@ fn main() -> Result<()> {
         .message_input_name_to_id("freq")
         .expect("No freq port found!");
 
-    // Downsample before demodulation
-    let interp = (audio_rate * audio_mult) as usize;
-    let decim = sample_rate as usize;
-    println!("interp {}   decim {}", interp, decim);
-    let resamp1 = FirBuilder::new_resampling::<Complex32>(interp, decim);
+    // Downsample to the intermediate rate before demodulation
+    let resamp1 = ArbitraryResamplerBuilder::new(args.if_rate / args.rate).build::<Complex32>();
 
     // Demodulation block using the conjugate delay method
     // See https://en.wikipedia.org/wiki/Detector_(radio)#Quadrature_detector
@@ -122,19 +110,18 @@ fn main() -> Result<()> {
         last * v
     });
 
-    // Design filter for the audio and decimate by 5.
+    // Design filter for the audio.
     // Ideally, this should be a FM de-emphasis filter, but the following works.
-    let cutoff = 2_000.0 / (audio_rate * audio_mult) as f64;
-    let transition = 10_000.0 / (audio_rate * audio_mult) as f64;
+    let cutoff = 2_000.0 / args.if_rate;
+    let transition = 10_000.0 / args.if_rate;
     println!("cutoff {}   transition {}", cutoff, transition);
     let audio_filter_taps = firdes::kaiser::lowpass::<f32>(cutoff, transition, 0.1);
-    let resamp2 = FirBuilder::new_resampling_with_taps::<f32, f32, _>(
-        1,
-        audio_mult as usize,
-        audio_filter_taps,
-    );
+    let audio_filter = FirBuilder::new::<f32, f32, _>(audio_filter_taps);
+
+    // Downsample to the audio rate
+    let resamp2 = ArbitraryResamplerBuilder::new(audio_rate as f64 / args.if_rate).build::<f32>();
 
-    // Single-channel `AudioSink` with the downsampled rate (sample_rate / (8*5) = 48_000)
+    // Single-channel `AudioSink` with the audio rate
     let snk = AudioSink::new(audio_rate, 1);
 
     // Add all the blocks to the `Flowgraph`...
@@ -142,6 +129,7 @@ fn main() -> Result<()> {
     let shift = fg.add_block(shift);
     let resamp1 = fg.add_block(resamp1);
     let demod = fg.add_block(demod);
+    let audio_filter = fg.add_block(audio_filter);
     let resamp2 = fg.add_block(resamp2);
     let snk = fg.add_block(snk);
 
@@ -149,7 +137,8 @@ fn main() -> Result<()> {
     fg.connect_stream(src, "out", shift, "in")?;
     fg.connect_stream(shift, "out", resamp1, "in")?;
     fg.connect_stream(resamp1, "out", demod, "in")?;
-    fg.connect_stream(demod, "out", resamp2, "in")?;
+    fg.connect_stream(demod, "out", audio_filter, "in")?;
+    fg.connect_stream(audio_filter, "out", resamp2, "in")?;
     fg.connect_stream(resamp2, "out", snk, "in")?;
 
     // Start the flowgraph and save the handle
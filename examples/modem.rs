@@ -0,0 +1,82 @@
+use std::iter::repeat_with;
+
+use futuredsp::firdes;
+use futuresdr::anyhow::{Context, Result};
+use futuresdr::blocks::Apply;
+use futuresdr::blocks::Constellation;
+use futuresdr::blocks::ConstellationDemapperBuilder;
+use futuresdr::blocks::ConstellationMapper;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+fn main() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let n_bits = 60_000;
+    let sps = 4;
+    let span = 8;
+    let noise_amplitude = 0.1;
+    let constellation = Constellation::Qam16;
+
+    let bits: Vec<u8> = repeat_with(|| rand::random::<u8>() & 1)
+        .take(n_bits)
+        .collect();
+
+    // root raised cosine pulse shaping and matched filter
+    let taps = firdes::root_raised_cosine::<f32>(span, sps, 0.35);
+    let rx_taps: Vec<f32> = taps.iter().map(|x| x / (sps as f32).sqrt()).collect();
+    let mut tx_taps: Vec<f32> = taps.iter().map(|x| x * (sps as f32).sqrt()).collect();
+    // the interpolator needs a multiple of `sps` taps
+    tx_taps.resize(taps.len() + sps - taps.len() % sps, 0.0);
+
+    let src = fg.add_block(VectorSourceBuilder::<u8>::new(bits.clone()).build());
+    let mapper = fg.add_block(ConstellationMapper::new(constellation));
+    let tx = fg.add_block(FirBuilder::new_resampling_with_taps::<Complex32, f32, _>(
+        sps, 1, tx_taps,
+    ));
+    let channel = fg.add_block(Apply::new(move |x: &Complex32| {
+        x + Complex32::new(rand::random::<f32>() - 0.5, rand::random::<f32>() - 0.5)
+            * noise_amplitude
+    }));
+    let rx = fg.add_block(FirBuilder::new_resampling_with_taps::<Complex32, f32, _>(
+        1, sps, rx_taps,
+    ));
+    let demapper = fg.add_block(ConstellationDemapperBuilder::new(constellation).build_hard());
+    let snk = fg.add_block(VectorSinkBuilder::<u8>::new().build());
+
+    fg.connect_stream(src, "out", mapper, "in")?;
+    fg.connect_stream(mapper, "out", tx, "in")?;
+    fg.connect_stream(tx, "out", channel, "in")?;
+    fg.connect_stream(channel, "out", rx, "in")?;
+    fg.connect_stream(rx, "out", demapper, "in")?;
+    fg.connect_stream(demapper, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg
+        .kernel::<VectorSink<u8>>(snk)
+        .context("block not found")?;
+    let rx_bits = snk.items();
+
+    // the filters delay the signal by `span` symbols
+    let skip = span * constellation.bits_per_symbol();
+    let errors = rx_bits
+        .iter()
+        .zip(bits[skip..].iter())
+        .filter(|(a, b)| a != b)
+        .count();
+
+    println!(
+        "received {} bits, {} errors (BER {:.2e})",
+        rx_bits.len(),
+        errors,
+        errors as f64 / rx_bits.len() as f64
+    );
+
+    Ok(())
+}
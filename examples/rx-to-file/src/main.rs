@@ -1,9 +1,10 @@
 use clap::Parser;
 use futuresdr::anyhow::Result;
+use futuresdr::blocks::sigmf::{SigMfSinkBuilder, SigMfSourceBuilder};
 use futuresdr::blocks::Apply;
 use futuresdr::blocks::Head;
 use futuresdr::blocks::SoapySource;
-use futuresdr::blocks::{FileSink, FileSource};
+use futuresdr::blocks::{FileSink, FileSourceBuilder, SampleFormat};
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 use num_complex::Complex;
@@ -55,6 +56,7 @@ fn main() -> Result<()> {
 
     let mut fg = Flowgraph::new();
 
+    let hw = args.soapy.clone();
     let src = match (args.soapy, args.input) {
         (Some(_), Some(_)) => {
             panic!("Cannot specify both soapy source and input file");
@@ -75,15 +77,21 @@ fn main() -> Result<()> {
                 })
                 .expect("Input format could not be determined!");
             match format.as_str() {
-                "cs8" => {
-                    let src = fg.add_block(FileSource::<Complex<i8>>::new(input));
-                    let typecvt =
-                        fg.add_block(Apply::<Complex<i8>, Complex<f32>>::new(|i| Complex {
-                            re: i.re as f32 / 127.,
-                            im: i.im as f32 / 127.,
-                        }));
-                    fg.connect_stream(src, "out", typecvt, "in")?;
-                    typecvt
+                "cs8" | "cu8" | "cs16" | "cf32" => {
+                    let format = match format.as_str() {
+                        "cs8" => SampleFormat::Cs8,
+                        "cu8" => SampleFormat::Cu8,
+                        "cs16" => SampleFormat::Ci16,
+                        _ => SampleFormat::Cf32,
+                    };
+                    fg.add_block(
+                        FileSourceBuilder::<Complex<f32>>::new(input)
+                            .format(format)
+                            .build(),
+                    )
+                }
+                "sigmf" | "sigmf-data" | "sigmf-meta" => {
+                    fg.add_block(SigMfSourceBuilder::<Complex<f32>>::new(input).build())
                 }
                 _ => {
                     panic!("Unrecognized input format {}", format);
@@ -148,8 +156,21 @@ fn main() -> Result<()> {
             let sink = fg.add_block(FileSink::<Complex<f32>>::new(&args.out));
             fg.connect_stream(powermeter, "out", sink, "in")?;
         }
+        "sigmf" | "sigmf-data" | "sigmf-meta" => {
+            let mut sink = SigMfSinkBuilder::<Complex<f32>>::new(&args.out)
+                .sample_rate(args.rate)
+                .frequency(args.frequency);
+            if let Some(hw) = hw {
+                sink = sink.hw(hw);
+            }
+            let sink = fg.add_block(sink.build());
+            fg.connect_stream(powermeter, "out", sink, "in")?;
+        }
         format => {
-            panic!("Unknown format {}! (known formats: cs8, cf32", format);
+            panic!(
+                "Unknown format {}! (known formats: cs8, cf32, sigmf)",
+                format
+            );
         }
     }
 
@@ -1,5 +1,3 @@
-use std::mem::size_of;
-
 use futuresdr::anyhow::Result;
 use futuresdr::async_trait::async_trait;
 use futuresdr::blocks::FftBuilder;
@@ -54,8 +52,8 @@ impl ComplexToMag {
         Block::new(
             BlockMetaBuilder::new("ComplexToMag").build(),
             StreamIoBuilder::new()
-                .add_input("in", size_of::<Complex<f32>>())
-                .add_output("out", size_of::<f32>())
+                .add_typed_input::<Complex<f32>>("in")
+                .add_typed_output::<f32>("out")
                 .build(),
             MessageIoBuilder::new().build(),
             Self {},
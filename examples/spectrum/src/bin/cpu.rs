@@ -1,16 +1,12 @@
 use futuresdr::anyhow::Result;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::PsdAveraging;
+use futuresdr::blocks::PsdBuilder;
 use futuresdr::blocks::SoapySourceBuilder;
 use futuresdr::blocks::WebsocketSinkBuilder;
 use futuresdr::blocks::WebsocketSinkMode;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 
-use spectrum::lin2db_block;
-use spectrum::power_block;
-use spectrum::FftShift;
-use spectrum::Keep1InN;
-
 fn main() -> Result<()> {
     let mut fg = Flowgraph::new();
 
@@ -24,19 +20,16 @@ fn main() -> Result<()> {
         .build();
 
     let src = fg.add_block(src);
-    let fft = fg.add_block(Fft::new());
-    let power = fg.add_block(power_block());
-    let log = fg.add_block(lin2db_block());
-    let shift = fg.add_block(FftShift::<f32>::new());
-    let keep = fg.add_block(Keep1InN::new(0.1, 10));
+    let psd = fg.add_block(
+        PsdBuilder::new(2048)
+            .averaging(PsdAveraging::Exponential(0.1))
+            .decimation(20)
+            .build(),
+    );
     let snk = fg.add_block(snk);
 
-    fg.connect_stream(src, "out", fft, "in")?;
-    fg.connect_stream(fft, "out", power, "in")?;
-    fg.connect_stream(power, "out", log, "in")?;
-    fg.connect_stream(log, "out", shift, "in")?;
-    fg.connect_stream(shift, "out", keep, "in")?;
-    fg.connect_stream(keep, "out", snk, "in")?;
+    fg.connect_stream(src, "out", psd, "in")?;
+    fg.connect_stream(psd, "out", snk, "in")?;
 
     Runtime::new().run(fg)?;
     Ok(())
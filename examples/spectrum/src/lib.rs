@@ -15,10 +15,6 @@ use futuresdr::blocks::Apply;
 use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Block;
 
-pub fn lin2db_block() -> Block {
-    Apply::new(|x: &f32| 10.0 * x.log10())
-}
-
 pub fn power_block() -> Block {
     Apply::new(|x: &Complex32| x.norm())
 }
@@ -1,5 +1,6 @@
 use futuresdr::anyhow::Result;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::PsdAveraging;
+use futuresdr::blocks::PsdBuilder;
 use futuresdr::blocks::WasmFreq;
 use futuresdr::blocks::WasmSdr;
 use futuresdr::runtime::buffer::slab::Slab;
@@ -7,11 +8,6 @@ use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 use wasm_bindgen::prelude::*;
 
-use crate::lin2db_block;
-use crate::power_block;
-use crate::FftShift;
-use crate::Keep1InN;
-
 #[wasm_bindgen]
 pub async fn run_fg() {
     run().await.unwrap();
@@ -20,20 +16,18 @@ pub async fn run_fg() {
 async fn run() -> Result<()> {
     let mut fg = Flowgraph::new();
 
+    let fft_size = 2048;
     let src = fg.add_block(WasmSdr::new());
-    let fft = fg.add_block(Fft::new());
-    let power = fg.add_block(power_block());
-    let log = fg.add_block(lin2db_block());
-    let shift = fg.add_block(FftShift::<f32>::new());
-    let keep = fg.add_block(Keep1InN::new(0.1, 40));
+    let psd = fg.add_block(
+        PsdBuilder::new(fft_size)
+            .averaging(PsdAveraging::Exponential(0.1))
+            .decimation(80)
+            .build(),
+    );
     let snk = fg.add_block(WasmFreq::new());
 
-    fg.connect_stream_with_type(src, "out", fft, "in", Slab::with_config(65536, 2, 0))?;
-    fg.connect_stream_with_type(fft, "out", power, "in", Slab::with_config(65536, 2, 0))?;
-    fg.connect_stream_with_type(power, "out", log, "in", Slab::with_config(65536, 2, 0))?;
-    fg.connect_stream_with_type(log, "out", shift, "in", Slab::with_config(65536, 2, 0))?;
-    fg.connect_stream_with_type(shift, "out", keep, "in", Slab::with_config(65536, 2, 0))?;
-    fg.connect_stream_with_type(keep, "out", snk, "in", Slab::with_config(65536, 2, 0))?;
+    fg.connect_stream_with_type(src, "out", psd, "in", Slab::with_config(65536, 2, fft_size))?;
+    fg.connect_stream_with_type(psd, "out", snk, "in", Slab::with_config(65536, 2, 0))?;
 
     Runtime::new().run_async(fg).await?;
     Ok(())
@@ -5,8 +5,10 @@ use futuresdr::anyhow::Result;
 use futuresdr::async_io::block_on;
 use futuresdr::async_io::Timer;
 use futuresdr::blocks::Apply;
+use futuresdr::blocks::BlobToUdp;
 use futuresdr::blocks::SoapySinkBuilder;
 use futuresdr::blocks::SoapySourceBuilder;
+use futuresdr::blocks::UdpToBlob;
 use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Pmt;
@@ -30,6 +32,13 @@ struct Args {
     rx_gain: f64,
     #[clap(long, default_value_t = 18.0)]
     tx_gain: f64,
+    /// Transmit the frames received as UDP datagrams on this address,
+    /// instead of test frames
+    #[clap(long)]
+    tx_udp: Option<String>,
+    /// Send received frames as UDP datagrams to this address
+    #[clap(long)]
+    rx_udp: Option<String>,
 }
 
 fn main() -> Result<()> {
@@ -99,25 +108,39 @@ fn main() -> Result<()> {
     fg.connect_stream(mm, "out", decoder, "in")?;
     fg.connect_message(decoder, "out", mac, "rx")?;
 
+    // ========================================
+    // External applications
+    // ========================================
+    if let Some(remote) = &args.rx_udp {
+        let udp_snk = fg.add_block(BlobToUdp::new(remote));
+        fg.connect_message(mac, "rxed", udp_snk, "in")?;
+    }
+    if let Some(bind) = &args.tx_udp {
+        let udp_src = fg.add_block(UdpToBlob::new(bind));
+        fg.connect_message(udp_src, "out", mac, "tx")?;
+    }
+
     let rt = Runtime::new();
     let (fg, mut handle) = block_on(rt.start(fg));
 
     // send a message every 0.8 seconds
-    let mut seq = 0u64;
-    rt.spawn_background(async move {
-        loop {
-            Timer::after(Duration::from_secs_f32(0.8)).await;
-            handle
-                .call(
-                    0, // mac block
-                    1, // tx handler
-                    Pmt::Blob(format!("FutureSDR {}", seq).as_bytes().to_vec()),
-                )
-                .await
-                .unwrap();
-            seq += 1;
-        }
-    });
+    if args.tx_udp.is_none() {
+        let mut seq = 0u64;
+        rt.spawn_background(async move {
+            loop {
+                Timer::after(Duration::from_secs_f32(0.8)).await;
+                handle
+                    .call(
+                        0, // mac block
+                        1, // tx handler
+                        Pmt::Blob(format!("FutureSDR {}", seq).as_bytes().to_vec()),
+                    )
+                    .await
+                    .unwrap();
+                seq += 1;
+            }
+        });
+    }
 
     block_on(fg)?;
 
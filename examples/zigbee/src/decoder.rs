@@ -60,7 +60,7 @@ impl Decoder {
         Block::new(
             BlockMetaBuilder::new("Decoder").build(),
             StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<f32>())
+                .add_typed_input::<f32>("in")
                 .build(),
             MessageIoBuilder::<Self>::new().add_output("out").build(),
             Self {
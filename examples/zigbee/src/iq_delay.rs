@@ -34,8 +34,8 @@ impl IqDelay {
         Block::new(
             BlockMetaBuilder::new("IQ Delay").build(),
             StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<Complex32>())
-                .add_output("out", std::mem::size_of::<Complex32>())
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
                 .build(),
             MessageIoBuilder::new().build(),
             Self {
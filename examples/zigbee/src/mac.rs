@@ -53,7 +53,7 @@ impl Mac {
 
         Block::new(
             BlockMetaBuilder::new("Mac").build(),
-            StreamIoBuilder::new().add_output("out", 1).build(),
+            StreamIoBuilder::new().add_typed_output::<u8>("out").build(),
             MessageIoBuilder::new()
                 .add_input("rx", Self::received)
                 .add_input("tx", Self::transmit)
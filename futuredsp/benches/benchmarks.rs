@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use futuredsp::fir::NonResamplingFirKernel;
+use futuredsp::fir::{NonResamplingFirKernel, OverlapSaveFirKernel};
 use futuredsp::iir::IirKernel;
 use futuredsp::{StatefulUnaryKernel, TapsAccessor, UnaryKernel};
 use num_complex::Complex;
@@ -68,6 +68,27 @@ fn bench_fir_static_taps<SampleType: Generatable, TapType: Generatable, const N:
     });
 }
 
+fn bench_fir_overlap_save<SampleType: Generatable, TapType: Generatable>(
+    b: &mut criterion::Bencher,
+    ntaps: usize,
+    nsamps: usize,
+) where
+    SampleType: Clone,
+    Vec<TapType>: TapsAccessor<TapType = TapType>,
+    TapType: Into<Complex<f32>>,
+    OverlapSaveFirKernel<SampleType, TapType>: StatefulUnaryKernel<SampleType>,
+{
+    let taps: Vec<_> = (0..ntaps).map(|_| TapType::generate()).collect();
+    let input: Vec<_> = (0..nsamps + ntaps)
+        .map(|_| SampleType::generate())
+        .collect();
+    let mut output = vec![SampleType::generate(); nsamps];
+    let mut fir = OverlapSaveFirKernel::<SampleType, _>::new(black_box(taps));
+    b.iter(|| {
+        fir.work(black_box(&input), black_box(&mut output));
+    });
+}
+
 fn bench_iir<SampleType: Generatable, TapType: Generatable>(
     b: &mut criterion::Bencher,
     n_a_taps: usize,
@@ -110,8 +131,32 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 bench_fir_dynamic_taps::<Complex<f32>, f32>(b, ntaps, nsamps);
             },
         );
+        group.bench_function(
+            format!("fir-{}tap-dynamic complex/complex {}", ntaps, nsamps),
+            |b| {
+                bench_fir_dynamic_taps::<Complex<f32>, Complex<f32>>(b, ntaps, nsamps);
+            },
+        );
     }
 
+    // Compare with the scalar implementation
+    futuredsp::simd::set_enabled(false);
+    for ntaps in [3, 64] {
+        group.bench_function(
+            format!("fir-{}tap-dynamic-scalar real/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_dynamic_taps::<f32, f32>(b, ntaps, nsamps);
+            },
+        );
+        group.bench_function(
+            format!("fir-{}tap-dynamic-scalar complex/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_dynamic_taps::<Complex<f32>, f32>(b, ntaps, nsamps);
+            },
+        );
+    }
+    futuredsp::simd::set_enabled(true);
+
     // Check some static taps as well
     group.bench_function(format!("fir-3tap-static complex/real {}", nsamps), |b| {
         bench_fir_static_taps::<Complex<f32>, f32, 3>(b, nsamps);
@@ -122,11 +167,45 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 
     group.finish();
 
+    let nsamps = 10000usize;
+    let mut group = c.benchmark_group("fir-long");
+    group.throughput(criterion::Throughput::Elements(nsamps as u64));
+
+    for ntaps in [64, 128, 256, 1024] {
+        group.bench_function(
+            format!("fir-{}tap-direct real/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_dynamic_taps::<f32, f32>(b, ntaps, nsamps);
+            },
+        );
+        group.bench_function(
+            format!("fir-{}tap-overlap-save real/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_overlap_save::<f32, f32>(b, ntaps, nsamps);
+            },
+        );
+        group.bench_function(
+            format!("fir-{}tap-direct complex/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_dynamic_taps::<Complex<f32>, f32>(b, ntaps, nsamps);
+            },
+        );
+        group.bench_function(
+            format!("fir-{}tap-overlap-save complex/real {}", ntaps, nsamps),
+            |b| {
+                bench_fir_overlap_save::<Complex<f32>, f32>(b, ntaps, nsamps);
+            },
+        );
+    }
+
+    group.finish();
+
+    let nsamps = 1000usize;
     let mut group = c.benchmark_group("iir");
     group.throughput(criterion::Throughput::Elements(nsamps as u64));
 
     group.bench_function("iir", |b| {
-        bench_iir(b, 7, 1, nsamps);
+        bench_iir::<f32, f32>(b, 7, 1, nsamps);
     });
 
     group.finish();
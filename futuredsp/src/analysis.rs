@@ -0,0 +1,289 @@
+//! Analysis of FIR and IIR filters.
+//!
+//! The [FilterResponse] trait computes frequency, phase, group delay, impulse,
+//! and step responses. It is implemented for FIR taps (slices and vectors of
+//! `f32` or `f64`), for IIR filters in direct form ([TransferFunction]), and
+//! for cascades of second-order sections ([Sos]). Frequencies are given in
+//! cycles/sample.
+//!
+//! Example usage:
+//! ```
+//! use futuredsp::analysis::FilterResponse;
+//! use futuredsp::{firdes, windows};
+//!
+//! let taps = firdes::lowpass::<f32>(0.1, &windows::hamming(63, false));
+//! assert!(taps.magnitude_db(0.0).abs() < 0.1);
+//! assert!(taps.magnitude_db(0.2) < -50.0);
+//! // linear-phase filters delay all frequencies by half their length
+//! assert!((taps.group_delay(0.05) - 31.0).abs() < 1e-3);
+//! ```
+
+extern crate alloc;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+use crate::iirdes::Sos;
+
+/// Frequency and time-domain responses of a linear filter.
+pub trait FilterResponse {
+    /// Complex frequency response at frequency `f` (in cycles/sample).
+    fn frequency_response(&self, f: f64) -> Complex<f64>;
+
+    /// Group delay (in samples) at frequency `f` (in cycles/sample), i.e., the
+    /// negative derivative of the phase with respect to the angular frequency.
+    /// At zeros of the response, where the group delay is not defined, zero is
+    /// returned.
+    fn group_delay(&self, f: f64) -> f64;
+
+    /// The first `len` samples of the impulse response.
+    fn impulse_response(&self, len: usize) -> Vec<f64>;
+
+    /// Magnitude of the frequency response in dB.
+    fn magnitude_db(&self, f: f64) -> f64 {
+        20.0 * self.frequency_response(f).norm().log10()
+    }
+
+    /// Phase of the frequency response in radians, in `(-π, π]`. Use
+    /// [unwrap_phase] to get a continuous phase for a range of frequencies.
+    fn phase(&self, f: f64) -> f64 {
+        self.frequency_response(f).arg()
+    }
+
+    /// The first `len` samples of the step response.
+    fn step_response(&self, len: usize) -> Vec<f64> {
+        let mut sum = 0.0;
+        self.impulse_response(len)
+            .into_iter()
+            .map(|h| {
+                sum += h;
+                sum
+            })
+            .collect()
+    }
+}
+
+/// An IIR filter in direct form with the transfer function
+/// ```text
+///        b[0] + b[1] z^-1 + ... + b[n] z^-n
+/// H(z) = ----------------------------------
+///        a[0] + a[1] z^-1 + ... + a[m] z^-m
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TransferFunction<'a, T> {
+    /// Feed-forward coefficients.
+    pub b: &'a [T],
+    /// Feedback coefficients, starting with `a[0]`.
+    pub a: &'a [T],
+}
+
+impl<'a, T> TransferFunction<'a, T> {
+    /// Creates a transfer function from its numerator `b` and denominator `a`
+    /// coefficients.
+    pub fn new(b: &'a [T], a: &'a [T]) -> Self {
+        Self { b, a }
+    }
+}
+
+/// Evaluates `Σ c[n] z^-n` and `Σ n c[n] z^-n` at `f`.
+fn polynomial<T: Copy + Into<f64>>(c: &[T], f: f64) -> (Complex<f64>, Complex<f64>) {
+    let w = -2.0 * core::f64::consts::PI * f;
+    c.iter().enumerate().fold(
+        (Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)),
+        |(sum, ramp), (n, c)| {
+            let v = Complex::from_polar((*c).into(), w * n as f64);
+            (sum + v, ramp + v * n as f64)
+        },
+    )
+}
+
+/// Group delay of the polynomial `Σ c[n] z^-n`.
+fn polynomial_group_delay<T: Copy + Into<f64>>(c: &[T], f: f64) -> f64 {
+    let (sum, ramp) = polynomial(c, f);
+    if sum.norm() < 1e-12 * ramp.norm().max(1.0) {
+        0.0
+    } else {
+        (ramp / sum).re
+    }
+}
+
+/// Runs the difference equation of `b / a` on a unit impulse.
+fn filter_impulse<T: Copy + Into<f64>>(b: &[T], a: &[T], len: usize) -> Vec<f64> {
+    assert!(!a.is_empty() && a[0].into() != 0.0, "a[0] must not be zero");
+    let a0 = a[0].into();
+    let mut y: Vec<f64> = Vec::with_capacity(len);
+    for n in 0..len {
+        let mut v = b.get(n).map_or(0.0, |b| (*b).into());
+        for (k, a) in a.iter().enumerate().skip(1).take(n) {
+            v -= (*a).into() * y[n - k];
+        }
+        y.push(v / a0);
+    }
+    y
+}
+
+impl<T: Copy + Into<f64>> FilterResponse for [T] {
+    fn frequency_response(&self, f: f64) -> Complex<f64> {
+        polynomial(self, f).0
+    }
+
+    fn group_delay(&self, f: f64) -> f64 {
+        polynomial_group_delay(self, f)
+    }
+
+    fn impulse_response(&self, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|n| self.get(n).map_or(0.0, |h| (*h).into()))
+            .collect()
+    }
+}
+
+impl<T: Copy + Into<f64>> FilterResponse for TransferFunction<'_, T> {
+    fn frequency_response(&self, f: f64) -> Complex<f64> {
+        polynomial(self.b, f).0 / polynomial(self.a, f).0
+    }
+
+    fn group_delay(&self, f: f64) -> f64 {
+        polynomial_group_delay(self.b, f) - polynomial_group_delay(self.a, f)
+    }
+
+    fn impulse_response(&self, len: usize) -> Vec<f64> {
+        filter_impulse(self.b, self.a, len)
+    }
+}
+
+impl FilterResponse for Sos {
+    fn frequency_response(&self, f: f64) -> Complex<f64> {
+        TransferFunction::new(&self.b, &self.a).frequency_response(f)
+    }
+
+    fn group_delay(&self, f: f64) -> f64 {
+        TransferFunction::new(&self.b, &self.a).group_delay(f)
+    }
+
+    fn impulse_response(&self, len: usize) -> Vec<f64> {
+        filter_impulse(&self.b, &self.a, len)
+    }
+}
+
+impl FilterResponse for [Sos] {
+    fn frequency_response(&self, f: f64) -> Complex<f64> {
+        self.iter()
+            .map(|s| s.frequency_response(f))
+            .fold(Complex::new(1.0, 0.0), |acc, h| acc * h)
+    }
+
+    fn group_delay(&self, f: f64) -> f64 {
+        self.iter().map(|s| s.group_delay(f)).sum()
+    }
+
+    fn impulse_response(&self, len: usize) -> Vec<f64> {
+        let mut x = vec![0.0; len];
+        if len > 0 {
+            x[0] = 1.0;
+        }
+        for s in self {
+            let h = s.impulse_response(len);
+            // convolve the input with the impulse response of the section
+            x = (0..len)
+                .map(|n| (0..=n).map(|k| h[k] * x[n - k]).sum())
+                .collect();
+        }
+        x
+    }
+}
+
+/// Removes jumps of more than `π` between consecutive phase values (in
+/// radians) by adding multiples of `2π`.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::analysis::{unwrap_phase, FilterResponse};
+///
+/// let taps = [1.0f64; 8];
+/// let mut phase: Vec<f64> = (0..100).map(|i| taps.phase(0.1 * i as f64 / 100.0)).collect();
+/// unwrap_phase(&mut phase);
+/// ```
+pub fn unwrap_phase(phase: &mut [f64]) {
+    let two_pi = 2.0 * core::f64::consts::PI;
+    let mut offset = 0.0;
+    for i in 1..phase.len() {
+        let prev = phase[i - 1];
+        let mut diff = phase[i] + offset - prev;
+        while diff > core::f64::consts::PI {
+            offset -= two_pi;
+            diff -= two_pi;
+        }
+        while diff < -core::f64::consts::PI {
+            offset += two_pi;
+            diff += two_pi;
+        }
+        phase[i] += offset;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iirdes::{self, BandType};
+
+    #[test]
+    fn fir_responses() {
+        let taps = [1.0f32, 2.0, 1.0];
+        assert!((taps.frequency_response(0.0) - Complex::new(4.0, 0.0)).norm() < 1e-12);
+        assert!(taps.frequency_response(0.5).norm() < 1e-12);
+        assert!((taps.magnitude_db(0.0) - 20.0 * 4f64.log10()).abs() < 1e-12);
+        assert!((taps.group_delay(0.1) - 1.0).abs() < 1e-12);
+        assert!((taps.phase(0.1) + 2.0 * core::f64::consts::PI * 0.1).abs() < 1e-12);
+        assert_eq!(taps.impulse_response(4), vec![1.0, 2.0, 1.0, 0.0]);
+        assert_eq!(taps.step_response(4), vec![1.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn transfer_function() {
+        // one-pole lowpass y[n] = x[n] + 0.5 y[n-1]
+        let (b, a) = ([1.0f64], [1.0f64, -0.5]);
+        let tf = TransferFunction::new(&b, &a);
+        assert!((tf.frequency_response(0.0).re - 2.0).abs() < 1e-12);
+        assert!((tf.frequency_response(0.5).re - 2.0 / 3.0).abs() < 1e-12);
+        // group delay at DC: a / (1 - a)
+        assert!((tf.group_delay(0.0) - 1.0).abs() < 1e-12);
+        assert_eq!(tf.impulse_response(4), vec![1.0, 0.5, 0.25, 0.125]);
+        assert_eq!(tf.step_response(3), vec![1.0, 1.5, 1.75]);
+    }
+
+    #[test]
+    fn sos_cascade() {
+        let sections = iirdes::butterworth(5, BandType::Lowpass(0.15));
+        let h = sections.impulse_response(64);
+
+        // frequency response from the impulse response
+        let f = 0.12;
+        let direct = h.as_slice().frequency_response(f);
+        assert!((sections.frequency_response(f) - direct).norm() < 1e-6);
+
+        // group delay as derivative of the phase
+        let df = 1e-6;
+        let mut phase = [sections.phase(f - df), sections.phase(f + df)];
+        unwrap_phase(&mut phase);
+        let numeric = -(phase[1] - phase[0]) / (2.0 * core::f64::consts::PI * 2.0 * df);
+        assert!((sections.group_delay(f) - numeric).abs() < 1e-4);
+
+        // unit DC gain
+        let step = sections.step_response(200);
+        assert!((step[199] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unwrap() {
+        let mut phase: Vec<f64> = (0..50)
+            .map(|i| {
+                let p = -0.4 * i as f64;
+                Complex::from_polar(1.0, p).arg()
+            })
+            .collect();
+        unwrap_phase(&mut phase);
+        for (i, p) in phase.iter().enumerate() {
+            assert!((p + 0.4 * i as f64).abs() < 1e-12);
+        }
+    }
+}
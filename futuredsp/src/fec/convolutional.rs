@@ -0,0 +1,251 @@
+//! Convolutional codes with soft-decision Viterbi decoding.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// A rate `1/n` convolutional code with constraint length `k`, optionally
+/// punctured to a higher rate.
+///
+/// Bits are handled unpacked, i.e., one bit per `u8`. Blocks are terminated
+/// with `k - 1` zero tail bits, so that the decoder ends in the zero state.
+///
+/// The decoder expects log-likelihood ratios, where positive values favor a
+/// zero bit. Punctured bits are treated as erasures.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::fec::ConvolutionalCode;
+///
+/// let code = ConvolutionalCode::k7_rate_1_2().punctured(ConvolutionalCode::RATE_3_4.to_vec());
+///
+/// let bits = vec![1, 0, 1, 1, 0, 0, 1, 0];
+/// let coded = code.encode(&bits);
+/// let llrs: Vec<f32> = coded.iter().map(|b| if *b == 0 { 1.0 } else { -1.0 }).collect();
+/// assert_eq!(code.decode(&llrs), bits);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConvolutionalCode {
+    k: usize,
+    polys: Vec<u32>,
+    puncturing: Option<Vec<bool>>,
+}
+
+impl ConvolutionalCode {
+    /// Puncturing pattern for rate 2/3 from the rate 1/2 mother code.
+    pub const RATE_2_3: [bool; 4] = [true, true, false, true];
+    /// Puncturing pattern for rate 3/4 from the rate 1/2 mother code.
+    pub const RATE_3_4: [bool; 6] = [true, true, false, true, true, false];
+    /// Puncturing pattern for rate 5/6 from the rate 1/2 mother code.
+    pub const RATE_5_6: [bool; 10] = [
+        true, true, false, true, true, false, false, true, true, false,
+    ];
+
+    /// Create a code with constraint length `k` and one generator polynomial
+    /// per output bit. The least significant bit of a polynomial taps the
+    /// most recent input bit.
+    pub fn new(k: usize, polys: Vec<u32>) -> Self {
+        assert!((2..=16).contains(&k), "k must be in [2, 16]");
+        assert!(!polys.is_empty(), "at least one polynomial is required");
+        Self {
+            k,
+            polys,
+            puncturing: None,
+        }
+    }
+
+    /// The standard `k = 7`, rate 1/2 code with polynomials 171 and 133 (octal).
+    pub fn k7_rate_1_2() -> Self {
+        Self::new(7, vec![0o171, 0o133])
+    }
+
+    /// Puncture the output of the encoder with the given pattern, where
+    /// `false` marks a bit that is not transmitted.
+    #[must_use]
+    pub fn punctured(mut self, pattern: Vec<bool>) -> Self {
+        assert!(
+            pattern.iter().any(|x| *x),
+            "pattern must keep at least one bit"
+        );
+        self.puncturing = Some(pattern);
+        self
+    }
+
+    fn outputs(&self, reg: u32) -> impl Iterator<Item = u8> + '_ {
+        self.polys
+            .iter()
+            .map(move |p| ((reg & p).count_ones() & 1) as u8)
+    }
+
+    /// Encode `bits` and append the tail.
+    pub fn encode(&self, bits: &[u8]) -> Vec<u8> {
+        let mask = (1u32 << self.k) - 1;
+        let mut reg = 0u32;
+        let mut out = Vec::with_capacity((bits.len() + self.k - 1) * self.polys.len());
+        let tail = [0u8; 16];
+        for b in bits.iter().chain(tail[..self.k - 1].iter()) {
+            reg = ((reg << 1) | (*b & 1) as u32) & mask;
+            out.extend(self.outputs(reg));
+        }
+
+        match &self.puncturing {
+            Some(pattern) => out
+                .into_iter()
+                .zip(pattern.iter().cycle())
+                .filter(|(_, keep)| **keep)
+                .map(|(b, _)| b)
+                .collect(),
+            None => out,
+        }
+    }
+
+    fn depuncture(&self, llrs: &[f32]) -> Vec<f32> {
+        match &self.puncturing {
+            Some(pattern) => {
+                let n = self.polys.len();
+                let mut out = Vec::new();
+                let mut erasures = 0;
+                let mut input = llrs.iter();
+                for keep in pattern.iter().cycle() {
+                    if *keep {
+                        match input.next() {
+                            Some(l) => {
+                                out.resize(out.len() + erasures, 0.0);
+                                out.push(*l);
+                                erasures = 0;
+                            }
+                            None => break,
+                        }
+                    } else {
+                        erasures += 1;
+                    }
+                }
+                // complete the last symbol, if its final bits were punctured
+                let rem = out.len() % n;
+                if rem != 0 {
+                    out.resize(out.len() + n - rem, 0.0);
+                }
+                out
+            }
+            None => llrs.to_vec(),
+        }
+    }
+
+    /// Decode a terminated block of LLRs, returning the information bits.
+    pub fn decode(&self, llrs: &[f32]) -> Vec<u8> {
+        let llrs = self.depuncture(llrs);
+        let n = self.polys.len();
+        let steps = llrs.len() / n;
+        if steps < self.k - 1 {
+            return Vec::new();
+        }
+
+        let n_states = 1usize << (self.k - 1);
+        let state_mask = n_states - 1;
+
+        // expected encoder output for every register value
+        let outputs: Vec<Vec<f32>> = (0..(n_states << 1))
+            .map(|reg| {
+                self.outputs(reg as u32)
+                    .map(|b| if b == 0 { 1.0 } else { -1.0 })
+                    .collect()
+            })
+            .collect();
+
+        let mut metrics = vec![f32::MIN; n_states];
+        metrics[0] = 0.0;
+        let mut next = vec![0.0f32; n_states];
+        let mut decisions = vec![0u8; steps * n_states];
+
+        for (step, symbol) in llrs.chunks_exact(n).enumerate() {
+            for (ns, m) in next.iter_mut().enumerate() {
+                let mut best = f32::MIN;
+                let mut decision = 0;
+                for msb in 0..2 {
+                    let reg = (msb << (self.k - 1)) | ns;
+                    let prev = reg >> 1;
+                    let branch: f32 = outputs[reg]
+                        .iter()
+                        .zip(symbol.iter())
+                        .map(|(o, l)| o * l)
+                        .sum();
+                    let candidate = metrics[prev] + branch;
+                    if candidate > best {
+                        best = candidate;
+                        decision = msb as u8;
+                    }
+                }
+                *m = best;
+                decisions[step * n_states + ns] = decision;
+            }
+            core::mem::swap(&mut metrics, &mut next);
+        }
+
+        // trace back from the zero state
+        let mut bits = vec![0u8; steps];
+        let mut state = 0usize;
+        for step in (0..steps).rev() {
+            bits[step] = (state & 1) as u8;
+            let msb = decisions[step * n_states + state] as usize;
+            state = (((msb << (self.k - 1)) | state) >> 1) & state_mask;
+        }
+
+        bits.truncate(steps - (self.k - 1));
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_llrs(bits: &[u8]) -> Vec<f32> {
+        bits.iter()
+            .map(|b| if *b == 0 { 1.0 } else { -1.0 })
+            .collect()
+    }
+
+    fn bits(n: usize) -> Vec<u8> {
+        (0..n).map(|i| ((i * 7 + i / 3) % 5 < 2) as u8).collect()
+    }
+
+    #[test]
+    fn encode_impulse() {
+        let code = ConvolutionalCode::k7_rate_1_2();
+        let coded = code.encode(&[1]);
+        assert_eq!(coded.len(), 14);
+        // the impulse response interleaves the generator polynomials
+        for i in 0..7 {
+            assert_eq!(coded[2 * i] as u32, (0o171 >> i) & 1);
+            assert_eq!(coded[2 * i + 1] as u32, (0o133 >> i) & 1);
+        }
+    }
+
+    #[test]
+    fn decode_errors() {
+        let code = ConvolutionalCode::k7_rate_1_2();
+        let data = bits(200);
+        let mut llrs = to_llrs(&code.encode(&data));
+        assert_eq!(llrs.len(), 2 * (200 + 6));
+        for i in (5..llrs.len()).step_by(23) {
+            llrs[i] = -llrs[i];
+        }
+        assert_eq!(code.decode(&llrs), data);
+    }
+
+    #[test]
+    fn decode_punctured() {
+        for pattern in [
+            ConvolutionalCode::RATE_2_3.to_vec(),
+            ConvolutionalCode::RATE_3_4.to_vec(),
+            ConvolutionalCode::RATE_5_6.to_vec(),
+        ] {
+            let code = ConvolutionalCode::k7_rate_1_2().punctured(pattern);
+            let data = bits(300);
+            let coded = code.encode(&data);
+            assert!(coded.len() < 2 * 306);
+            let mut llrs = to_llrs(&coded);
+            llrs[40] = -llrs[40];
+            assert_eq!(code.decode(&llrs), data);
+        }
+    }
+}
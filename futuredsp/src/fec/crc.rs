@@ -0,0 +1,175 @@
+//! Table-driven cyclic redundancy checks.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// A CRC with a width of 8 to 32 bits, described by the usual Rocksoft
+/// parameters (`poly`, `init`, `refin`, `refout`, `xorout`).
+///
+/// [Crc::append] adds the checksum to a frame, least significant byte first
+/// for reflected CRCs and most significant byte first otherwise.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::fec::Crc;
+///
+/// let crc = Crc::crc32();
+/// assert_eq!(crc.checksum(b"123456789"), 0xcbf43926);
+///
+/// let mut frame = b"hello".to_vec();
+/// crc.append(&mut frame);
+/// assert!(crc.check(&frame));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Crc {
+    width: u32,
+    init: u32,
+    refin: bool,
+    refout: bool,
+    xorout: u32,
+    table: [u32; 256],
+}
+
+impl Crc {
+    /// Create a CRC with the given parameters. `width` must be in `[8, 32]`.
+    pub fn new(width: u32, poly: u32, init: u32, refin: bool, refout: bool, xorout: u32) -> Self {
+        assert!((8..=32).contains(&width), "width must be in [8, 32]");
+        let mask = Self::mask_for(width);
+        let top = 1u32 << (width - 1);
+
+        let mut table = [0u32; 256];
+        for (byte, entry) in table.iter_mut().enumerate() {
+            let mut reg = (byte as u32) << (width - 8);
+            for _ in 0..8 {
+                reg = if reg & top != 0 {
+                    (reg << 1) ^ poly
+                } else {
+                    reg << 1
+                };
+            }
+            *entry = reg & mask;
+        }
+
+        Self {
+            width,
+            init: init & mask,
+            refin,
+            refout,
+            xorout: xorout & mask,
+            table,
+        }
+    }
+
+    /// CRC-8 (polynomial 0x07).
+    pub fn crc8() -> Self {
+        Self::new(8, 0x07, 0x00, false, false, 0x00)
+    }
+
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, init 0xffff).
+    pub fn crc16_ccitt() -> Self {
+        Self::new(16, 0x1021, 0xffff, false, false, 0x0000)
+    }
+
+    /// CRC-16/KERMIT, the frame check sequence of IEEE 802.15.4.
+    pub fn crc16_kermit() -> Self {
+        Self::new(16, 0x1021, 0x0000, true, true, 0x0000)
+    }
+
+    /// CRC-32 as used by Ethernet, zip, and PNG.
+    pub fn crc32() -> Self {
+        Self::new(32, 0x04c1_1db7, 0xffff_ffff, true, true, 0xffff_ffff)
+    }
+
+    fn mask_for(width: u32) -> u32 {
+        if width == 32 {
+            u32::MAX
+        } else {
+            (1 << width) - 1
+        }
+    }
+
+    fn reflect(mut x: u32, bits: u32) -> u32 {
+        let mut r = 0;
+        for _ in 0..bits {
+            r = (r << 1) | (x & 1);
+            x >>= 1;
+        }
+        r
+    }
+
+    /// Number of checksum bytes.
+    pub fn num_bytes(&self) -> usize {
+        self.width.div_ceil(8) as usize
+    }
+
+    /// Compute the checksum of `data`.
+    pub fn checksum(&self, data: &[u8]) -> u32 {
+        let mask = Self::mask_for(self.width);
+        let mut reg = self.init;
+        for b in data {
+            let b = if self.refin { b.reverse_bits() } else { *b };
+            let idx = ((reg >> (self.width - 8)) as u8 ^ b) as usize;
+            reg = ((reg << 8) ^ self.table[idx]) & mask;
+        }
+        if self.refout {
+            reg = Self::reflect(reg, self.width);
+        }
+        reg ^ self.xorout
+    }
+
+    fn to_bytes(&self, crc: u32) -> Vec<u8> {
+        let n = self.num_bytes();
+        if self.refout {
+            crc.to_le_bytes()[..n].to_vec()
+        } else {
+            crc.to_be_bytes()[4 - n..].to_vec()
+        }
+    }
+
+    /// Append the checksum of `data` to `data`.
+    pub fn append(&self, data: &mut Vec<u8>) {
+        let crc = self.checksum(data);
+        data.extend_from_slice(&self.to_bytes(crc));
+    }
+
+    /// Check a frame that ends with its checksum, as produced by [Crc::append].
+    pub fn check(&self, frame: &[u8]) -> bool {
+        let n = self.num_bytes();
+        if frame.len() < n {
+            return false;
+        }
+        let (data, crc) = frame.split_at(frame.len() - n);
+        self.to_bytes(self.checksum(data)) == crc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_values() {
+        let data = b"123456789";
+        assert_eq!(Crc::crc8().checksum(data), 0xf4);
+        assert_eq!(Crc::crc16_ccitt().checksum(data), 0x29b1);
+        assert_eq!(Crc::crc16_kermit().checksum(data), 0x2189);
+        assert_eq!(Crc::crc32().checksum(data), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn append_check() {
+        for crc in [
+            Crc::crc8(),
+            Crc::crc16_ccitt(),
+            Crc::crc16_kermit(),
+            Crc::crc32(),
+        ] {
+            let mut frame = b"futuredsp".to_vec();
+            crc.append(&mut frame);
+            assert_eq!(frame.len(), 9 + crc.num_bytes());
+            assert!(crc.check(&frame));
+            frame[3] ^= 0x10;
+            assert!(!crc.check(&frame));
+        }
+    }
+}
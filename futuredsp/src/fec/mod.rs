@@ -0,0 +1,9 @@
+//! Forward error correction and error detection codes.
+
+pub mod convolutional;
+pub mod crc;
+pub mod reed_solomon;
+
+pub use convolutional::ConvolutionalCode;
+pub use crc::Crc;
+pub use reed_solomon::ReedSolomon;
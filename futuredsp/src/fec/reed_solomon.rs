@@ -0,0 +1,299 @@
+//! Reed-Solomon codes over GF(256).
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// A systematic Reed-Solomon code over GF(256) with `n - k` parity bytes.
+///
+/// The field is generated by the primitive polynomial `0x11d` with primitive
+/// element `2`, and the generator polynomial has its first consecutive root
+/// at `1`. Codes with `n < 255` are shortened codes, i.e., the leading data
+/// bytes of the full-length code are implicitly zero.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::fec::ReedSolomon;
+///
+/// let rs = ReedSolomon::rs_255_223();
+/// let data: Vec<u8> = (0..223).map(|i| i as u8).collect();
+///
+/// let mut codeword = rs.encode(&data);
+/// codeword[10] ^= 0xff;
+/// codeword[100] ^= 0x01;
+/// assert_eq!(rs.decode(&mut codeword), Some(2));
+/// assert_eq!(&codeword[..223], &data[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReedSolomon {
+    n: usize,
+    k: usize,
+    exp: [u8; 512],
+    log: [u8; 256],
+    /// Generator polynomial, highest degree first.
+    generator: Vec<u8>,
+}
+
+impl ReedSolomon {
+    /// Create an `(n, k)` code. `n` must not exceed 255 and `n - k` must be even.
+    pub fn new(n: usize, k: usize) -> Self {
+        assert!(n <= 255, "n must not exceed 255");
+        assert!(k > 0 && k < n, "k must be in [1, n)");
+        assert!((n - k) & 1 == 0, "n - k must be even");
+
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut x = 1u16;
+        for i in 0..255 {
+            exp[i] = x as u8;
+            exp[i + 255] = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11d;
+            }
+        }
+
+        let mut rs = Self {
+            n,
+            k,
+            exp,
+            log,
+            generator: vec![1],
+        };
+
+        // g(x) = (x - a^0) (x - a^1) ... (x - a^(n-k-1))
+        let mut generator = vec![1u8];
+        for i in 0..n - k {
+            let root = rs.exp[i];
+            let mut next = generator.clone();
+            next.push(0);
+            for (j, g) in generator.iter().enumerate() {
+                next[j + 1] ^= rs.mul(*g, root);
+            }
+            generator = next;
+        }
+        rs.generator = generator;
+        rs
+    }
+
+    /// The CCSDS/DVB outer code size, correcting up to 16 byte errors.
+    pub fn rs_255_223() -> Self {
+        Self::new(255, 223)
+    }
+
+    /// Codeword length in bytes.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of data bytes per codeword.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        assert!(b != 0, "division by zero");
+        if a == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + 255 - self.log[b as usize] as usize]
+        }
+    }
+
+    /// `a^i` for the primitive element `a`.
+    fn alpha(&self, i: usize) -> u8 {
+        self.exp[i % 255]
+    }
+
+    /// Evaluate a polynomial, given lowest degree first.
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter().rev().fold(0, |acc, c| self.mul(acc, x) ^ c)
+    }
+
+    /// Encode `k` data bytes into a codeword of `n` bytes, with the parity
+    /// bytes appended to the data.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        assert_eq!(data.len(), self.k, "data must have k bytes");
+        let nroots = self.n - self.k;
+        let mut parity = vec![0u8; nroots];
+        for d in data {
+            let feedback = d ^ parity[0];
+            parity.rotate_left(1);
+            parity[nroots - 1] = 0;
+            if feedback != 0 {
+                for (p, g) in parity.iter_mut().zip(self.generator[1..].iter()) {
+                    *p ^= self.mul(feedback, *g);
+                }
+            }
+        }
+
+        let mut out = Vec::with_capacity(self.n);
+        out.extend_from_slice(data);
+        out.extend_from_slice(&parity);
+        out
+    }
+
+    /// Correct a codeword of `n` bytes in place.
+    ///
+    /// Returns the number of corrected bytes or `None`, if the codeword could
+    /// not be corrected. In the latter case, the codeword is not modified.
+    pub fn decode(&self, codeword: &mut [u8]) -> Option<usize> {
+        assert_eq!(codeword.len(), self.n, "codeword must have n bytes");
+        let nroots = self.n - self.k;
+
+        // syndromes S_i = c(a^i), with c[0] the highest degree coefficient
+        let syndromes: Vec<u8> = (0..nroots)
+            .map(|i| {
+                let x = self.alpha(i);
+                codeword.iter().fold(0, |acc, c| self.mul(acc, x) ^ c)
+            })
+            .collect();
+        if syndromes.iter().all(|s| *s == 0) {
+            return Some(0);
+        }
+
+        // Berlekamp-Massey for the error locator, lowest degree first
+        let mut locator = vec![0u8; nroots + 1];
+        locator[0] = 1;
+        let mut prev = locator.clone();
+        let mut l = 0;
+        let mut m = 1;
+        let mut b = 1u8;
+        for r in 0..nroots {
+            let mut d = syndromes[r];
+            for i in 1..=l {
+                d ^= self.mul(locator[i], syndromes[r - i]);
+            }
+            if d == 0 {
+                m += 1;
+                continue;
+            }
+            let coef = self.div(d, b);
+            let old = locator.clone();
+            for i in 0..=nroots - m {
+                locator[i + m] ^= self.mul(coef, prev[i]);
+            }
+            if 2 * l <= r {
+                l = r + 1 - l;
+                prev = old;
+                b = d;
+                m = 1;
+            } else {
+                m += 1;
+            }
+        }
+        if 2 * l > nroots {
+            return None;
+        }
+        locator.truncate(l + 1);
+
+        // Chien search, an error at degree j has locator a^j
+        let positions: Vec<usize> = (0..self.n)
+            .filter(|j| self.eval(&locator, self.alpha(255 - j % 255)) == 0)
+            .collect();
+        if positions.len() != l {
+            return None;
+        }
+
+        // Forney, error evaluator W(x) = S(x) L(x) mod x^(n-k)
+        let mut evaluator = vec![0u8; nroots];
+        for (i, s) in syndromes.iter().enumerate() {
+            for (j, c) in locator.iter().enumerate().take(nroots - i) {
+                evaluator[i + j] ^= self.mul(*s, *c);
+            }
+        }
+        // formal derivative, only odd powers remain in characteristic 2
+        let derivative: Vec<u8> = locator
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| if i % 2 == 1 { *c } else { 0 })
+            .collect();
+
+        let mut errors = Vec::with_capacity(l);
+        for j in positions.iter() {
+            let x = self.alpha(*j);
+            let x_inv = self.alpha(255 - j % 255);
+            let denom = self.eval(&derivative, x_inv);
+            if denom == 0 {
+                return None;
+            }
+            let magnitude = self.mul(x, self.div(self.eval(&evaluator, x_inv), denom));
+            errors.push((self.n - 1 - j, magnitude));
+        }
+
+        for (pos, magnitude) in errors {
+            codeword[pos] ^= magnitude;
+        }
+        Some(l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(k: usize) -> Vec<u8> {
+        (0..k).map(|i| (i * 37 + 11) as u8).collect()
+    }
+
+    #[test]
+    fn encode_is_codeword() {
+        let rs = ReedSolomon::rs_255_223();
+        let mut codeword = rs.encode(&data(223));
+        assert_eq!(codeword.len(), 255);
+        assert_eq!(rs.decode(&mut codeword), Some(0));
+    }
+
+    #[test]
+    fn correct_errors() {
+        let rs = ReedSolomon::rs_255_223();
+        let data = data(223);
+        let codeword = rs.encode(&data);
+        for errors in [1, 5, 16] {
+            let mut received = codeword.clone();
+            for e in 0..errors {
+                received[e * 15 + 3] ^= (e as u8).wrapping_mul(29) | 1;
+            }
+            assert_eq!(rs.decode(&mut received), Some(errors));
+            assert_eq!(received, codeword);
+        }
+    }
+
+    #[test]
+    fn detect_uncorrectable() {
+        let rs = ReedSolomon::new(40, 30);
+        let codeword = rs.encode(&data(30));
+        let mut received = codeword.clone();
+        for e in 0..12 {
+            received[e * 3] ^= 0x5a;
+        }
+        let copy = received.clone();
+        if rs.decode(&mut received).is_none() {
+            assert_eq!(received, copy);
+        } else {
+            assert_ne!(received, codeword);
+        }
+    }
+
+    #[test]
+    fn shortened() {
+        let rs = ReedSolomon::new(60, 50);
+        let data = data(50);
+        let codeword = rs.encode(&data);
+        let mut received = codeword.clone();
+        received[0] ^= 0x80;
+        received[59] ^= 0x01;
+        received[30] ^= 0x42;
+        assert_eq!(rs.decode(&mut received), Some(3));
+        assert_eq!(received, codeword);
+    }
+}
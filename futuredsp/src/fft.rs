@@ -0,0 +1,138 @@
+//! A radix-2 fast Fourier transform.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+/// An in-place, radix-2 FFT for power-of-two lengths.
+///
+/// Twiddle factors and the bit-reversal permutation are precomputed, so that
+/// repeated transforms of the same length do not allocate. Neither transform
+/// is normalized, i.e., `inverse(forward(x)) = len * x`.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::fft::Fft;
+/// use num_complex::Complex;
+///
+/// let fft = Fft::new(4);
+/// let mut x = [Complex::new(1.0, 0.0); 4];
+/// fft.forward(&mut x);
+/// assert_eq!(x[0], Complex::new(4.0, 0.0));
+/// assert_eq!(x[1], Complex::new(0.0, 0.0));
+/// ```
+pub struct Fft {
+    len: usize,
+    twiddles: Vec<Complex<f32>>,
+    permutation: Vec<(usize, usize)>,
+}
+
+impl Fft {
+    /// Create an FFT of the given length, which has to be a power of two.
+    pub fn new(len: usize) -> Self {
+        assert!(len.is_power_of_two(), "FFT length must be a power of two");
+        let twiddles = (0..len / 2)
+            .map(|k| {
+                let phi = -2.0 * core::f64::consts::PI * k as f64 / len as f64;
+                Complex::new(phi.cos() as f32, phi.sin() as f32)
+            })
+            .collect();
+        let bits = len.trailing_zeros();
+        let permutation = (0..len)
+            .filter_map(|k| {
+                let r = k
+                    .reverse_bits()
+                    .checked_shr(usize::BITS - bits)
+                    .unwrap_or(0);
+                (k < r).then_some((k, r))
+            })
+            .collect();
+        Self {
+            len,
+            twiddles,
+            permutation,
+        }
+    }
+
+    /// Length of the transform.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the transform has length zero, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Forward transform, computed in place.
+    pub fn forward(&self, buffer: &mut [Complex<f32>]) {
+        self.process(buffer, false);
+    }
+
+    /// Inverse transform, computed in place and not normalized.
+    pub fn inverse(&self, buffer: &mut [Complex<f32>]) {
+        self.process(buffer, true);
+    }
+
+    fn process(&self, buffer: &mut [Complex<f32>], inverse: bool) {
+        assert_eq!(buffer.len(), self.len, "buffer does not match FFT length");
+        for &(a, b) in &self.permutation {
+            buffer.swap(a, b);
+        }
+
+        let mut half = 1;
+        while half < self.len {
+            let stride = self.len / (2 * half);
+            for chunk in buffer.chunks_exact_mut(2 * half) {
+                let (lo, hi) = chunk.split_at_mut(half);
+                for (k, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let w = self.twiddles[k * stride];
+                    let w = if inverse { w.conj() } else { w };
+                    let t = *b * w;
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+            half *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dft(x: &[Complex<f32>]) -> Vec<Complex<f32>> {
+        let n = x.len();
+        (0..n)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .map(|(i, x)| {
+                        let phi = -2.0 * core::f64::consts::PI * (i * k) as f64 / n as f64;
+                        x * Complex::new(phi.cos() as f32, phi.sin() as f32)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_dft() {
+        for len in [1, 2, 4, 8, 64, 256] {
+            let x: Vec<Complex<f32>> = (0..len)
+                .map(|i| Complex::new((i as f32 * 0.37).sin(), (i as f32 * 1.3).cos()))
+                .collect();
+            let mut y = x.clone();
+            let fft = Fft::new(len);
+            fft.forward(&mut y);
+            for (a, b) in y.iter().zip(dft(&x)) {
+                assert!((a - b).norm() < 1e-3);
+            }
+            fft.inverse(&mut y);
+            for (a, b) in y.iter().zip(x.iter()) {
+                assert!((a / len as f32 - b).norm() < 1e-5);
+            }
+        }
+    }
+}
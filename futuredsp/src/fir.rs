@@ -2,15 +2,27 @@ use core::cmp::Ordering;
 #[cfg(not(RUSTC_IS_STABLE))]
 use core::intrinsics::{fadd_fast, fmul_fast};
 
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::simd;
 use crate::{ComputationStatus, TapsAccessor, UnaryKernel};
 use num_complex::Complex;
 
+mod arbitrary_resampling;
+mod overlap_save;
+pub use arbitrary_resampling::ArbitraryResamplingFirKernel;
+pub use overlap_save::OverlapSaveFirKernel;
+
 /// A non-resampling FIR filter. Calling `work()` on this struct always
 /// produces exactly as many samples as it consumes.
 ///
 /// Implementations of this core exist for the following combinations:
 /// - `f32` samples, `f32` taps.
 /// - `Complex<f32>` samples, `f32` taps.
+/// - `Complex<f32>` samples, `Complex<f32>` taps.
+///
+/// All of them use SIMD instructions, if supported by the CPU (see [simd]).
 ///
 /// Example usage:
 /// ```
@@ -25,19 +37,96 @@ use num_complex::Complex;
 /// ```
 pub struct NonResamplingFirKernel<SampleType, TapsType: TapsAccessor> {
     taps: TapsType,
+    /// Contiguous copy of the taps in reversed order for the SIMD implementations.
+    reversed: Vec<TapsType::TapType>,
     _sampletype: core::marker::PhantomData<SampleType>,
 }
 
 impl<SampleType, TapsType: TapsAccessor> NonResamplingFirKernel<SampleType, TapsType> {
     /// Create a new non-resampling FIR filter using the given taps.
     pub fn new(taps: TapsType) -> Self {
+        let n = taps.num_taps();
+        let reversed = (0..n).map(|t| unsafe { taps.get(n - 1 - t) }).collect();
         Self {
             taps,
+            reversed,
             _sampletype: core::marker::PhantomData,
         }
     }
 }
 
+/// Reinterprets complex samples as interleaved real and imaginary parts.
+fn as_f32(s: &[Complex<f32>]) -> &[f32] {
+    // Complex is repr(C) and, therefore, laid out as [re, im]
+    unsafe { core::slice::from_raw_parts(s.as_ptr() as *const f32, 2 * s.len()) }
+}
+
+/// Number of output samples and status of the non-resampling kernel.
+fn fir_num_outputs(
+    num_taps: usize,
+    num_inputs: usize,
+    num_outputs: usize,
+) -> (usize, ComputationStatus) {
+    let num_producable_samples = (num_inputs + 1).saturating_sub(num_taps);
+    match num_producable_samples.cmp(&num_outputs) {
+        Ordering::Greater => (num_outputs, ComputationStatus::InsufficientOutput),
+        Ordering::Equal => (num_producable_samples, ComputationStatus::BothSufficient),
+        Ordering::Less => (num_producable_samples, ComputationStatus::InsufficientInput),
+    }
+}
+
+/// Runs the non-resampling kernel with real samples and real taps using SIMD
+/// instructions. Returns `None`, if no SIMD implementation is available.
+fn fir_simd_real(
+    taps: &[f32],
+    i: &[f32],
+    o: &mut [f32],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = taps.len();
+    let (n, status) = fir_num_outputs(num_taps, i.len(), o.len());
+    simd::dot_real_each(
+        n,
+        |k| (&i[k..k + num_taps], taps),
+        |k, v| unsafe { *o.get_unchecked_mut(k) = v },
+    )
+    .then_some((n, n, status))
+}
+
+/// Runs the non-resampling kernel with complex samples and real taps using
+/// SIMD instructions. Returns `None`, if no SIMD implementation is available.
+fn fir_simd_complex_real(
+    taps: &[f32],
+    i: &[Complex<f32>],
+    o: &mut [Complex<f32>],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = taps.len();
+    let (n, status) = fir_num_outputs(num_taps, i.len(), o.len());
+    simd::dot_complex_real_each(
+        n,
+        |k| (as_f32(&i[k..k + num_taps]), taps),
+        |k, re, im| unsafe { *o.get_unchecked_mut(k) = Complex { re, im } },
+    )
+    .then_some((n, n, status))
+}
+
+/// Runs the non-resampling kernel with complex samples and complex taps using
+/// SIMD instructions. Returns `None`, if no SIMD implementation is available.
+fn fir_simd_complex(
+    taps: &[Complex<f32>],
+    i: &[Complex<f32>],
+    o: &mut [Complex<f32>],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = taps.len();
+    let (n, status) = fir_num_outputs(num_taps, i.len(), o.len());
+    let taps = as_f32(taps);
+    simd::dot_complex_each(
+        n,
+        |k| (as_f32(&i[k..k + num_taps]), taps),
+        |k, re, im| unsafe { *o.get_unchecked_mut(k) = Complex { re, im } },
+    )
+    .then_some((n, n, status))
+}
+
 /// Internal helper function to abstract away everything but the core computation.
 /// Note that this function gets heavily inlined, so there is no (runtime) performance
 /// overhead.
@@ -57,12 +146,7 @@ where
     SampleType: Copy,
     TapsType::TapType: Copy,
 {
-    let num_producable_samples = (i.len() + 1).saturating_sub(taps.num_taps());
-    let (n, status) = match num_producable_samples.cmp(&o.len()) {
-        Ordering::Greater => (o.len(), ComputationStatus::InsufficientOutput),
-        Ordering::Equal => (num_producable_samples, ComputationStatus::BothSufficient),
-        Ordering::Less => (num_producable_samples, ComputationStatus::InsufficientInput),
-    };
+    let (n, status) = fir_num_outputs(taps.num_taps(), i.len(), o.len());
 
     unsafe {
         for k in 0..n {
@@ -86,6 +170,9 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<f32>
     for NonResamplingFirKernel<f32, TapsType>
 {
     fn work(&self, i: &[f32], o: &mut [f32]) -> (usize, usize, ComputationStatus) {
+        if let Some(r) = fir_simd_real(&self.reversed, i, o) {
+            return r;
+        }
         fir_kernel_core(
             &self.taps,
             i,
@@ -101,6 +188,9 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<f32>
     for NonResamplingFirKernel<f32, TapsType>
 {
     fn work(&self, i: &[f32], o: &mut [f32]) -> (usize, usize, ComputationStatus) {
+        if let Some(r) = fir_simd_real(&self.reversed, i, o) {
+            return r;
+        }
         fir_kernel_core(
             &self.taps,
             i,
@@ -111,46 +201,128 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<f32>
     }
 }
 
-#[cfg(not(RUSTC_IS_STABLE))]
-impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<Complex<f32>>
-    for NonResamplingFirKernel<Complex<f32>, TapsType>
-{
-    fn work(
-        &self,
+/// Taps that can filter complex samples, i.e., `f32` and `Complex<f32>`.
+pub trait ComplexFirTap: Copy + Send {
+    /// Multiply-accumulate of a complex sample with a tap.
+    fn mac(accum: Complex<f32>, sample: Complex<f32>, tap: Self) -> Complex<f32>;
+    /// Runs the non-resampling kernel with SIMD instructions, if available.
+    #[doc(hidden)]
+    fn fir_simd(
+        taps: &[Self],
         i: &[Complex<f32>],
         o: &mut [Complex<f32>],
-    ) -> (usize, usize, ComputationStatus) {
-        fir_kernel_core(
-            &self.taps,
-            i,
-            o,
-            || Complex { re: 0.0, im: 0.0 },
-            |accum, sample, tap| Complex {
-                re: unsafe { fadd_fast(accum.re, fmul_fast(sample.re, tap)) },
-                im: unsafe { fadd_fast(accum.im, fmul_fast(sample.im, tap)) },
-            },
-        )
+    ) -> Option<(usize, usize, ComputationStatus)>;
+    /// Runs the resampling kernel with SIMD instructions, if available.
+    #[doc(hidden)]
+    fn resampling_fir_simd(
+        interp: usize,
+        decim: usize,
+        banks: &[Self],
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> Option<(usize, usize, ComputationStatus)>;
+}
+
+impl ComplexFirTap for f32 {
+    #[cfg(not(RUSTC_IS_STABLE))]
+    #[inline(always)]
+    fn mac(accum: Complex<f32>, sample: Complex<f32>, tap: f32) -> Complex<f32> {
+        Complex {
+            re: unsafe { fadd_fast(accum.re, fmul_fast(sample.re, tap)) },
+            im: unsafe { fadd_fast(accum.im, fmul_fast(sample.im, tap)) },
+        }
+    }
+
+    #[cfg(RUSTC_IS_STABLE)]
+    #[inline(always)]
+    fn mac(accum: Complex<f32>, sample: Complex<f32>, tap: f32) -> Complex<f32> {
+        Complex {
+            re: accum.re + sample.re * tap,
+            im: accum.im + sample.im * tap,
+        }
+    }
+
+    fn fir_simd(
+        taps: &[f32],
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> Option<(usize, usize, ComputationStatus)> {
+        fir_simd_complex_real(taps, i, o)
+    }
+
+    fn resampling_fir_simd(
+        interp: usize,
+        decim: usize,
+        banks: &[f32],
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> Option<(usize, usize, ComputationStatus)> {
+        resampling_fir_simd_complex_real(interp, decim, banks, i, o)
     }
 }
 
-#[cfg(RUSTC_IS_STABLE)]
-impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<Complex<f32>>
+impl ComplexFirTap for Complex<f32> {
+    #[cfg(not(RUSTC_IS_STABLE))]
+    #[inline(always)]
+    fn mac(accum: Complex<f32>, sample: Complex<f32>, tap: Complex<f32>) -> Complex<f32> {
+        unsafe {
+            Complex {
+                re: fadd_fast(
+                    accum.re,
+                    fadd_fast(fmul_fast(sample.re, tap.re), -fmul_fast(sample.im, tap.im)),
+                ),
+                im: fadd_fast(
+                    accum.im,
+                    fadd_fast(fmul_fast(sample.re, tap.im), fmul_fast(sample.im, tap.re)),
+                ),
+            }
+        }
+    }
+
+    #[cfg(RUSTC_IS_STABLE)]
+    #[inline(always)]
+    fn mac(accum: Complex<f32>, sample: Complex<f32>, tap: Complex<f32>) -> Complex<f32> {
+        accum + sample * tap
+    }
+
+    fn fir_simd(
+        taps: &[Complex<f32>],
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> Option<(usize, usize, ComputationStatus)> {
+        fir_simd_complex(taps, i, o)
+    }
+
+    fn resampling_fir_simd(
+        interp: usize,
+        decim: usize,
+        banks: &[Complex<f32>],
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> Option<(usize, usize, ComputationStatus)> {
+        resampling_fir_simd_complex(interp, decim, banks, i, o)
+    }
+}
+
+impl<TapsType: TapsAccessor> UnaryKernel<Complex<f32>>
     for NonResamplingFirKernel<Complex<f32>, TapsType>
+where
+    TapsType::TapType: ComplexFirTap,
 {
     fn work(
         &self,
         i: &[Complex<f32>],
         o: &mut [Complex<f32>],
     ) -> (usize, usize, ComputationStatus) {
+        if let Some(r) = TapsType::TapType::fir_simd(&self.reversed, i, o) {
+            return r;
+        }
         fir_kernel_core(
             &self.taps,
             i,
             o,
             || Complex { re: 0.0, im: 0.0 },
-            |accum, sample, tap| Complex {
-                re: accum.re + sample.re * tap,
-                im: accum.im + sample.im * tap,
-            },
+            TapsType::TapType::mac,
         )
     }
 }
@@ -170,6 +342,9 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<Complex<f32>>
 /// Implementations of this core exist for the following combinations:
 /// - `f32` samples, `f32` taps.
 /// - `Complex<f32>` samples, `f32` taps.
+/// - `Complex<f32>` samples, `Complex<f32>` taps.
+///
+/// All of them use SIMD instructions, if supported by the CPU (see [simd]).
 ///
 /// Example usage:
 /// ```
@@ -189,6 +364,9 @@ pub struct PolyphaseResamplingFirKernel<SampleType, TapsType: TapsAccessor> {
     interp: usize,
     decim: usize,
     taps: TapsType,
+    /// Contiguous taps of the polyphase components in reversed order for the
+    /// SIMD implementations, one component after the other.
+    banks: Vec<TapsType::TapType>,
     _sampletype: core::marker::PhantomData<SampleType>,
 }
 
@@ -197,15 +375,126 @@ impl<SampleType, TapsType: TapsAccessor> PolyphaseResamplingFirKernel<SampleType
     pub fn new(interp: usize, decim: usize, taps: TapsType) -> Self {
         // Ensure number of taps is divisible by interp
         assert!(taps.num_taps() % interp == 0);
+        let num_taps = taps.num_taps() / interp;
+        let banks = (0..interp)
+            .flat_map(|b| (0..num_taps).map(move |t| (b, t)))
+            .map(|(b, t)| unsafe { taps.get(interp * (num_taps - 1 - t) + b) })
+            .collect();
         Self {
             interp,
             decim,
             taps,
+            banks,
             _sampletype: core::marker::PhantomData,
         }
     }
 }
 
+/// Number of consumed and produced samples and status of the resampling kernel.
+fn resampling_fir_num_outputs(
+    interp: usize,
+    decim: usize,
+    num_taps: usize,
+    num_inputs: usize,
+    num_outputs: usize,
+) -> (usize, usize, ComputationStatus) {
+    let num_producable_samples =
+        ((num_inputs + 1).saturating_sub(num_taps) * interp).saturating_sub(1) / decim;
+    // Ensure it is divisible by interpolation factor to avoid keeping track of state
+    let num_producable_samples = (num_producable_samples / interp) * interp;
+    let (num_producable_samples, status) = match num_producable_samples.cmp(&num_outputs) {
+        Ordering::Greater => (
+            (num_outputs / interp) * interp,
+            ComputationStatus::InsufficientOutput,
+        ),
+        Ordering::Equal => (num_producable_samples, ComputationStatus::BothSufficient),
+        Ordering::Less => (num_producable_samples, ComputationStatus::InsufficientInput),
+    };
+    // Compute number of input samples to consume
+    //let n = num_producable_samples.saturating_sub(1) * decim / interp + 1;
+    let n = (num_producable_samples / interp) * decim;
+    // Assert state is 0 so that we do not need to keep track of the state
+    debug_assert!(((num_producable_samples * decim) % interp) == 0);
+
+    (n, num_producable_samples, status)
+}
+
+/// Runs the resampling kernel with real samples and real taps using SIMD
+/// instructions. Returns `None`, if no SIMD implementation is available.
+fn resampling_fir_simd_real(
+    interp: usize,
+    decim: usize,
+    banks: &[f32],
+    i: &[f32],
+    o: &mut [f32],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = banks.len() / interp;
+    let (n, m, status) = resampling_fir_num_outputs(interp, decim, num_taps, i.len(), o.len());
+    simd::dot_real_each(
+        m,
+        |k| {
+            let bank = (k * decim) % interp * num_taps;
+            let input = k * decim / interp;
+            (&i[input..input + num_taps], &banks[bank..bank + num_taps])
+        },
+        |k, v| unsafe { *o.get_unchecked_mut(k) = v },
+    )
+    .then_some((n, m, status))
+}
+
+/// Runs the resampling kernel with complex samples and real taps using SIMD
+/// instructions. Returns `None`, if no SIMD implementation is available.
+fn resampling_fir_simd_complex_real(
+    interp: usize,
+    decim: usize,
+    banks: &[f32],
+    i: &[Complex<f32>],
+    o: &mut [Complex<f32>],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = banks.len() / interp;
+    let (n, m, status) = resampling_fir_num_outputs(interp, decim, num_taps, i.len(), o.len());
+    simd::dot_complex_real_each(
+        m,
+        |k| {
+            let bank = (k * decim) % interp * num_taps;
+            let input = k * decim / interp;
+            (
+                as_f32(&i[input..input + num_taps]),
+                &banks[bank..bank + num_taps],
+            )
+        },
+        |k, re, im| unsafe { *o.get_unchecked_mut(k) = Complex { re, im } },
+    )
+    .then_some((n, m, status))
+}
+
+/// Runs the resampling kernel with complex samples and complex taps using SIMD
+/// instructions. Returns `None`, if no SIMD implementation is available.
+fn resampling_fir_simd_complex(
+    interp: usize,
+    decim: usize,
+    banks: &[Complex<f32>],
+    i: &[Complex<f32>],
+    o: &mut [Complex<f32>],
+) -> Option<(usize, usize, ComputationStatus)> {
+    let num_taps = banks.len() / interp;
+    let (n, m, status) = resampling_fir_num_outputs(interp, decim, num_taps, i.len(), o.len());
+    let banks = as_f32(banks);
+    simd::dot_complex_each(
+        m,
+        |k| {
+            let bank = (k * decim) % interp * 2 * num_taps;
+            let input = k * decim / interp;
+            (
+                as_f32(&i[input..input + num_taps]),
+                &banks[bank..bank + 2 * num_taps],
+            )
+        },
+        |k, re, im| unsafe { *o.get_unchecked_mut(k) = Complex { re, im } },
+    )
+    .then_some((n, m, status))
+}
+
 /// Internal helper function to abstract away everything but the core computation.
 /// Note that this function gets heavily inlined, so there is no (runtime) performance
 /// overhead.
@@ -229,21 +518,8 @@ where
 {
     // Assume same number of taps in all filters
     let num_taps = taps.num_taps() / interp;
-    let num_producable_samples =
-        ((i.len() + 1).saturating_sub(num_taps) * interp).saturating_sub(1) / decim;
-    // Ensure it is divisible by interpolation factor to avoid keeping track of state
-    let num_producable_samples = (num_producable_samples / interp) * interp;
-    let (num_producable_samples, status) = match num_producable_samples.cmp(&o.len()) {
-        Ordering::Greater => (
-            (o.len() / interp) * interp,
-            ComputationStatus::InsufficientOutput,
-        ),
-        Ordering::Equal => (num_producable_samples, ComputationStatus::BothSufficient),
-        Ordering::Less => (num_producable_samples, ComputationStatus::InsufficientInput),
-    };
-    // Compute number of input samples to consume
-    //let n = num_producable_samples.saturating_sub(1) * decim / interp + 1;
-    let n = (num_producable_samples / interp) * decim;
+    let (n, num_producable_samples, status) =
+        resampling_fir_num_outputs(interp, decim, num_taps, i.len(), o.len());
 
     unsafe {
         for k in 0..num_producable_samples {
@@ -257,8 +533,6 @@ where
             *o.get_unchecked_mut(k) = sum;
         }
     }
-    // Assert state is 0 so that we do not need to keep track of the state
-    debug_assert!(((num_producable_samples * decim) % interp) == 0);
 
     (n, num_producable_samples, status)
 }
@@ -267,6 +541,9 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<f32>
     for PolyphaseResamplingFirKernel<f32, TapsType>
 {
     fn work(&self, i: &[f32], o: &mut [f32]) -> (usize, usize, ComputationStatus) {
+        if let Some(r) = resampling_fir_simd_real(self.interp, self.decim, &self.banks, i, o) {
+            return r;
+        }
         resampling_fir_kernel_core(
             self.interp,
             self.decim,
@@ -279,14 +556,21 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<f32>
     }
 }
 
-impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<Complex<f32>>
+impl<TapsType: TapsAccessor> UnaryKernel<Complex<f32>>
     for PolyphaseResamplingFirKernel<Complex<f32>, TapsType>
+where
+    TapsType::TapType: ComplexFirTap,
 {
     fn work(
         &self,
         i: &[Complex<f32>],
         o: &mut [Complex<f32>],
     ) -> (usize, usize, ComputationStatus) {
+        if let Some(r) =
+            TapsType::TapType::resampling_fir_simd(self.interp, self.decim, &self.banks, i, o)
+        {
+            return r;
+        }
         resampling_fir_kernel_core(
             self.interp,
             self.decim,
@@ -294,10 +578,7 @@ impl<TapsType: TapsAccessor<TapType = f32>> UnaryKernel<Complex<f32>>
             i,
             o,
             || Complex { re: 0.0, im: 0.0 },
-            |accum, sample, tap| Complex {
-                re: accum.re + sample.re * tap,
-                im: accum.im + sample.im * tap,
-            },
+            TapsType::TapType::mac,
         )
     }
 }
@@ -454,4 +735,200 @@ mod tests {
         assert_eq!(output[0], 4.0);
         assert_eq!(output[1], 13.0);
     }
+
+    /// Deterministic test signal in `[-1, 1)`.
+    fn signal(n: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    fn complex_signal(n: usize, seed: u32) -> Vec<Complex<f32>> {
+        signal(2 * n, seed)
+            .chunks(2)
+            .map(|c| Complex::new(c[0], c[1]))
+            .collect()
+    }
+
+    fn assert_close(a: Complex<f32>, b: Complex<f32>) {
+        assert!((a - b).norm() < 1e-4, "{} != {}", a, b);
+    }
+
+    /// Runs the scalar and, if available, the SIMD implementation of a kernel.
+    /// Checks that both consume and produce the same number of samples and
+    /// returns the number of outputs and the output buffers.
+    fn run_both<T: Clone>(
+        o: &[T],
+        scalar: impl FnOnce(&mut [T]) -> (usize, usize, ComputationStatus),
+        simd: impl FnOnce(&mut [T]) -> Option<(usize, usize, ComputationStatus)>,
+    ) -> (usize, Vec<Vec<T>>) {
+        let mut s = o.to_vec();
+        let r = scalar(&mut s);
+        let mut outputs = vec![s];
+        let mut v = o.to_vec();
+        if let Some(simd_r) = simd(&mut v) {
+            assert_eq!(r, simd_r);
+            outputs.push(v);
+        }
+        (r.1, outputs)
+    }
+
+    /// Compares the kernels (SIMD and scalar) with a direct convolution for
+    /// all tap lengths up to the point where every SIMD tail path is taken.
+    /// Both implementations are called directly, since toggling
+    /// [simd::set_enabled] would affect tests running in parallel.
+    #[test]
+    fn simd_matches_reference() {
+        for num_taps in 1..40 {
+            let n = 50;
+            let rt = signal(num_taps, num_taps as u32);
+            let ct = complex_signal(num_taps, num_taps as u32 + 1);
+            let ri = signal(n + num_taps - 1, 7);
+            let ci = complex_signal(n + num_taps - 1, 8);
+
+            let kernel = NonResamplingFirKernel::<f32, _>::new(rt.clone());
+            let (m, outputs) = run_both(
+                &vec![0.0; n],
+                |o| fir_kernel_core(&kernel.taps, &ri, o, || 0.0, |a, s, t| a + s * t),
+                |o| fir_simd_real(&kernel.reversed, &ri, o),
+            );
+            assert_eq!(m, n);
+            for o in outputs {
+                for k in 0..n {
+                    let r: f32 = (0..num_taps)
+                        .map(|t| ri[k + t] * rt[num_taps - 1 - t])
+                        .sum();
+                    assert!((o[k] - r).abs() < 1e-4);
+                }
+            }
+
+            let kernel = NonResamplingFirKernel::<Complex<f32>, _>::new(rt.clone());
+            let (_, outputs) = run_both(
+                &vec![Complex::new(0.0, 0.0); n],
+                |o| fir_kernel_core(&kernel.taps, &ci, o, || Complex::new(0.0, 0.0), f32::mac),
+                |o| fir_simd_complex_real(&kernel.reversed, &ci, o),
+            );
+            for o in outputs {
+                for k in 0..n {
+                    let r: Complex<f32> = (0..num_taps)
+                        .map(|t| ci[k + t] * rt[num_taps - 1 - t])
+                        .sum();
+                    assert_close(o[k], r);
+                }
+            }
+
+            let kernel = NonResamplingFirKernel::<Complex<f32>, _>::new(ct.clone());
+            let (_, outputs) = run_both(
+                &vec![Complex::new(0.0, 0.0); n],
+                |o| {
+                    fir_kernel_core(
+                        &kernel.taps,
+                        &ci,
+                        o,
+                        || Complex::new(0.0, 0.0),
+                        Complex::<f32>::mac,
+                    )
+                },
+                |o| fir_simd_complex(&kernel.reversed, &ci, o),
+            );
+            for o in outputs {
+                for k in 0..n {
+                    let r: Complex<f32> = (0..num_taps)
+                        .map(|t| ci[k + t] * ct[num_taps - 1 - t])
+                        .sum();
+                    assert_close(o[k], r);
+                }
+            }
+
+            // polyphase with the taps split into three components
+            let (interp, decim) = (3, 2);
+            let rt = signal(interp * num_taps, 3);
+            let ct = complex_signal(interp * num_taps, 4);
+            let reference = |k: usize, tap: &dyn Fn(usize) -> Complex<f32>| {
+                let bank = (k * decim) % interp;
+                let input = k * decim / interp;
+                (0..num_taps)
+                    .map(|t| ci[input + t] * tap(interp * (num_taps - 1 - t) + bank))
+                    .sum::<Complex<f32>>()
+            };
+
+            let kernel = PolyphaseResamplingFirKernel::<f32, _>::new(interp, decim, rt.clone());
+            let (m, outputs) = run_both(
+                &vec![0.0; 3 * n],
+                |o| {
+                    resampling_fir_kernel_core(
+                        interp,
+                        decim,
+                        &kernel.taps,
+                        &ri,
+                        o,
+                        || 0.0,
+                        |a, s, t| a + s * t,
+                    )
+                },
+                |o| resampling_fir_simd_real(interp, decim, &kernel.banks, &ri, o),
+            );
+            assert!(m > 0);
+            for o in outputs {
+                for (k, o) in o.iter().enumerate().take(m) {
+                    let bank = (k * decim) % interp;
+                    let input = k * decim / interp;
+                    let r: f32 = (0..num_taps)
+                        .map(|t| ri[input + t] * rt[interp * (num_taps - 1 - t) + bank])
+                        .sum();
+                    assert!((o - r).abs() < 1e-4);
+                }
+            }
+
+            let kernel =
+                PolyphaseResamplingFirKernel::<Complex<f32>, _>::new(interp, decim, rt.clone());
+            let (m, outputs) = run_both(
+                &vec![Complex::new(0.0, 0.0); 3 * n],
+                |o| {
+                    resampling_fir_kernel_core(
+                        interp,
+                        decim,
+                        &kernel.taps,
+                        &ci,
+                        o,
+                        || Complex::new(0.0, 0.0),
+                        f32::mac,
+                    )
+                },
+                |o| resampling_fir_simd_complex_real(interp, decim, &kernel.banks, &ci, o),
+            );
+            for o in outputs {
+                for (k, o) in o.iter().enumerate().take(m) {
+                    assert_close(*o, reference(k, &|i| Complex::new(rt[i], 0.0)));
+                }
+            }
+
+            let kernel =
+                PolyphaseResamplingFirKernel::<Complex<f32>, _>::new(interp, decim, ct.clone());
+            let (m, outputs) = run_both(
+                &vec![Complex::new(0.0, 0.0); 3 * n],
+                |o| {
+                    resampling_fir_kernel_core(
+                        interp,
+                        decim,
+                        &kernel.taps,
+                        &ci,
+                        o,
+                        || Complex::new(0.0, 0.0),
+                        Complex::<f32>::mac,
+                    )
+                },
+                |o| resampling_fir_simd_complex(interp, decim, &kernel.banks, &ci, o),
+            );
+            for o in outputs {
+                for (k, o) in o.iter().enumerate().take(m) {
+                    assert_close(*o, reference(k, &|i| ct[i]));
+                }
+            }
+        }
+    }
 }
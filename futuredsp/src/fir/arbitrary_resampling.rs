@@ -0,0 +1,271 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+use num_complex::Complex;
+
+use crate::{ComputationStatus, StatefulUnaryKernel, TapsAccessor};
+
+/// A FIR filter that resamples by an arbitrary, real-valued ratio.
+///
+/// The prototype filter is designed for an interpolation by `num_filters` and
+/// split into `num_filters` polyphase arms, which compute the input signal at
+/// fractional delays of `1/num_filters` samples. Outputs between two arms are
+/// linearly interpolated (a first-order Farrow structure on top of the
+/// polyphase filter bank). Suitable prototype filters are designed by
+/// [firdes::kaiser::arbitrary_resampling](crate::firdes::kaiser::arbitrary_resampling).
+///
+/// The ratio can be changed at runtime with [set_rate](Self::set_rate). Since
+/// the number of produced samples depends on the fractional phase, the kernel
+/// is stateful.
+///
+/// Implementations of this core exist for `f32` and `Complex<f32>` samples
+/// with `f32` taps.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::StatefulUnaryKernel;
+/// use futuredsp::fir::ArbitraryResamplingFirKernel;
+/// use futuredsp::firdes;
+///
+/// let rate = 48_000.0 / 44_100.0;
+/// let taps = firdes::kaiser::arbitrary_resampling::<f32>(rate, 32, 0.001);
+/// let mut resampler = ArbitraryResamplingFirKernel::<f32>::new(rate, 32, taps);
+///
+/// let input = [1.0; 1000];
+/// let mut output = [0.0; 2000];
+/// let (consumed, produced, _) = resampler.work(&input, &mut output);
+/// assert!((produced as f64 - consumed as f64 * rate).abs() < 2.0);
+/// ```
+pub struct ArbitraryResamplingFirKernel<SampleType> {
+    rate: f64,
+    /// Polyphase arms, each reversed to be applied to the input window.
+    arms: Vec<Vec<f32>>,
+    /// Fractional position of the next output between two input samples.
+    phase: f64,
+    /// Input samples to skip before the next output.
+    skip: usize,
+    _sampletype: core::marker::PhantomData<SampleType>,
+}
+
+impl<SampleType> ArbitraryResamplingFirKernel<SampleType> {
+    /// Create a new resampler with the ratio `rate` (output rate / input rate)
+    /// and a prototype filter for an interpolation by `num_filters`.
+    pub fn new<Taps: TapsAccessor<TapType = f32>>(
+        rate: f64,
+        num_filters: usize,
+        taps: Taps,
+    ) -> Self {
+        let mut kernel = Self {
+            rate: 1.0,
+            arms: Vec::new(),
+            phase: 0.0,
+            skip: 0,
+            _sampletype: core::marker::PhantomData,
+        };
+        kernel.set_rate(rate);
+        kernel.set_taps(num_filters, taps);
+        kernel
+    }
+
+    /// The resampling ratio (output rate / input rate).
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Sets the resampling ratio (output rate / input rate). The prototype
+    /// filter has to suppress aliasing also for the new ratio.
+    pub fn set_rate(&mut self, rate: f64) {
+        assert!(
+            rate.is_finite() && rate > 0.0,
+            "rate must be positive and finite"
+        );
+        self.rate = rate;
+    }
+
+    /// Replaces the prototype filter, keeping the phase of the resampler.
+    pub fn set_taps<Taps: TapsAccessor<TapType = f32>>(&mut self, num_filters: usize, taps: Taps) {
+        assert!(num_filters > 0, "num_filters must be positive");
+        assert!(taps.num_taps() > 0, "filter needs at least one tap");
+        let arm_len = taps.num_taps().div_ceil(num_filters);
+        self.arms = (0..num_filters)
+            .map(|p| {
+                (0..arm_len)
+                    .rev()
+                    .map(|k| {
+                        let t = p + k * num_filters;
+                        if t < taps.num_taps() {
+                            // Safety: t is smaller than the number of taps
+                            unsafe { taps.get(t) }
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+}
+
+impl<SampleType> ArbitraryResamplingFirKernel<SampleType>
+where
+    SampleType:
+        Copy + Add<Output = SampleType> + Sub<Output = SampleType> + Mul<f32, Output = SampleType>,
+{
+    fn dot(arm: &[f32], i: &[SampleType]) -> SampleType {
+        let mut sum = i[0] * arm[0];
+        for (t, x) in arm.iter().zip(i.iter()).skip(1) {
+            sum = sum + *x * *t;
+        }
+        sum
+    }
+
+    fn resample(
+        &mut self,
+        i: &[SampleType],
+        o: &mut [SampleType],
+    ) -> (usize, usize, ComputationStatus) {
+        if self.skip >= i.len() {
+            self.skip -= i.len();
+            let status = if o.is_empty() {
+                ComputationStatus::BothSufficient
+            } else {
+                ComputationStatus::InsufficientInput
+            };
+            return (i.len(), 0, status);
+        }
+
+        let num_filters = self.arms.len();
+        let arm_len = self.arms[0].len();
+        let step = 1.0 / self.rate;
+        let mut n = self.skip;
+        let mut produced = 0;
+
+        // one more sample is needed to interpolate towards the first arm
+        while produced < o.len() && n + arm_len < i.len() {
+            let pos = self.phase * num_filters as f64;
+            let j = core::cmp::min(pos as usize, num_filters - 1);
+            let frac = (pos - j as f64) as f32;
+            let y0 = Self::dot(&self.arms[j], &i[n..]);
+            let y1 = if j + 1 < num_filters {
+                Self::dot(&self.arms[j + 1], &i[n..])
+            } else {
+                Self::dot(&self.arms[0], &i[n + 1..])
+            };
+            o[produced] = y0 + (y1 - y0) * frac;
+            produced += 1;
+
+            self.phase += step;
+            let advance = self.phase.floor();
+            self.phase -= advance;
+            n += advance as usize;
+        }
+
+        let consumed = core::cmp::min(n, i.len());
+        self.skip = n - consumed;
+        let status = if produced < o.len() {
+            ComputationStatus::InsufficientInput
+        } else if n + arm_len < i.len() {
+            ComputationStatus::InsufficientOutput
+        } else {
+            ComputationStatus::BothSufficient
+        };
+        (consumed, produced, status)
+    }
+}
+
+impl StatefulUnaryKernel<f32> for ArbitraryResamplingFirKernel<f32> {
+    fn work(&mut self, i: &[f32], o: &mut [f32]) -> (usize, usize, ComputationStatus) {
+        self.resample(i, o)
+    }
+}
+
+impl StatefulUnaryKernel<Complex<f32>> for ArbitraryResamplingFirKernel<Complex<f32>> {
+    fn work(
+        &mut self,
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> (usize, usize, ComputationStatus) {
+        self.resample(i, o)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::firdes;
+
+    /// Resamples `input`, passing at most `in_chunk` input and `out_chunk`
+    /// output samples per call.
+    fn resample(rate: f64, input: &[f32], in_chunk: usize, out_chunk: usize) -> Vec<f32> {
+        let taps = firdes::kaiser::arbitrary_resampling::<f32>(rate, 32, 0.001);
+        let mut kernel = ArbitraryResamplingFirKernel::<f32>::new(rate, 32, taps);
+        let mut output = Vec::new();
+        let mut buf = vec![0.0; out_chunk];
+        let mut start = 0;
+        loop {
+            let end = core::cmp::min(start + in_chunk, input.len());
+            let (consumed, produced, _) = kernel.work(&input[start..end], &mut buf);
+            output.extend_from_slice(&buf[..produced]);
+            start += consumed;
+            if consumed == 0 && produced == 0 {
+                break;
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn tone_is_resampled() {
+        let f = 0.05;
+        let input: Vec<f32> = (0..20000)
+            .map(|n| (2.0 * core::f64::consts::PI * f * n as f64).sin() as f32)
+            .collect();
+        for rate in [0.3, 0.75, 1.0, 1.0884, 2.5] {
+            for (in_chunk, out_chunk) in [(500, 7), (20000, 1000)] {
+                let output = resample(rate, &input, in_chunk, out_chunk);
+                let expected = input.len() as f64 * rate;
+                assert!((output.len() as f64 - expected).abs() < 2.0 + 40.0 * rate.max(1.0));
+
+                // compare with the tone at the output rate, skipping the filter
+                // delay, which is found by correlating with sine and cosine
+                let fo = f / rate;
+                let settled = &output[output.len() / 4..output.len() * 3 / 4];
+                let (mut s, mut c) = (0.0f64, 0.0f64);
+                for (m, y) in settled.iter().enumerate() {
+                    let phi = 2.0 * core::f64::consts::PI * fo * m as f64;
+                    s += *y as f64 * phi.sin();
+                    c += *y as f64 * phi.cos();
+                }
+                let phase = c.atan2(s);
+                let amplitude = 2.0 * (s * s + c * c).sqrt() / settled.len() as f64;
+                assert!(
+                    (amplitude - 1.0).abs() < 0.01,
+                    "rate {} amplitude {}",
+                    rate,
+                    amplitude
+                );
+                for (m, y) in settled.iter().enumerate() {
+                    let phi = 2.0 * core::f64::consts::PI * fo * m as f64 + phase;
+                    assert!((*y as f64 - phi.sin()).abs() < 0.01, "rate {}", rate);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rate_change() {
+        let taps = firdes::kaiser::arbitrary_resampling::<f32>(0.5, 16, 0.001);
+        let mut kernel = ArbitraryResamplingFirKernel::<Complex<f32>>::new(0.5, 16, taps);
+        let input = vec![Complex::new(1.0, -1.0); 10000];
+        let mut output = vec![Complex::new(0.0, 0.0); 10000];
+        let (consumed, produced, status) = kernel.work(&input[..4000], &mut output);
+        assert_eq!(status, ComputationStatus::InsufficientInput);
+        assert!((produced as f64 - consumed as f64 * 0.5).abs() < 2.0);
+        assert!((output[produced - 1] - Complex::new(1.0, -1.0)).norm() < 0.01);
+
+        kernel.set_rate(0.25);
+        assert_eq!(kernel.rate(), 0.25);
+        let (consumed, produced, _) = kernel.work(&input[..4000], &mut output);
+        assert!((produced as f64 - consumed as f64 * 0.25).abs() < 2.0);
+    }
+}
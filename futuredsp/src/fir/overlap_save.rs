@@ -0,0 +1,241 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+use super::fir_num_outputs;
+use crate::fft::Fft;
+use crate::{ComputationStatus, StatefulUnaryKernel, TapsAccessor};
+
+/// A non-resampling FIR filter using FFT-based fast convolution (overlap-save).
+///
+/// The input is split into overlapping segments, which are filtered in the
+/// frequency domain. For long filters, this is much faster than the direct
+/// form of [NonResamplingFirKernel](super::NonResamplingFirKernel), which
+/// needs one multiply-accumulate per tap and output sample. The output is the
+/// same as for the direct form (up to rounding), i.e., calling `work()` always
+/// produces exactly as many samples as it consumes. The kernel is stateful,
+/// since it owns the buffers for the transforms.
+///
+/// Implementations of this core exist for the following combinations:
+/// - `f32` samples, `f32` taps.
+/// - `Complex<f32>` samples, `f32` taps.
+/// - `Complex<f32>` samples, `Complex<f32>` taps.
+///
+/// Real samples are filtered two segments at a time, using the real and the
+/// imaginary part of one complex transform.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::StatefulUnaryKernel;
+/// use futuredsp::fir::OverlapSaveFirKernel;
+///
+/// let mut fir = OverlapSaveFirKernel::<f32, _>::new([1.0, 2.0, 3.0]);
+///
+/// let input = [1.0, 2.0, 3.0, 4.0];
+/// let mut output = [0.0; 2];
+/// fir.work(&input, &mut output);
+/// assert!((output[0] - 10.0).abs() < 1e-4);
+/// assert!((output[1] - 16.0).abs() < 1e-4);
+/// ```
+pub struct OverlapSaveFirKernel<SampleType, TapType> {
+    num_taps: usize,
+    fft: Fft,
+    /// Transform of the taps, including the normalization of the inverse FFT.
+    spectrum: Vec<Complex<f32>>,
+    buffer: Vec<Complex<f32>>,
+    _type: core::marker::PhantomData<(SampleType, TapType)>,
+}
+
+impl<SampleType, TapType: Into<Complex<f32>>> OverlapSaveFirKernel<SampleType, TapType> {
+    /// Create a new overlap-save FIR filter using the given taps.
+    ///
+    /// The FFT size is the next power of two of four times the number of taps.
+    pub fn new<T: TapsAccessor<TapType = TapType>>(taps: T) -> Self {
+        let fft_len = (4 * taps.num_taps()).next_power_of_two().max(64);
+        Self::with_fft_len(taps, fft_len)
+    }
+
+    /// Create a new overlap-save FIR filter with a given FFT size, which has
+    /// to be a power of two and larger than the number of taps.
+    pub fn with_fft_len<T: TapsAccessor<TapType = TapType>>(taps: T, fft_len: usize) -> Self {
+        let num_taps = taps.num_taps();
+        assert!(num_taps > 0, "filter needs at least one tap");
+        assert!(
+            fft_len > num_taps,
+            "FFT size has to exceed the number of taps"
+        );
+
+        let fft = Fft::new(fft_len);
+        let mut spectrum = vec![Complex::new(0.0, 0.0); fft_len];
+        for (t, s) in spectrum.iter_mut().enumerate().take(num_taps) {
+            *s = unsafe { taps.get(t) }.into() / fft_len as f32;
+        }
+        fft.forward(&mut spectrum);
+
+        Self {
+            num_taps,
+            fft,
+            spectrum,
+            buffer: vec![Complex::new(0.0, 0.0); fft_len],
+            _type: core::marker::PhantomData,
+        }
+    }
+
+    /// Number of output samples computed per transform.
+    fn block_len(&self) -> usize {
+        self.fft.len() - self.num_taps + 1
+    }
+
+    /// Filters the segment in the buffer. Valid outputs start at index
+    /// `num_taps - 1`.
+    fn filter_buffer(&mut self) {
+        self.fft.forward(&mut self.buffer);
+        for (b, s) in self.buffer.iter_mut().zip(self.spectrum.iter()) {
+            *b *= s;
+        }
+        self.fft.inverse(&mut self.buffer);
+    }
+}
+
+impl StatefulUnaryKernel<f32> for OverlapSaveFirKernel<f32, f32> {
+    fn work(&mut self, i: &[f32], o: &mut [f32]) -> (usize, usize, ComputationStatus) {
+        let (n, status) = fir_num_outputs(self.num_taps, i.len(), o.len());
+        let block = self.block_len();
+        let history = self.num_taps - 1;
+
+        let mut k = 0;
+        while k < n {
+            // the real part holds the first segment, the imaginary part the second
+            let m1 = core::cmp::min(block, n - k);
+            let m2 = core::cmp::min(block, n - k - m1);
+            let first = &i[k..k + m1 + history];
+            let second = if m2 > 0 {
+                &i[k + m1..k + m1 + m2 + history]
+            } else {
+                &[]
+            };
+            for (j, b) in self.buffer.iter_mut().enumerate() {
+                *b = Complex::new(
+                    first.get(j).copied().unwrap_or(0.0),
+                    second.get(j).copied().unwrap_or(0.0),
+                );
+            }
+
+            self.filter_buffer();
+
+            let valid = &self.buffer[history..];
+            for (o, b) in o[k..k + m1].iter_mut().zip(valid) {
+                *o = b.re;
+            }
+            for (o, b) in o[k + m1..k + m1 + m2].iter_mut().zip(valid) {
+                *o = b.im;
+            }
+            k += m1 + m2;
+        }
+
+        (n, n, status)
+    }
+}
+
+impl<TapType: Send> StatefulUnaryKernel<Complex<f32>>
+    for OverlapSaveFirKernel<Complex<f32>, TapType>
+where
+    TapType: Into<Complex<f32>>,
+{
+    fn work(
+        &mut self,
+        i: &[Complex<f32>],
+        o: &mut [Complex<f32>],
+    ) -> (usize, usize, ComputationStatus) {
+        let (n, status) = fir_num_outputs(self.num_taps, i.len(), o.len());
+        let block = self.block_len();
+        let history = self.num_taps - 1;
+
+        let mut k = 0;
+        while k < n {
+            let m = core::cmp::min(block, n - k);
+            let segment = &i[k..k + m + history];
+            self.buffer[..segment.len()].copy_from_slice(segment);
+            self.buffer[segment.len()..].fill(Complex::new(0.0, 0.0));
+
+            self.filter_buffer();
+
+            o[k..k + m].copy_from_slice(&self.buffer[history..history + m]);
+            k += m;
+        }
+
+        (n, n, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fir::NonResamplingFirKernel;
+    use crate::UnaryKernel;
+
+    fn signal(n: usize, f: f32) -> Vec<f32> {
+        (0..n).map(|i| (i as f32 * f).sin()).collect()
+    }
+
+    #[test]
+    fn matches_direct_form() {
+        for num_taps in [1, 2, 3, 17, 64, 300] {
+            let taps = signal(num_taps, 0.11);
+            let input = signal(3000, 0.7);
+            let cinput: Vec<Complex<f32>> = input
+                .iter()
+                .zip(signal(3000, 0.3))
+                .map(|(re, im)| Complex::new(*re, im))
+                .collect();
+            let ctaps: Vec<Complex<f32>> = taps
+                .iter()
+                .zip(signal(num_taps, 0.5))
+                .map(|(re, im)| Complex::new(*re, im))
+                .collect();
+
+            // limit the output, to check partial segments
+            let n = 2000;
+            let mut expected = vec![0.0; n];
+            let mut output = vec![0.0; n];
+            let r = NonResamplingFirKernel::<f32, _>::new(taps.clone()).work(&input, &mut expected);
+            let mut fir = OverlapSaveFirKernel::<f32, _>::new(taps.clone());
+            assert_eq!(fir.work(&input, &mut output), r);
+            for (a, b) in output.iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 1e-3);
+            }
+
+            let mut expected = vec![Complex::new(0.0, 0.0); n];
+            let mut output = vec![Complex::new(0.0, 0.0); n];
+            let r = NonResamplingFirKernel::<Complex<f32>, _>::new(taps.clone())
+                .work(&cinput, &mut expected);
+            let mut fir = OverlapSaveFirKernel::<Complex<f32>, _>::new(taps.clone());
+            assert_eq!(fir.work(&cinput, &mut output), r);
+            for (a, b) in output.iter().zip(expected.iter()) {
+                assert!((a - b).norm() < 1e-3);
+            }
+
+            let r = NonResamplingFirKernel::<Complex<f32>, _>::new(ctaps.clone())
+                .work(&cinput, &mut expected);
+            let mut fir = OverlapSaveFirKernel::<Complex<f32>, _>::new(ctaps);
+            assert_eq!(fir.work(&cinput, &mut output), r);
+            for (a, b) in output.iter().zip(expected.iter()) {
+                assert!((a - b).norm() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn insufficient_input() {
+        let mut fir = OverlapSaveFirKernel::<f32, _>::new([1.0, 2.0, 3.0]);
+        let mut output = [0.0; 4];
+        assert_eq!(
+            fir.work(&[1.0, 2.0], &mut output),
+            (0, 0, ComputationStatus::InsufficientInput)
+        );
+        assert_eq!(
+            fir.work(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &mut output),
+            (4, 4, ComputationStatus::BothSufficient)
+        );
+    }
+}
@@ -216,6 +216,176 @@ mod tests {
     }
 }
 
+/// A frequency band of a multi-band filter specification, used by the
+/// [remez] and [least_squares] designs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Band {
+    /// Lower band edge in cycles/sample.
+    pub start: f64,
+    /// Upper band edge in cycles/sample.
+    pub end: f64,
+    /// Desired amplitude in the band.
+    pub gain: f64,
+    /// Weight of the approximation error in the band.
+    pub weight: f64,
+}
+
+impl Band {
+    /// Creates a band from `start` to `end` (in cycles/sample) with the desired
+    /// amplitude `gain` and the error weight `weight`.
+    pub fn new(start: f64, end: f64, gain: f64, weight: f64) -> Self {
+        Self {
+            start,
+            end,
+            gain,
+            weight,
+        }
+    }
+}
+
+/// Symmetry of the impulse response of a linear-phase filter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Symmetry {
+    /// `h[n] = h[N-1-n]`, e.g., lowpass or bandpass filters.
+    Even,
+    /// `h[n] = -h[N-1-n]`, e.g., Hilbert transformers or differentiators.
+    Odd,
+}
+
+/// A linear-phase filter has the frequency response `A(f) e^{-j2πfM}` (even
+/// symmetry) or `j A(f) e^{-j2πfM}` (odd symmetry) with `M = (N-1)/2` and the
+/// real amplitude `A(f) = Q(f) Σ_k a_k cos(2πkf)`. Returns the number of
+/// cosine terms.
+fn num_basis_functions(num_taps: usize, symmetry: Symmetry) -> usize {
+    match (symmetry, num_taps & 1 == 1) {
+        (Symmetry::Even, true) => num_taps.div_ceil(2),
+        (Symmetry::Odd, true) => (num_taps - 1) / 2,
+        (_, false) => num_taps / 2,
+    }
+}
+
+/// The factor `Q(f)` of the amplitude, see [num_basis_functions].
+fn amplitude_factor(num_taps: usize, symmetry: Symmetry, f: f64) -> f64 {
+    let pi = core::f64::consts::PI;
+    match (symmetry, num_taps & 1 == 1) {
+        (Symmetry::Even, true) => 1.0,
+        (Symmetry::Even, false) => (pi * f).cos(),
+        (Symmetry::Odd, true) => (2.0 * pi * f).sin(),
+        (Symmetry::Odd, false) => (pi * f).sin(),
+    }
+}
+
+/// Desired amplitude and error weight on a dense frequency grid.
+struct Grid {
+    freqs: Vec<f64>,
+    desired: Vec<f64>,
+    weights: Vec<f64>,
+    /// Index of the first grid point of each band.
+    band_starts: Vec<usize>,
+}
+
+impl Grid {
+    /// Samples the bands with `density` points per basis function. Points,
+    /// where the amplitude is forced to zero by the filter type, are skipped.
+    fn new(
+        num_taps: usize,
+        symmetry: Symmetry,
+        bands: &[Band],
+        density: usize,
+        desired: impl Fn(&Band, f64) -> f64,
+        weight: impl Fn(&Band, f64) -> f64,
+    ) -> Self {
+        assert!(!bands.is_empty(), "at least one band is required");
+        for (i, b) in bands.iter().enumerate() {
+            assert!(
+                b.start >= 0.0 && b.start < b.end && b.end <= 0.5,
+                "band edges must be increasing and in [0, 1/2]"
+            );
+            assert!(b.weight > 0.0, "band weights must be positive");
+            if i > 0 {
+                assert!(
+                    bands[i - 1].end < b.start,
+                    "bands must be sorted and must not overlap"
+                );
+            }
+        }
+
+        let num_basis = num_basis_functions(num_taps, symmetry);
+        let spacing = 0.5 / (density * num_basis) as f64;
+        let mut grid = Grid {
+            freqs: Vec::new(),
+            desired: Vec::new(),
+            weights: Vec::new(),
+            band_starts: Vec::new(),
+        };
+        for b in bands {
+            grid.band_starts.push(grid.freqs.len());
+            let n = ((b.end - b.start) / spacing).ceil() as usize;
+            for i in 0..=n {
+                let f = b.start + (b.end - b.start) * i as f64 / n as f64;
+                if amplitude_factor(num_taps, symmetry, f).abs() < 1e-6 {
+                    continue;
+                }
+                grid.freqs.push(f);
+                grid.desired.push(desired(b, f));
+                grid.weights.push(weight(b, f));
+            }
+        }
+        grid
+    }
+
+    /// Range of grid indices of band `i`.
+    fn band(&self, i: usize) -> core::ops::Range<usize> {
+        let end = self
+            .band_starts
+            .get(i + 1)
+            .copied()
+            .unwrap_or(self.freqs.len());
+        self.band_starts[i]..end
+    }
+}
+
+/// Computes the taps of a linear-phase filter with the given amplitude by
+/// sampling the frequency response at `k/N` and taking the inverse DFT.
+fn linear_phase_taps(
+    num_taps: usize,
+    symmetry: Symmetry,
+    amplitude: impl Fn(f64) -> f64,
+) -> Vec<f64> {
+    let pi = core::f64::consts::PI;
+    let n = num_taps as f64;
+    let m = (n - 1.0) / 2.0;
+    let samples: Vec<f64> = (0..=num_taps / 2)
+        .map(|k| amplitude(k as f64 / n))
+        .collect();
+    (0..num_taps)
+        .map(|i| {
+            let t = i as f64 - m;
+            let sum: f64 = samples
+                .iter()
+                .enumerate()
+                .map(|(k, a)| {
+                    // the DC and (for even lengths) the Nyquist bin appear once
+                    let scale = if k == 0 || 2 * k == num_taps {
+                        1.0
+                    } else {
+                        2.0
+                    };
+                    let phi = 2.0 * pi * k as f64 / n * t;
+                    match symmetry {
+                        Symmetry::Even => scale * a * phi.cos(),
+                        Symmetry::Odd => -scale * a * phi.sin(),
+                    }
+                })
+                .sum();
+            sum / n
+        })
+        .collect()
+}
+
+pub mod least_squares;
+pub mod remez;
+
 /// FIR filter design methods based on the Kaiser window method. The resulting
 /// filters have generalized linear phase.
 ///
@@ -375,6 +545,36 @@ pub mod kaiser {
         taps
     }
 
+    /// Designs the prototype filter of an arbitrary resampler with ratio
+    /// `rate` (output rate / input rate) and `num_filters` polyphase arms, see
+    /// [ArbitraryResamplingFirKernel](crate::fir::ArbitraryResamplingFirKernel).
+    ///
+    /// The passband extends to 40% and the stopband starts at 50% of the
+    /// lower of the input and the output sampling rate. The filter is scaled
+    /// by `num_filters` to get unit gain after resampling.
+    ///
+    /// Example usage:
+    /// ```
+    /// use futuredsp::firdes;
+    ///
+    /// let taps = firdes::kaiser::arbitrary_resampling::<f32>(48_000.0 / 1e6, 32, 0.001);
+    /// ```
+    pub fn arbitrary_resampling<T: FromPrimitive>(
+        rate: f64,
+        num_filters: usize,
+        max_ripple: f64,
+    ) -> Vec<T> {
+        assert!(rate > 0.0, "rate must be greater than 0");
+        assert!(num_filters > 0, "num_filters must be greater than 0");
+        let band = rate.min(1.0) / num_filters as f64;
+        let (num_taps, beta) = design_kaiser_window(0.1 * band, max_ripple);
+        let win: Vec<f64> = kaiser(num_taps, beta)
+            .iter()
+            .map(|x| num_filters as f64 * x)
+            .collect();
+        super::lowpass(0.45 * band, win.as_slice())
+    }
+
     fn compute_kaiser_beta(max_ripple: f64) -> f64 {
         // Determine Kaiser window parameters
         let ripple_db = -20.0 * max_ripple.log10();
@@ -396,6 +596,20 @@ pub mod kaiser {
     #[allow(clippy::excessive_precision)]
     mod tests {
         use super::*;
+        use crate::analysis::FilterResponse;
+
+        #[test]
+        fn lowpass_specs() {
+            let (cutoff, transition_bw, max_ripple) = (0.2, 0.05, 0.01);
+            let taps = lowpass::<f64>(cutoff, transition_bw, max_ripple);
+            for i in 0..=100 {
+                let pass = cutoff * i as f64 / 100.0;
+                let stop =
+                    cutoff + transition_bw + (0.5 - cutoff - transition_bw) * i as f64 / 100.0;
+                assert!((taps.frequency_response(pass).norm() - 1.0).abs() < max_ripple);
+                assert!(taps.magnitude_db(stop) < 20.0 * max_ripple.log10());
+            }
+        }
 
         #[test]
         fn lowpass_accuracy() {
@@ -0,0 +1,249 @@
+//! Least-squares FIR filter design.
+//!
+//! The designs minimize the weighted squared deviation from the desired
+//! amplitude, integrated over all bands. Regions between bands are transition
+//! bands, where the response is not constrained. The resulting filters have
+//! linear phase. Compared to the equiripple designs of the [remez](super::remez)
+//! module, they trade a larger peak error at the band edges for less error
+//! energy.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use num_traits::FromPrimitive;
+
+use super::{amplitude_factor, linear_phase_taps, num_basis_functions, Band, Grid, Symmetry};
+
+const GRID_DENSITY: usize = 16;
+
+/// Designs a multi-band filter with `num_taps` taps and even symmetry, e.g.,
+/// a lowpass, highpass, bandpass, or bandstop filter. Filters with an even
+/// number of taps have a zero at the Nyquist frequency and cannot be highpass
+/// filters.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::{least_squares, Band};
+///
+/// let taps = least_squares::multiband::<f32>(
+///     65,
+///     &[Band::new(0.0, 0.1, 1.0, 1.0), Band::new(0.15, 0.5, 0.0, 1.0)],
+/// );
+/// ```
+pub fn multiband<T: FromPrimitive>(num_taps: usize, bands: &[Band]) -> Vec<T> {
+    design(
+        num_taps,
+        Symmetry::Even,
+        bands,
+        |b, _| b.gain,
+        |b, _| b.weight,
+    )
+}
+
+/// Designs a Hilbert transformer with `num_taps` taps and transition bands of
+/// width `transition_bw` (in cycles/sample) at DC and, for an odd number of
+/// taps, at the Nyquist frequency.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::least_squares;
+///
+/// let taps = least_squares::hilbert::<f32>(31, 0.05);
+/// ```
+pub fn hilbert<T: FromPrimitive>(num_taps: usize, transition_bw: f64) -> Vec<T> {
+    assert!(
+        transition_bw > 0.0 && transition_bw < 0.25,
+        "transition_bw must be in (0, 1/4)"
+    );
+    let end = if num_taps & 1 == 1 {
+        0.5 - transition_bw
+    } else {
+        0.5
+    };
+    // H(f) = -j for positive frequencies
+    design(
+        num_taps,
+        Symmetry::Odd,
+        &[Band::new(transition_bw, end, 1.0, 1.0)],
+        |_, _| -1.0,
+        |b, _| b.weight,
+    )
+}
+
+/// Designs a differentiator with `num_taps` taps. In each band, the desired
+/// amplitude is `gain * 2πf`, i.e., `gain = 1` approximates the ideal
+/// differentiator `H(f) = j2πf` and `gain = 0` specifies a stopband. Filters
+/// with an odd number of taps have a zero at the Nyquist frequency, so
+/// full-band differentiators should have an even number of taps.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::{least_squares, Band};
+///
+/// let taps = least_squares::differentiator::<f32>(
+///     31,
+///     &[Band::new(0.0, 0.2, 1.0, 1.0), Band::new(0.3, 0.5, 0.0, 1.0)],
+/// );
+/// ```
+pub fn differentiator<T: FromPrimitive>(num_taps: usize, bands: &[Band]) -> Vec<T> {
+    design(
+        num_taps,
+        Symmetry::Odd,
+        bands,
+        |b, f| b.gain * 2.0 * core::f64::consts::PI * f,
+        |b, _| b.weight,
+    )
+}
+
+fn design<T: FromPrimitive>(
+    num_taps: usize,
+    symmetry: Symmetry,
+    bands: &[Band],
+    desired: impl Fn(&Band, f64) -> f64,
+    weight: impl Fn(&Band, f64) -> f64,
+) -> Vec<T> {
+    let num_basis = num_basis_functions(num_taps, symmetry);
+    assert!(num_basis > 0, "num_taps too small for the filter type");
+    let grid = Grid::new(num_taps, symmetry, bands, GRID_DENSITY, desired, weight);
+    assert!(
+        grid.freqs.len() >= num_basis,
+        "bands too narrow for the number of taps"
+    );
+
+    let basis = |k: usize, f: f64| {
+        amplitude_factor(num_taps, symmetry, f) * (2.0 * core::f64::consts::PI * k as f64 * f).cos()
+    };
+
+    // normal equations of the weighted least-squares problem on the grid
+    let mut gram = vec![0.0; num_basis * num_basis];
+    let mut rhs = vec![0.0; num_basis];
+    let mut psi = vec![0.0; num_basis];
+    for ((f, d), w) in grid.freqs.iter().zip(&grid.desired).zip(&grid.weights) {
+        for (k, p) in psi.iter_mut().enumerate() {
+            *p = basis(k, *f);
+        }
+        for k in 0..num_basis {
+            rhs[k] += w * psi[k] * d;
+            for l in 0..num_basis {
+                gram[k * num_basis + l] += w * psi[k] * psi[l];
+            }
+        }
+    }
+    let coeffs = solve(gram, rhs);
+
+    linear_phase_taps(num_taps, symmetry, |f| {
+        coeffs
+            .iter()
+            .enumerate()
+            .map(|(k, a)| a * basis(k, f))
+            .sum()
+    })
+    .into_iter()
+    .map(|x| T::from_f64(x).unwrap())
+    .collect()
+}
+
+/// Solves the linear system `a x = b` with Gaussian elimination and partial
+/// pivoting. `a` is stored row-major.
+fn solve(mut a: Vec<f64>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|i, j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))
+            .unwrap();
+        if pivot != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot * n + k);
+            }
+            b.swap(col, pivot);
+        }
+        let p = a[col * n + col];
+        assert!(p.abs() > 1e-300, "singular least-squares problem");
+        for row in col + 1..n {
+            let factor = a[row * n + col] / p;
+            if factor != 0.0 {
+                for k in col..n {
+                    a[row * n + k] -= factor * a[col * n + k];
+                }
+                b[row] -= factor * b[col];
+            }
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row * n + k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row * n + row];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::FilterResponse;
+    use crate::firdes::remez;
+
+    /// Squared error of the magnitude, summed over a grid of the bands.
+    fn squared_error(taps: &[f64], bands: &[Band]) -> f64 {
+        bands
+            .iter()
+            .flat_map(|b| {
+                (0..=500).map(move |i| {
+                    let f = b.start + (b.end - b.start) * i as f64 / 500.0;
+                    (taps.frequency_response(f).norm() - b.gain).powi(2)
+                })
+            })
+            .sum()
+    }
+
+    #[test]
+    fn lowpass() {
+        let bands = [
+            Band::new(0.0, 0.1, 1.0, 1.0),
+            Band::new(0.15, 0.5, 0.0, 1.0),
+        ];
+        for num_taps in [30, 31] {
+            let taps = multiband::<f64>(num_taps, &bands);
+            for i in 0..num_taps {
+                assert!((taps[i] - taps[num_taps - 1 - i]).abs() < 1e-12);
+            }
+            let dc = taps.frequency_response(0.0).re;
+            assert!((dc - 1.0).abs() < 0.05);
+            // less error energy than the equiripple design
+            let equiripple = remez::multiband::<f64>(num_taps, &bands);
+            assert!(squared_error(&taps, &bands) < squared_error(&equiripple, &bands));
+        }
+    }
+
+    #[test]
+    fn hilbert_transformer() {
+        let taps = hilbert::<f64>(41, 0.05);
+        for i in 0..41 {
+            assert!((taps[i] + taps[40 - i]).abs() < 1e-12);
+        }
+        assert!(taps[21] > 0.5);
+        for i in 0..=20 {
+            let f = 0.1 + 0.3 * i as f64 / 20.0;
+            assert!((taps.frequency_response(f).norm() - 1.0).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn differentiator_response() {
+        let taps = differentiator::<f64>(40, &[Band::new(0.0, 0.5, 1.0, 1.0)]);
+        for i in 1..50 {
+            let f = 0.5 * i as f64 / 50.0;
+            let want = 2.0 * core::f64::consts::PI * f;
+            assert!((taps.frequency_response(f).norm() - want).abs() < 0.02 * want.max(1.0));
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Equiripple FIR filter design with the Parks-McClellan (Remez exchange)
+//! algorithm.
+//!
+//! The designs minimize the maximum weighted deviation from the desired
+//! amplitude over all bands. Regions between bands are transition bands,
+//! where the response is not constrained. The resulting filters have linear
+//! phase.
+//!
+//! The algorithm is described in:
+//! - J. H. McClellan, T. W. Parks and L. R. Rabiner "A Computer Program for
+//!   Designing Optimum FIR Linear Phase Digital Filters," IEEE Transactions on
+//!   Audio and Electroacoustics, vol. 21, no. 6, Dec. 1973.
+
+extern crate alloc;
+use alloc::vec::Vec;
+use num_traits::FromPrimitive;
+
+use super::{amplitude_factor, linear_phase_taps, num_basis_functions, Band, Grid, Symmetry};
+
+const GRID_DENSITY: usize = 16;
+const MAX_ITERATIONS: usize = 250;
+
+/// Designs a multi-band filter with `num_taps` taps and even symmetry, e.g.,
+/// a lowpass, highpass, bandpass, or bandstop filter. Filters with an even
+/// number of taps have a zero at the Nyquist frequency and cannot be highpass
+/// filters.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::{remez, Band};
+///
+/// // lowpass filter with 10x more weight on the stopband
+/// let taps = remez::multiband::<f32>(
+///     65,
+///     &[Band::new(0.0, 0.1, 1.0, 1.0), Band::new(0.15, 0.5, 0.0, 10.0)],
+/// );
+/// ```
+pub fn multiband<T: FromPrimitive>(num_taps: usize, bands: &[Band]) -> Vec<T> {
+    design(
+        num_taps,
+        Symmetry::Even,
+        bands,
+        |b, _| b.gain,
+        |b, _| b.weight,
+    )
+}
+
+/// Designs a Hilbert transformer with `num_taps` taps and transition bands of
+/// width `transition_bw` (in cycles/sample) at DC and, for an odd number of
+/// taps, at the Nyquist frequency. Odd lengths result in every other tap
+/// being zero.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::remez;
+///
+/// let taps = remez::hilbert::<f32>(31, 0.05);
+/// ```
+pub fn hilbert<T: FromPrimitive>(num_taps: usize, transition_bw: f64) -> Vec<T> {
+    assert!(
+        transition_bw > 0.0 && transition_bw < 0.25,
+        "transition_bw must be in (0, 1/4)"
+    );
+    let end = if num_taps & 1 == 1 {
+        0.5 - transition_bw
+    } else {
+        0.5
+    };
+    // H(f) = -j for positive frequencies
+    design(
+        num_taps,
+        Symmetry::Odd,
+        &[Band::new(transition_bw, end, 1.0, 1.0)],
+        |_, _| -1.0,
+        |b, _| b.weight,
+    )
+}
+
+/// Designs a differentiator with `num_taps` taps. In each band, the desired
+/// amplitude is `gain * 2πf`, i.e., `gain = 1` approximates the ideal
+/// differentiator `H(f) = j2πf` and `gain = 0` specifies a stopband. The error
+/// is weighted relative to the desired amplitude. Filters with an odd number of
+/// taps have a zero at the Nyquist frequency, so full-band differentiators
+/// should have an even number of taps.
+///
+/// The filter taps are constructed internally as `f64` and then casted to the
+/// generic type `T` using [`num_traits::FromPrimitive::from_f64()`].
+///
+/// Example usage:
+/// ```
+/// use futuredsp::firdes::{remez, Band};
+///
+/// let taps = remez::differentiator::<f32>(32, &[Band::new(0.0, 0.5, 1.0, 1.0)]);
+/// ```
+pub fn differentiator<T: FromPrimitive>(num_taps: usize, bands: &[Band]) -> Vec<T> {
+    let pi = core::f64::consts::PI;
+    design(
+        num_taps,
+        Symmetry::Odd,
+        bands,
+        |b, f| b.gain * 2.0 * pi * f,
+        |b, f| {
+            if b.gain == 0.0 {
+                b.weight
+            } else {
+                b.weight / (2.0 * pi * f)
+            }
+        },
+    )
+}
+
+fn design<T: FromPrimitive>(
+    num_taps: usize,
+    symmetry: Symmetry,
+    bands: &[Band],
+    desired: impl Fn(&Band, f64) -> f64,
+    weight: impl Fn(&Band, f64) -> f64,
+) -> Vec<T> {
+    let num_basis = num_basis_functions(num_taps, symmetry);
+    assert!(num_basis > 0, "num_taps too small for the filter type");
+    let grid = Grid::new(num_taps, symmetry, bands, GRID_DENSITY, desired, weight);
+    assert!(
+        grid.freqs.len() > num_basis,
+        "bands too narrow for the number of taps"
+    );
+
+    // approximate D/Q with the weight W*Q by a polynomial in cos(2πf)
+    let q: Vec<f64> = grid
+        .freqs
+        .iter()
+        .map(|f| amplitude_factor(num_taps, symmetry, *f))
+        .collect();
+    let desired: Vec<f64> = grid.desired.iter().zip(&q).map(|(d, q)| d / q).collect();
+    let weights: Vec<f64> = grid.weights.iter().zip(&q).map(|(w, q)| w * q).collect();
+    let x: Vec<f64> = grid
+        .freqs
+        .iter()
+        .map(|f| (2.0 * core::f64::consts::PI * f).cos())
+        .collect();
+
+    let p = exchange(num_basis, &grid, &x, &desired, &weights);
+
+    linear_phase_taps(num_taps, symmetry, |f| {
+        amplitude_factor(num_taps, symmetry, f) * p.eval((2.0 * core::f64::consts::PI * f).cos())
+    })
+    .into_iter()
+    .map(|x| T::from_f64(x).unwrap())
+    .collect()
+}
+
+/// Polynomial in barycentric Lagrange form.
+struct Interpolant {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    w: Vec<f64>,
+}
+
+impl Interpolant {
+    fn eval(&self, x: f64) -> f64 {
+        let mut num = 0.0;
+        let mut den = 0.0;
+        for ((xi, yi), wi) in self.x.iter().zip(&self.y).zip(&self.w) {
+            let d = x - xi;
+            if d == 0.0 {
+                return *yi;
+            }
+            num += wi * yi / d;
+            den += wi / d;
+        }
+        num / den
+    }
+}
+
+/// Barycentric weights `1 / Π_{j≠i} (x_i - x_j)`, up to a common factor.
+/// They are computed in the log domain, since the products overflow for long
+/// filters.
+fn barycentric_weights(x: &[f64]) -> Vec<f64> {
+    let logs: Vec<(f64, f64)> = x
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            let mut log = 0.0;
+            let mut sign = 1.0;
+            for (j, xj) in x.iter().enumerate() {
+                if i != j {
+                    let d = xi - xj;
+                    log -= d.abs().ln();
+                    if d < 0.0 {
+                        sign = -sign;
+                    }
+                }
+            }
+            (log, sign)
+        })
+        .collect();
+    let max = logs.iter().fold(f64::NEG_INFINITY, |m, (l, _)| m.max(*l));
+    logs.iter().map(|(l, s)| s * (l - max).exp()).collect()
+}
+
+/// Runs the Remez exchange and returns the optimal polynomial with
+/// `num_basis` coefficients.
+fn exchange(
+    num_basis: usize,
+    grid: &Grid,
+    x: &[f64],
+    desired: &[f64],
+    weights: &[f64],
+) -> Interpolant {
+    let n = x.len();
+    let mut extremals: Vec<usize> = (0..=num_basis).map(|i| i * (n - 1) / num_basis).collect();
+    let mut interpolant = None;
+
+    for _ in 0..MAX_ITERATIONS {
+        let xe: Vec<f64> = extremals.iter().map(|i| x[*i]).collect();
+        let b = barycentric_weights(&xe);
+
+        // levelled deviation on the extremal set
+        let mut num = 0.0;
+        let mut den = 0.0;
+        for (k, (bk, i)) in b.iter().zip(&extremals).enumerate() {
+            let sign = if k & 1 == 0 { 1.0 } else { -1.0 };
+            num += bk * desired[*i];
+            den += sign * bk / weights[*i];
+        }
+        let delta = num / den;
+
+        // interpolate through all but the last extremal
+        let last = xe[num_basis];
+        let p = Interpolant {
+            x: xe[..num_basis].to_vec(),
+            y: extremals[..num_basis]
+                .iter()
+                .enumerate()
+                .map(|(k, i)| {
+                    let sign = if k & 1 == 0 { 1.0 } else { -1.0 };
+                    desired[*i] - sign * delta / weights[*i]
+                })
+                .collect(),
+            w: b[..num_basis]
+                .iter()
+                .zip(&xe)
+                .map(|(b, x)| b * (x - last))
+                .collect(),
+        };
+
+        let error: Vec<f64> = (0..n)
+            .map(|i| weights[i] * (desired[i] - p.eval(x[i])))
+            .collect();
+        let max_error = error.iter().fold(0.0f64, |m, e| m.max(e.abs()));
+        interpolant = Some(p);
+
+        if max_error - delta.abs() <= 1e-9 * max_error {
+            break;
+        }
+        match find_extremals(grid, &error, delta.abs(), num_basis + 1) {
+            Some(e) if e != extremals => extremals = e,
+            _ => break,
+        }
+    }
+
+    interpolant.unwrap()
+}
+
+/// Finds `count` alternating extrema of the error function with a magnitude
+/// of at least `delta`.
+fn find_extremals(grid: &Grid, error: &[f64], delta: f64, count: usize) -> Option<Vec<usize>> {
+    let mut candidates: Vec<usize> = Vec::new();
+    for band in 0..grid.band_starts.len() {
+        let range = grid.band(band);
+        for i in range.clone() {
+            let e = error[i];
+            if e.abs() < delta * (1.0 - 1e-9) {
+                continue;
+            }
+            let left = if i > range.start { error[i - 1] } else { e };
+            let right = if i + 1 < range.end { error[i + 1] } else { e };
+            if (e > 0.0 && e >= left && e >= right) || (e < 0.0 && e <= left && e <= right) {
+                candidates.push(i);
+            }
+        }
+    }
+
+    // keep the largest of consecutive extrema with the same sign
+    let mut alternating: Vec<usize> = Vec::new();
+    for i in candidates {
+        match alternating.last_mut() {
+            Some(last) if (error[*last] > 0.0) == (error[i] > 0.0) => {
+                if error[i].abs() > error[*last].abs() {
+                    *last = i;
+                }
+            }
+            _ => alternating.push(i),
+        }
+    }
+    if alternating.len() < count {
+        return None;
+    }
+
+    // drop the smaller extremum at the ends, preserving the alternation
+    let mut start = 0;
+    let mut end = alternating.len();
+    while end - start > count {
+        if error[alternating[start]].abs() < error[alternating[end - 1]].abs() {
+            start += 1;
+        } else {
+            end -= 1;
+        }
+    }
+    Some(alternating[start..end].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::FilterResponse;
+
+    /// Maximum deviation from `gain` in the band.
+    fn deviation(taps: &[f64], start: f64, end: f64, gain: f64) -> f64 {
+        (0..=200)
+            .map(|i| start + (end - start) * i as f64 / 200.0)
+            .map(|f| (taps.frequency_response(f).norm() - gain).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn equiripple_lowpass() {
+        for num_taps in [31, 32, 65] {
+            let taps = multiband::<f64>(
+                num_taps,
+                &[
+                    Band::new(0.0, 0.1, 1.0, 1.0),
+                    Band::new(0.15, 0.5, 0.0, 1.0),
+                ],
+            );
+            assert_eq!(taps.len(), num_taps);
+            for i in 0..num_taps {
+                assert!((taps[i] - taps[num_taps - 1 - i]).abs() < 1e-12);
+            }
+            let pass = deviation(&taps, 0.0, 0.1, 1.0);
+            let stop = deviation(&taps, 0.15, 0.5, 0.0);
+            assert!(pass < 0.05, "passband ripple {}", pass);
+            assert!((pass - stop).abs() < 0.02 * pass, "{} != {}", pass, stop);
+        }
+    }
+
+    #[test]
+    fn band_weights() {
+        let taps = multiband::<f64>(
+            51,
+            &[
+                Band::new(0.0, 0.2, 1.0, 1.0),
+                Band::new(0.25, 0.5, 0.0, 10.0),
+            ],
+        );
+        let pass = deviation(&taps, 0.0, 0.2, 1.0);
+        let stop = deviation(&taps, 0.25, 0.5, 0.0);
+        assert!((pass / stop - 10.0).abs() < 0.2, "ratio {}", pass / stop);
+    }
+
+    #[test]
+    fn bandpass() {
+        let taps = multiband::<f64>(
+            81,
+            &[
+                Band::new(0.0, 0.1, 0.0, 1.0),
+                Band::new(0.15, 0.25, 1.0, 1.0),
+                Band::new(0.3, 0.5, 0.0, 1.0),
+            ],
+        );
+        assert!(deviation(&taps, 0.15, 0.25, 1.0) < 0.01);
+        assert!(deviation(&taps, 0.0, 0.1, 0.0) < 0.01);
+        assert!(deviation(&taps, 0.3, 0.5, 0.0) < 0.01);
+    }
+
+    #[test]
+    fn hilbert_transformer() {
+        let taps = hilbert::<f64>(31, 0.05);
+        for i in 0..31 {
+            assert!((taps[i] + taps[30 - i]).abs() < 1e-12);
+            // odd length Hilbert transformers have zero taps at even offsets
+            if (i as i32 - 15) % 2 == 0 {
+                assert!(taps[i].abs() < 1e-6);
+            }
+        }
+        // positive taps after the center, like 2/(πn)
+        assert!(taps[16] > 0.5);
+        assert!(deviation(&taps, 0.05, 0.45, 1.0) < 0.01);
+    }
+
+    #[test]
+    fn full_band_differentiator() {
+        let taps = differentiator::<f64>(32, &[Band::new(0.0, 0.5, 1.0, 1.0)]);
+        for i in 0..32 {
+            assert!((taps[i] + taps[31 - i]).abs() < 1e-12);
+        }
+        for i in 1..50 {
+            let f = 0.5 * i as f64 / 50.0;
+            let want = 2.0 * core::f64::consts::PI * f;
+            assert!((taps.frequency_response(f).norm() - want).abs() < 0.01 * want);
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! Methods for designing IIR filters.
+//!
+//! The filters are derived from analog lowpass prototypes, which are mapped
+//! to the desired band with a frequency transformation and discretized with
+//! the bilinear transform. Band edges are given in cycles/sample and
+//! pre-warped, so that they are exact for the digital filter.
+//!
+//! Designs are returned as cascades of second-order sections ([Sos]), which
+//! are numerically more robust than a single high-order transfer function.
+//! They can be used with the [SosKernel](crate::iir::SosKernel).
+
+extern crate alloc;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+type C = Complex<f64>;
+
+/// A second-order section (biquad) with the transfer function
+/// ```text
+///        b[0] + b[1] z^-1 + b[2] z^-2
+/// H(z) = ----------------------------
+///        a[0] + a[1] z^-1 + a[2] z^-2
+/// ```
+/// The designs of this module always normalize `a[0]` to one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sos {
+    /// Feed-forward coefficients.
+    pub b: [f64; 3],
+    /// Feedback coefficients.
+    pub a: [f64; 3],
+}
+
+/// Type and edge frequencies (in cycles/sample) of a filter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BandType {
+    /// Lowpass filter with the given cutoff frequency.
+    Lowpass(f64),
+    /// Highpass filter with the given cutoff frequency.
+    Highpass(f64),
+    /// Bandpass filter with the given lower and upper cutoff frequencies.
+    Bandpass(f64, f64),
+    /// Bandstop filter with the given lower and upper cutoff frequencies.
+    Bandstop(f64, f64),
+}
+
+/// Designs a Butterworth filter with a maximally flat passband. The cutoff
+/// frequencies are the -3 dB points.
+///
+/// `order` is the order of the analog lowpass prototype. Bandpass and bandstop
+/// filters have twice this order.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::iirdes::{self, BandType};
+///
+/// // 4th-order lowpass filter with a cutoff frequency of 0.1 cycles/sample
+/// let sections = iirdes::butterworth(4, BandType::Lowpass(0.1));
+/// assert_eq!(sections.len(), 2);
+/// ```
+pub fn butterworth(order: usize, band: BandType) -> Vec<Sos> {
+    assert!(order > 0, "order must be positive");
+    let n = order as f64;
+    let poles = (0..order)
+        .map(|i| {
+            let m = 2.0 * i as f64 + 1.0 - n;
+            -C::from_polar(1.0, core::f64::consts::PI * m / (2.0 * n))
+        })
+        .collect();
+    design(
+        Zpk {
+            zeros: Vec::new(),
+            poles,
+            gain: 1.0,
+        },
+        band,
+    )
+}
+
+/// Designs a Chebyshev type I filter with `ripple_db` dB of equiripple in the
+/// passband. The cutoff frequencies are the edges of the passband, where the
+/// response drops below `-ripple_db` dB.
+///
+/// `order` is the order of the analog lowpass prototype. Bandpass and bandstop
+/// filters have twice this order.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::iirdes::{self, BandType};
+///
+/// let sections = iirdes::chebyshev1(5, 0.5, BandType::Highpass(0.2));
+/// assert_eq!(sections.len(), 3);
+/// ```
+pub fn chebyshev1(order: usize, ripple_db: f64, band: BandType) -> Vec<Sos> {
+    assert!(order > 0, "order must be positive");
+    assert!(ripple_db > 0.0, "ripple must be positive");
+    let n = order as f64;
+    let eps = (10f64.powf(ripple_db / 10.0) - 1.0).sqrt();
+    let mu = (1.0 / eps).asinh() / n;
+    let poles: Vec<C> = (0..order)
+        .map(|i| {
+            let theta = core::f64::consts::PI * (2.0 * i as f64 + 1.0 - n) / (2.0 * n);
+            -C::new(mu, theta).sinh()
+        })
+        .collect();
+    let mut gain = poles.iter().fold(C::new(1.0, 0.0), |acc, p| acc * -p).re;
+    if order & 1 == 0 {
+        gain /= (1.0 + eps * eps).sqrt();
+    }
+    design(
+        Zpk {
+            zeros: Vec::new(),
+            poles,
+            gain,
+        },
+        band,
+    )
+}
+
+/// Designs a Chebyshev type II (inverse Chebyshev) filter with a minimum
+/// attenuation of `attenuation_db` dB in the stopband. The cutoff frequencies
+/// are the edges of the stopband, where the attenuation first reaches
+/// `attenuation_db` dB.
+///
+/// `order` is the order of the analog lowpass prototype. Bandpass and bandstop
+/// filters have twice this order.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::iirdes::{self, BandType};
+///
+/// let sections = iirdes::chebyshev2(6, 60.0, BandType::Bandpass(0.1, 0.2));
+/// assert_eq!(sections.len(), 6);
+/// ```
+pub fn chebyshev2(order: usize, attenuation_db: f64, band: BandType) -> Vec<Sos> {
+    assert!(order > 0, "order must be positive");
+    assert!(attenuation_db > 0.0, "attenuation must be positive");
+    let n = order as f64;
+    let de = 1.0 / (10f64.powf(attenuation_db / 10.0) - 1.0).sqrt();
+    let mu = (1.0 / de).asinh() / n;
+    let angle = |i: usize| core::f64::consts::PI * (2.0 * i as f64 + 1.0 - n) / (2.0 * n);
+
+    // the zero at infinity of odd orders is left out
+    let zeros: Vec<C> = (0..order)
+        .filter(|i| 2 * i + 1 != order)
+        .map(|i| C::new(0.0, 1.0 / angle(i).sin()))
+        .collect();
+    let poles: Vec<C> = (0..order)
+        .map(|i| {
+            let p = -C::from_polar(1.0, angle(i));
+            C::new(mu.sinh() * p.re, mu.cosh() * p.im).inv()
+        })
+        .collect();
+    let gain = (prod_neg(&poles) / prod_neg(&zeros)).re;
+    design(Zpk { zeros, poles, gain }, band)
+}
+
+/// Designs an elliptic (Cauer) filter with `ripple_db` dB of equiripple in the
+/// passband and a minimum attenuation of `attenuation_db` dB in the stopband.
+/// The cutoff frequencies are the edges of the passband, where the response
+/// drops below `-ripple_db` dB. For a given order, elliptic filters have the
+/// narrowest transition band.
+///
+/// `order` is the order of the analog lowpass prototype. Bandpass and bandstop
+/// filters have twice this order.
+///
+/// The design follows:
+/// - S. J. Orfanidis, "Lecture Notes on Elliptic Filter Design," Rutgers
+///   University, 2006.
+///
+/// Example usage:
+/// ```
+/// use futuredsp::iirdes::{self, BandType};
+///
+/// let sections = iirdes::elliptic(4, 0.5, 60.0, BandType::Bandstop(0.2, 0.3));
+/// assert_eq!(sections.len(), 4);
+/// ```
+pub fn elliptic(order: usize, ripple_db: f64, attenuation_db: f64, band: BandType) -> Vec<Sos> {
+    assert!(order > 0, "order must be positive");
+    assert!(
+        ripple_db > 0.0 && attenuation_db > ripple_db,
+        "ripple must be positive and smaller than the attenuation"
+    );
+    let n = order as f64;
+    let ep = (10f64.powf(ripple_db / 10.0) - 1.0).sqrt();
+    let es = (10f64.powf(attenuation_db / 10.0) - 1.0).sqrt();
+    let k1 = ep / es;
+    let k1p = (1.0 - k1 * k1).sqrt();
+
+    // solve the degree equation for the selectivity k
+    let u: Vec<f64> = (1..=order / 2)
+        .map(|i| (2.0 * i as f64 - 1.0) / n)
+        .collect();
+    let kp = u.iter().fold(k1p.powi(order as i32), |acc, u| {
+        acc * sne(C::new(*u, 0.0), k1p, k1).re.powi(4)
+    });
+    let k = (1.0 - kp * kp).sqrt();
+
+    let mut zeros = Vec::new();
+    let mut poles = Vec::new();
+    let v0 = (-C::i() * asne(C::new(0.0, 1.0 / ep), k1, k1p) / n).re;
+    for u in &u {
+        let zeta = cde(C::new(*u, 0.0), k, kp).re;
+        let z = C::new(0.0, 1.0 / (k * zeta));
+        zeros.push(z);
+        zeros.push(z.conj());
+        let p = C::i() * cde(C::new(*u, -v0), k, kp);
+        poles.push(p);
+        poles.push(p.conj());
+    }
+    if order & 1 == 1 {
+        poles.push(C::i() * sne(C::new(0.0, v0), k, kp));
+    }
+
+    let mut gain = (prod_neg(&poles) / prod_neg(&zeros)).re;
+    if order & 1 == 0 {
+        gain /= (1.0 + ep * ep).sqrt();
+    }
+    design(Zpk { zeros, poles, gain }, band)
+}
+
+/// Zeros, poles, and gain of a transfer function.
+struct Zpk {
+    zeros: Vec<C>,
+    poles: Vec<C>,
+    gain: f64,
+}
+
+fn prod_neg(roots: &[C]) -> C {
+    roots.iter().fold(C::new(1.0, 0.0), |acc, r| acc * -r)
+}
+
+/// Maps the analog lowpass prototype with a cutoff of 1 rad/s to the band and
+/// discretizes it.
+fn design(prototype: Zpk, band: BandType) -> Vec<Sos> {
+    // pre-warped frequencies for the bilinear transform s = (z - 1) / (z + 1)
+    let warp = |f: f64| {
+        assert!(f > 0.0 && f < 0.5, "cutoff frequencies must be in (0, 1/2)");
+        (core::f64::consts::PI * f).tan()
+    };
+    let warp_band = |f1: f64, f2: f64| {
+        assert!(f1 < f2, "lower cutoff must be below upper cutoff");
+        let (w1, w2) = (warp(f1), warp(f2));
+        ((w1 * w2).sqrt(), w2 - w1)
+    };
+    let analog = match band {
+        BandType::Lowpass(f) => lowpass_to_lowpass(prototype, warp(f)),
+        BandType::Highpass(f) => lowpass_to_highpass(prototype, warp(f)),
+        BandType::Bandpass(f1, f2) => {
+            let (wo, bw) = warp_band(f1, f2);
+            lowpass_to_bandpass(prototype, wo, bw)
+        }
+        BandType::Bandstop(f1, f2) => {
+            let (wo, bw) = warp_band(f1, f2);
+            lowpass_to_bandstop(prototype, wo, bw)
+        }
+    };
+    zpk_to_sos(bilinear(analog))
+}
+
+fn lowpass_to_lowpass(zpk: Zpk, wo: f64) -> Zpk {
+    let degree = zpk.poles.len() - zpk.zeros.len();
+    Zpk {
+        zeros: zpk.zeros.iter().map(|z| z * wo).collect(),
+        poles: zpk.poles.iter().map(|p| p * wo).collect(),
+        gain: zpk.gain * wo.powi(degree as i32),
+    }
+}
+
+fn lowpass_to_highpass(zpk: Zpk, wo: f64) -> Zpk {
+    let degree = zpk.poles.len() - zpk.zeros.len();
+    let gain = zpk.gain * (prod_neg(&zpk.zeros) / prod_neg(&zpk.poles)).re;
+    let mut zeros: Vec<C> = zpk.zeros.iter().map(|z| wo / z).collect();
+    zeros.extend(core::iter::repeat_n(C::new(0.0, 0.0), degree));
+    Zpk {
+        zeros,
+        poles: zpk.poles.iter().map(|p| wo / p).collect(),
+        gain,
+    }
+}
+
+/// Each root `r` is split into the two roots of `s^2 - r bw s + wo^2`.
+fn split_roots(roots: impl Iterator<Item = C>, wo: f64) -> Vec<C> {
+    roots
+        .flat_map(|r| {
+            let d = (r * r - wo * wo).sqrt();
+            [r + d, r - d]
+        })
+        .collect()
+}
+
+fn lowpass_to_bandpass(zpk: Zpk, wo: f64, bw: f64) -> Zpk {
+    let degree = zpk.poles.len() - zpk.zeros.len();
+    let mut zeros = split_roots(zpk.zeros.iter().map(|z| z * bw / 2.0), wo);
+    zeros.extend(core::iter::repeat_n(C::new(0.0, 0.0), degree));
+    Zpk {
+        zeros,
+        poles: split_roots(zpk.poles.iter().map(|p| p * bw / 2.0), wo),
+        gain: zpk.gain * bw.powi(degree as i32),
+    }
+}
+
+fn lowpass_to_bandstop(zpk: Zpk, wo: f64, bw: f64) -> Zpk {
+    let degree = zpk.poles.len() - zpk.zeros.len();
+    let gain = zpk.gain * (prod_neg(&zpk.zeros) / prod_neg(&zpk.poles)).re;
+    let mut zeros = split_roots(zpk.zeros.iter().map(|z| bw / 2.0 / z), wo);
+    for _ in 0..degree {
+        zeros.push(C::new(0.0, wo));
+        zeros.push(C::new(0.0, -wo));
+    }
+    Zpk {
+        zeros,
+        poles: split_roots(zpk.poles.iter().map(|p| bw / 2.0 / p), wo),
+        gain,
+    }
+}
+
+/// Bilinear transform `s = (z - 1) / (z + 1)`. Zeros at infinity are mapped
+/// to the Nyquist frequency.
+fn bilinear(zpk: Zpk) -> Zpk {
+    let degree = zpk.poles.len() - zpk.zeros.len();
+    let one = C::new(1.0, 0.0);
+    let gain = zpk.gain
+        * (zpk.zeros.iter().fold(one, |acc, z| acc * (one - z))
+            / zpk.poles.iter().fold(one, |acc, p| acc * (one - p)))
+        .re;
+    let mut zeros: Vec<C> = zpk.zeros.iter().map(|z| (one + z) / (one - z)).collect();
+    zeros.extend(core::iter::repeat_n(-one, degree));
+    Zpk {
+        zeros,
+        poles: zpk.poles.iter().map(|p| (one + p) / (one - p)).collect(),
+        gain,
+    }
+}
+
+/// Groups roots into complex-conjugate pairs and pairs of real roots. For an
+/// odd number of real roots, one of them is left on its own.
+fn pair_roots(roots: &[C]) -> Vec<(C, Option<C>)> {
+    let is_real = |r: &C| r.im.abs() <= 1e-10 * r.norm().max(1.0);
+    let mut pairs: Vec<(C, Option<C>)> = roots
+        .iter()
+        .filter(|r| !is_real(r) && r.im > 0.0)
+        .map(|r| (*r, Some(r.conj())))
+        .collect();
+    let mut real: Vec<f64> = roots.iter().filter(|r| is_real(r)).map(|r| r.re).collect();
+    real.sort_by(|a, b| a.total_cmp(b));
+    pairs.extend(
+        real.chunks(2)
+            .map(|c| (C::new(c[0], 0.0), c.get(1).map(|r| C::new(*r, 0.0)))),
+    );
+    pairs
+}
+
+/// Coefficients of `(1 - r0 z^-1) (1 - r1 z^-1)`.
+fn pair_polynomial(pair: (C, Option<C>)) -> [f64; 3] {
+    match pair {
+        (r0, Some(r1)) => [1.0, -(r0 + r1).re, (r0 * r1).re],
+        (r0, None) => [1.0, -r0.re, 0.0],
+    }
+}
+
+/// Converts to second-order sections. Poles closest to the unit circle are
+/// paired first with their closest zeros and placed in the last sections.
+fn zpk_to_sos(zpk: Zpk) -> Vec<Sos> {
+    assert_eq!(zpk.zeros.len(), zpk.poles.len());
+    let radius = |pair: &(C, Option<C>)| pair.0.norm().max(pair.1.map_or(0.0, |r| r.norm()));
+    let mut poles = pair_roots(&zpk.poles);
+    poles.sort_by(|a, b| radius(a).total_cmp(&radius(b)));
+    let mut zeros = pair_roots(&zpk.zeros);
+
+    let mut sections: Vec<Sos> = poles
+        .iter()
+        .rev()
+        .map(|p| {
+            // prefer zeros with the same number of roots
+            let i = (0..zeros.len())
+                .min_by(|i, j| {
+                    let key = |k: usize| {
+                        let z = &zeros[k];
+                        (z.1.is_some() != p.1.is_some(), (z.0 - p.0).norm())
+                    };
+                    let (a, b) = (key(*i), key(*j));
+                    a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))
+                })
+                .unwrap();
+            Sos {
+                b: pair_polynomial(zeros.swap_remove(i)),
+                a: pair_polynomial(*p),
+            }
+        })
+        .collect();
+    sections.reverse();
+
+    for b in sections[0].b.iter_mut() {
+        *b *= zpk.gain;
+    }
+    sections
+}
+
+/// Descending sequence of moduli of the Landen transformation, starting at
+/// `k` with the complementary modulus `kp = sqrt(1 - k^2)`.
+fn landen(k: f64, kp: f64) -> Vec<f64> {
+    let mut v = Vec::new();
+    let mut k = k / (1.0 + kp);
+    k *= k;
+    while k > 1e-16 {
+        v.push(k);
+        let kp = (1.0 - k * k).sqrt();
+        k /= 1.0 + kp;
+        k *= k;
+    }
+    v.push(k);
+    v
+}
+
+/// Jacobi elliptic function `cd(uK, k)`, with `u` normalized to the quarter
+/// period `K`.
+fn cde(u: C, k: f64, kp: f64) -> C {
+    ascending_landen((u * core::f64::consts::FRAC_PI_2).cos(), k, kp)
+}
+
+/// Jacobi elliptic function `sn(uK, k)`, with `u` normalized to the quarter
+/// period `K`.
+fn sne(u: C, k: f64, kp: f64) -> C {
+    ascending_landen((u * core::f64::consts::FRAC_PI_2).sin(), k, kp)
+}
+
+fn ascending_landen(mut w: C, k: f64, kp: f64) -> C {
+    for v in landen(k, kp).iter().rev() {
+        w = (1.0 + v) * w / (1.0 + v * w * w);
+    }
+    w
+}
+
+/// Inverse of [sne], i.e., the normalized `u` with `sn(uK, k) = w`.
+fn asne(w: C, k: f64, kp: f64) -> C {
+    let mut w = w;
+    let mut prev = k;
+    for v in landen(k, kp) {
+        w = w / (1.0 + (1.0 - w * w * prev * prev).sqrt()) * 2.0 / (1.0 + v);
+        prev = v;
+    }
+    // sn(uK) = cd((1 - u)K)
+    1.0 - w.acos() / core::f64::consts::FRAC_PI_2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::FilterResponse;
+
+    fn assert_stable(sections: &[Sos]) {
+        for s in sections {
+            // roots of z^2 + a1 z + a2 are inside the unit circle
+            assert!(s.a[2].abs() < 1.0);
+            assert!(s.a[1].abs() < 1.0 + s.a[2]);
+        }
+    }
+
+    fn sweep(from: f64, to: f64) -> impl Iterator<Item = f64> {
+        (0..=200).map(move |i| from + (to - from) * i as f64 / 200.0)
+    }
+
+    #[test]
+    fn butterworth_lowpass() {
+        for order in 1..8 {
+            let sections = butterworth(order, BandType::Lowpass(0.1));
+            assert_eq!(sections.len(), order.div_ceil(2));
+            assert_stable(&sections);
+            assert!(sections.magnitude_db(0.0).abs() < 1e-9);
+            assert!((sections.magnitude_db(0.1) + 3.0103).abs() < 1e-3);
+            assert!(sections.magnitude_db(0.5) < -100.0);
+        }
+    }
+
+    #[test]
+    fn butterworth_bands() {
+        let hp = butterworth(5, BandType::Highpass(0.3));
+        assert_stable(&hp);
+        assert!(hp.magnitude_db(0.5).abs() < 1e-9);
+        assert!((hp.magnitude_db(0.3) + 3.0103).abs() < 1e-3);
+
+        let bp = butterworth(3, BandType::Bandpass(0.1, 0.2));
+        assert_eq!(bp.len(), 3);
+        assert_stable(&bp);
+        assert!((bp.magnitude_db(0.1) + 3.0103).abs() < 1e-3);
+        assert!((bp.magnitude_db(0.2) + 3.0103).abs() < 1e-3);
+        assert!(bp.magnitude_db(0.0) < -100.0);
+
+        let bs = butterworth(3, BandType::Bandstop(0.1, 0.2));
+        assert_stable(&bs);
+        assert!(bs.magnitude_db(0.0).abs() < 1e-9);
+        assert!(bs.magnitude_db(0.5).abs() < 1e-9);
+        assert!((bs.magnitude_db(0.2) + 3.0103).abs() < 1e-3);
+    }
+
+    #[test]
+    fn chebyshev1_ripple() {
+        for order in [4, 5] {
+            let sections = chebyshev1(order, 1.0, BandType::Lowpass(0.2));
+            assert_stable(&sections);
+            for f in sweep(0.0, 0.2) {
+                let g = sections.magnitude_db(f);
+                assert!((-1.0 - 1e-6..=1e-6).contains(&g), "{f} {g}");
+            }
+            assert!((sections.magnitude_db(0.2) + 1.0).abs() < 1e-6);
+            assert!(sections.magnitude_db(0.3) < -20.0);
+        }
+    }
+
+    #[test]
+    fn chebyshev2_stopband() {
+        for order in [4, 5] {
+            let sections = chebyshev2(order, 40.0, BandType::Highpass(0.2));
+            assert_stable(&sections);
+            assert!(sections.magnitude_db(0.5).abs() < 1e-9);
+            for f in sweep(0.0, 0.2) {
+                assert!(sections.magnitude_db(f) < -40.0 + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn elliptic_ripple_and_stopband() {
+        for order in [3, 4, 5] {
+            let sections = elliptic(order, 0.5, 50.0, BandType::Lowpass(0.1));
+            assert_stable(&sections);
+            for f in sweep(0.0, 0.1) {
+                let g = sections.magnitude_db(f);
+                assert!((-0.5 - 1e-6..=1e-6).contains(&g), "{f} {g}");
+            }
+            // the stopband starts before the edge of a Chebyshev filter
+            let cheby = chebyshev1(order, 0.5, BandType::Lowpass(0.1));
+            let edge = sweep(0.1, 0.5)
+                .find(|f| sections.magnitude_db(*f) < -50.0)
+                .unwrap();
+            for f in sweep(edge, 0.5) {
+                assert!(sections.magnitude_db(f) < -50.0 + 1e-6, "{f}");
+            }
+            assert!(cheby.magnitude_db(edge) > -50.0);
+        }
+    }
+
+    #[test]
+    fn elliptic_bandpass() {
+        let sections = elliptic(4, 1.0, 60.0, BandType::Bandpass(0.2, 0.3));
+        assert_eq!(sections.len(), 4);
+        assert_stable(&sections);
+        for f in sweep(0.2, 0.3) {
+            assert!(sections.magnitude_db(f) > -1.0 - 1e-6);
+        }
+        assert!(sections.magnitude_db(0.05) < -60.0 + 1e-6);
+        assert!(sections.magnitude_db(0.45) < -60.0 + 1e-6);
+    }
+}
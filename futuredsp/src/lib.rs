@@ -7,10 +7,15 @@ pub extern crate log;
 #[macro_use]
 extern crate alloc;
 
+pub mod analysis;
+pub mod fec;
+pub mod fft;
 pub mod fir;
 pub mod firdes;
 pub mod iir;
+pub mod iirdes;
 pub mod math;
+pub mod simd;
 pub mod windows;
 
 mod tapsaccessor;
@@ -0,0 +1,110 @@
+//! NEON implementations.
+
+use super::Isa;
+
+pub(super) fn detect() -> Isa {
+    // NEON is part of the baseline of all common aarch64 targets
+    if cfg!(target_feature = "neon") {
+        Isa::Neon
+    } else {
+        Isa::Scalar
+    }
+}
+
+pub(super) mod neon {
+    use core::arch::aarch64::*;
+
+    /// Sums of the even and the odd lanes.
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn sum_pairs(v: float32x4_t) -> (f32, f32) {
+        let mut l = [0.0f32; 4];
+        vst1q_f32(l.as_mut_ptr(), v);
+        (l[0] + l[2], l[1] + l[3])
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn dot_real(x: &[f32], t: &[f32]) -> f32 {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        let mut a0 = vdupq_n_f32(0.0);
+        let mut a1 = vdupq_n_f32(0.0);
+        let mut k = 0;
+        while k + 8 <= n {
+            a0 = vfmaq_f32(a0, vld1q_f32(xp.add(k)), vld1q_f32(tp.add(k)));
+            a1 = vfmaq_f32(a1, vld1q_f32(xp.add(k + 4)), vld1q_f32(tp.add(k + 4)));
+            k += 8;
+        }
+        if k + 4 <= n {
+            a0 = vfmaq_f32(a0, vld1q_f32(xp.add(k)), vld1q_f32(tp.add(k)));
+            k += 4;
+        }
+        let (e, o) = sum_pairs(vaddq_f32(a0, a1));
+        let mut sum = e + o;
+        while k < n {
+            sum += *xp.add(k) * *tp.add(k);
+            k += 1;
+        }
+        sum
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn dot_complex_real(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        let mut a0 = vdupq_n_f32(0.0);
+        let mut a1 = vdupq_n_f32(0.0);
+        let mut k = 0;
+        while k + 4 <= n {
+            let tv = vld1q_f32(tp.add(k));
+            // duplicate each tap for the real and the imaginary part
+            a0 = vfmaq_f32(a0, vld1q_f32(xp.add(2 * k)), vzip1q_f32(tv, tv));
+            a1 = vfmaq_f32(a1, vld1q_f32(xp.add(2 * k + 4)), vzip2q_f32(tv, tv));
+            k += 4;
+        }
+        let (mut re, mut im) = sum_pairs(vaddq_f32(a0, a1));
+        while k < n {
+            re += *xp.add(2 * k) * *tp.add(k);
+            im += *xp.add(2 * k + 1) * *tp.add(k);
+            k += 1;
+        }
+        (re, im)
+    }
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn dot_complex(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        // a accumulates (re * re, im * im), b accumulates (re * im, im * re)
+        let mut a = vdupq_n_f32(0.0);
+        let mut b = vdupq_n_f32(0.0);
+        let mut k = 0;
+        while k + 4 <= n {
+            let xv = vld1q_f32(xp.add(k));
+            let tv = vld1q_f32(tp.add(k));
+            a = vfmaq_f32(a, xv, tv);
+            b = vfmaq_f32(b, xv, vrev64q_f32(tv));
+            k += 4;
+        }
+        let (ae, ao) = sum_pairs(a);
+        let (be, bo) = sum_pairs(b);
+        let mut re = ae - ao;
+        let mut im = be + bo;
+        while k < n {
+            let (xr, xi) = (*xp.add(k), *xp.add(k + 1));
+            let (tr, ti) = (*tp.add(k), *tp.add(k + 1));
+            re += xr * tr - xi * ti;
+            im += xr * ti + xi * tr;
+            k += 2;
+        }
+        (re, im)
+    }
+
+    drivers!("neon");
+}
@@ -0,0 +1,211 @@
+//! Runtime detection of SIMD instruction sets.
+//!
+//! The FIR kernels use SIMD implementations, if the CPU supports one of the
+//! accelerated instruction sets, and fall back to scalar code otherwise.
+//! Detection happens once, on first use, and does not require `std`.
+//!
+//! Example usage:
+//! ```
+//! use futuredsp::simd;
+//!
+//! println!("FIR kernels use {:?}", simd::active());
+//!
+//! // force the scalar implementation, e.g., for benchmarks
+//! simd::set_enabled(false);
+//! assert_eq!(simd::active(), simd::Isa::Scalar);
+//! ```
+
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// Generates the loops over all outputs for an instruction set. They are
+/// compiled with the target features enabled, so that the dot products and
+/// the closures of the caller get inlined.
+macro_rules! drivers {
+    ($feature:literal) => {
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn real<'a>(
+            n: usize,
+            inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+            mut output: impl FnMut(usize, f32),
+        ) {
+            for k in 0..n {
+                let (x, t) = inputs(k);
+                assert!(x.len() >= t.len());
+                output(k, dot_real(x, t));
+            }
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn complex_real<'a>(
+            n: usize,
+            inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+            mut output: impl FnMut(usize, f32, f32),
+        ) {
+            for k in 0..n {
+                let (x, t) = inputs(k);
+                assert!(x.len() >= 2 * t.len());
+                let (re, im) = dot_complex_real(x, t);
+                output(k, re, im);
+            }
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn complex<'a>(
+            n: usize,
+            inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+            mut output: impl FnMut(usize, f32, f32),
+        ) {
+            for k in 0..n {
+                let (x, t) = inputs(k);
+                assert!(x.len() >= t.len() && t.len() & 1 == 0);
+                let (re, im) = dot_complex(x, t);
+                output(k, re, im);
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+/// Instruction sets with SIMD implementations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Isa {
+    /// Portable scalar implementation.
+    Scalar,
+    /// x86 SSE4.1.
+    Sse41,
+    /// x86 AVX2 and FMA.
+    Avx2Fma,
+    /// ARM NEON.
+    Neon,
+}
+
+const UNKNOWN: u8 = u8::MAX;
+static DETECTED: AtomicU8 = AtomicU8::new(UNKNOWN);
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+impl Isa {
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => Isa::Sse41,
+            2 => Isa::Avx2Fma,
+            3 => Isa::Neon,
+            _ => Isa::Scalar,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Isa::Scalar => 0,
+            Isa::Sse41 => 1,
+            Isa::Avx2Fma => 2,
+            Isa::Neon => 3,
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn detect() -> Isa {
+    x86::detect()
+}
+
+#[cfg(target_arch = "aarch64")]
+fn detect() -> Isa {
+    aarch64::detect()
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect() -> Isa {
+    Isa::Scalar
+}
+
+/// The best instruction set supported by the CPU.
+pub fn detected() -> Isa {
+    let v = DETECTED.load(Ordering::Relaxed);
+    if v != UNKNOWN {
+        return Isa::from_u8(v);
+    }
+    let isa = detect();
+    DETECTED.store(isa.to_u8(), Ordering::Relaxed);
+    isa
+}
+
+/// The instruction set used by the kernels.
+pub fn active() -> Isa {
+    if ENABLED.load(Ordering::Relaxed) {
+        detected()
+    } else {
+        Isa::Scalar
+    }
+}
+
+/// Enable or disable the SIMD implementations globally.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Computes `n` dot products of real samples with real taps.
+///
+/// For output `k`, `inputs(k)` returns the samples and the taps, and the
+/// result is passed to `output`. Returns `false` without computing anything,
+/// if no SIMD implementation is active.
+pub(crate) fn dot_real_each<'a>(
+    n: usize,
+    inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+    output: impl FnMut(usize, f32),
+) -> bool {
+    match active() {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Avx2Fma => unsafe { x86::avx2::real(n, inputs, output) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Sse41 => unsafe { x86::sse41::real(n, inputs, output) },
+        #[cfg(target_arch = "aarch64")]
+        Isa::Neon => unsafe { aarch64::neon::real(n, inputs, output) },
+        _ => return false,
+    }
+    true
+}
+
+/// Computes `n` dot products of complex samples with real taps.
+///
+/// Samples are interleaved real and imaginary parts. See [dot_real_each].
+pub(crate) fn dot_complex_real_each<'a>(
+    n: usize,
+    inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+    output: impl FnMut(usize, f32, f32),
+) -> bool {
+    match active() {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Avx2Fma => unsafe { x86::avx2::complex_real(n, inputs, output) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Sse41 => unsafe { x86::sse41::complex_real(n, inputs, output) },
+        #[cfg(target_arch = "aarch64")]
+        Isa::Neon => unsafe { aarch64::neon::complex_real(n, inputs, output) },
+        _ => return false,
+    }
+    true
+}
+
+/// Computes `n` dot products of complex samples with complex taps.
+///
+/// Samples and taps are interleaved real and imaginary parts. See
+/// [dot_real_each].
+pub(crate) fn dot_complex_each<'a>(
+    n: usize,
+    inputs: impl Fn(usize) -> (&'a [f32], &'a [f32]),
+    output: impl FnMut(usize, f32, f32),
+) -> bool {
+    match active() {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Avx2Fma => unsafe { x86::avx2::complex(n, inputs, output) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Isa::Sse41 => unsafe { x86::sse41::complex(n, inputs, output) },
+        #[cfg(target_arch = "aarch64")]
+        Isa::Neon => unsafe { aarch64::neon::complex(n, inputs, output) },
+        _ => return false,
+    }
+    true
+}
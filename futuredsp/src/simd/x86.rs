@@ -0,0 +1,262 @@
+//! SSE4.1 and AVX2/FMA implementations.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::Isa;
+
+pub(super) fn detect() -> Isa {
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return Isa::Scalar;
+    }
+
+    unsafe {
+        let max_leaf = __cpuid(0).eax;
+        if max_leaf < 1 {
+            return Isa::Scalar;
+        }
+        let leaf1 = __cpuid(1);
+        let sse41 = leaf1.ecx & (1 << 19) != 0;
+        let fma = leaf1.ecx & (1 << 12) != 0;
+        let osxsave = leaf1.ecx & (1 << 27) != 0;
+        let avx = leaf1.ecx & (1 << 28) != 0;
+        let avx2 = max_leaf >= 7 && __cpuid_count(7, 0).ebx & (1 << 5) != 0;
+        // the OS has to preserve the YMM registers
+        let ymm = osxsave && avx && xgetbv0() & 0b110 == 0b110;
+
+        if avx2 && fma && ymm {
+            Isa::Avx2Fma
+        } else if sse41 {
+            Isa::Sse41
+        } else {
+            Isa::Scalar
+        }
+    }
+}
+
+#[target_feature(enable = "xsave")]
+unsafe fn xgetbv0() -> u64 {
+    _xgetbv(0)
+}
+
+pub(super) mod avx2 {
+    use super::*;
+
+    /// Sums of the even and the odd lanes.
+    #[inline]
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sum_pairs(v: __m256) -> (f32, f32) {
+        let mut l = [0.0f32; 8];
+        _mm256_storeu_ps(l.as_mut_ptr(), v);
+        (l[0] + l[2] + l[4] + l[6], l[1] + l[3] + l[5] + l[7])
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn dot_real(x: &[f32], t: &[f32]) -> f32 {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        let mut a0 = _mm256_setzero_ps();
+        let mut a1 = _mm256_setzero_ps();
+        let mut k = 0;
+        while k + 16 <= n {
+            a0 = _mm256_fmadd_ps(_mm256_loadu_ps(xp.add(k)), _mm256_loadu_ps(tp.add(k)), a0);
+            a1 = _mm256_fmadd_ps(
+                _mm256_loadu_ps(xp.add(k + 8)),
+                _mm256_loadu_ps(tp.add(k + 8)),
+                a1,
+            );
+            k += 16;
+        }
+        if k + 8 <= n {
+            a0 = _mm256_fmadd_ps(_mm256_loadu_ps(xp.add(k)), _mm256_loadu_ps(tp.add(k)), a0);
+            k += 8;
+        }
+        let (e, o) = sum_pairs(_mm256_add_ps(a0, a1));
+        let mut sum = e + o;
+        while k < n {
+            sum += *xp.add(k) * *tp.add(k);
+            k += 1;
+        }
+        sum
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn dot_complex_real(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        // duplicate each tap for the real and the imaginary part
+        let dup = _mm256_setr_epi32(0, 0, 1, 1, 2, 2, 3, 3);
+        let mut a0 = _mm256_setzero_ps();
+        let mut a1 = _mm256_setzero_ps();
+        let mut k = 0;
+        while k + 8 <= n {
+            let t0 = _mm256_permutevar8x32_ps(_mm256_castps128_ps256(_mm_loadu_ps(tp.add(k))), dup);
+            let t1 =
+                _mm256_permutevar8x32_ps(_mm256_castps128_ps256(_mm_loadu_ps(tp.add(k + 4))), dup);
+            a0 = _mm256_fmadd_ps(_mm256_loadu_ps(xp.add(2 * k)), t0, a0);
+            a1 = _mm256_fmadd_ps(_mm256_loadu_ps(xp.add(2 * k + 8)), t1, a1);
+            k += 8;
+        }
+        if k + 4 <= n {
+            let t0 = _mm256_permutevar8x32_ps(_mm256_castps128_ps256(_mm_loadu_ps(tp.add(k))), dup);
+            a0 = _mm256_fmadd_ps(_mm256_loadu_ps(xp.add(2 * k)), t0, a0);
+            k += 4;
+        }
+        let (mut re, mut im) = sum_pairs(_mm256_add_ps(a0, a1));
+        while k < n {
+            re += *xp.add(2 * k) * *tp.add(k);
+            im += *xp.add(2 * k + 1) * *tp.add(k);
+            k += 1;
+        }
+        (re, im)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn dot_complex(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        // a accumulates (re * re, im * im), b accumulates (re * im, im * re)
+        let mut a = _mm256_setzero_ps();
+        let mut b = _mm256_setzero_ps();
+        let mut k = 0;
+        while k + 8 <= n {
+            let xv = _mm256_loadu_ps(xp.add(k));
+            let tv = _mm256_loadu_ps(tp.add(k));
+            a = _mm256_fmadd_ps(xv, tv, a);
+            b = _mm256_fmadd_ps(xv, _mm256_permute_ps(tv, 0xb1), b);
+            k += 8;
+        }
+        let (ae, ao) = sum_pairs(a);
+        let (be, bo) = sum_pairs(b);
+        let mut re = ae - ao;
+        let mut im = be + bo;
+        while k < n {
+            let (xr, xi) = (*xp.add(k), *xp.add(k + 1));
+            let (tr, ti) = (*tp.add(k), *tp.add(k + 1));
+            re += xr * tr - xi * ti;
+            im += xr * ti + xi * tr;
+            k += 2;
+        }
+        (re, im)
+    }
+
+    drivers!("avx2,fma");
+}
+
+pub(super) mod sse41 {
+    use super::*;
+
+    /// Sums of the even and the odd lanes.
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn sum_pairs(v: __m128) -> (f32, f32) {
+        let mut l = [0.0f32; 4];
+        _mm_storeu_ps(l.as_mut_ptr(), v);
+        (l[0] + l[2], l[1] + l[3])
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn dot_real(x: &[f32], t: &[f32]) -> f32 {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        let mut a0 = _mm_setzero_ps();
+        let mut a1 = _mm_setzero_ps();
+        let mut k = 0;
+        while k + 8 <= n {
+            a0 = _mm_add_ps(
+                a0,
+                _mm_mul_ps(_mm_loadu_ps(xp.add(k)), _mm_loadu_ps(tp.add(k))),
+            );
+            a1 = _mm_add_ps(
+                a1,
+                _mm_mul_ps(_mm_loadu_ps(xp.add(k + 4)), _mm_loadu_ps(tp.add(k + 4))),
+            );
+            k += 8;
+        }
+        if k + 4 <= n {
+            a0 = _mm_add_ps(
+                a0,
+                _mm_mul_ps(_mm_loadu_ps(xp.add(k)), _mm_loadu_ps(tp.add(k))),
+            );
+            k += 4;
+        }
+        let (e, o) = sum_pairs(_mm_add_ps(a0, a1));
+        let mut sum = e + o;
+        while k < n {
+            sum += *xp.add(k) * *tp.add(k);
+            k += 1;
+        }
+        sum
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn dot_complex_real(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        let mut a0 = _mm_setzero_ps();
+        let mut a1 = _mm_setzero_ps();
+        let mut k = 0;
+        while k + 4 <= n {
+            let tv = _mm_loadu_ps(tp.add(k));
+            // duplicate each tap for the real and the imaginary part
+            let lo = _mm_unpacklo_ps(tv, tv);
+            let hi = _mm_unpackhi_ps(tv, tv);
+            a0 = _mm_add_ps(a0, _mm_mul_ps(_mm_loadu_ps(xp.add(2 * k)), lo));
+            a1 = _mm_add_ps(a1, _mm_mul_ps(_mm_loadu_ps(xp.add(2 * k + 4)), hi));
+            k += 4;
+        }
+        let (mut re, mut im) = sum_pairs(_mm_add_ps(a0, a1));
+        while k < n {
+            re += *xp.add(2 * k) * *tp.add(k);
+            im += *xp.add(2 * k + 1) * *tp.add(k);
+            k += 1;
+        }
+        (re, im)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn dot_complex(x: &[f32], t: &[f32]) -> (f32, f32) {
+        let n = t.len();
+        let xp = x.as_ptr();
+        let tp = t.as_ptr();
+        // a accumulates (re * re, im * im), b accumulates (re * im, im * re)
+        let mut a = _mm_setzero_ps();
+        let mut b = _mm_setzero_ps();
+        let mut k = 0;
+        while k + 4 <= n {
+            let xv = _mm_loadu_ps(xp.add(k));
+            let tv = _mm_loadu_ps(tp.add(k));
+            a = _mm_add_ps(a, _mm_mul_ps(xv, tv));
+            b = _mm_add_ps(b, _mm_mul_ps(xv, _mm_shuffle_ps(tv, tv, 0xb1)));
+            k += 4;
+        }
+        let (ae, ao) = sum_pairs(a);
+        let (be, bo) = sum_pairs(b);
+        let mut re = ae - ao;
+        let mut im = be + bo;
+        while k < n {
+            let (xr, xi) = (*xp.add(k), *xp.add(k + 1));
+            let (tr, ti) = (*tp.add(k), *tp.add(k + 1));
+            re += xr * tr - xi * ti;
+            im += xr * ti + xi * tr;
+            k += 2;
+        }
+        (re, im)
+    }
+
+    drivers!("sse4.1");
+}
@@ -1,5 +1,6 @@
 extern crate alloc;
 use alloc::vec::Vec;
+use num_complex::Complex;
 
 pub trait TapsAccessor: Send {
     type TapType;
@@ -51,3 +52,42 @@ impl TapsAccessor for Vec<f32> {
         *self.get_unchecked(index)
     }
 }
+
+impl<const N: usize> TapsAccessor for [Complex<f32>; N] {
+    type TapType = Complex<f32>;
+
+    fn num_taps(&self) -> usize {
+        N
+    }
+
+    unsafe fn get(&self, index: usize) -> Complex<f32> {
+        debug_assert!(index < self.num_taps());
+        *self.get_unchecked(index)
+    }
+}
+
+impl<const N: usize> TapsAccessor for &[Complex<f32>; N] {
+    type TapType = Complex<f32>;
+
+    fn num_taps(&self) -> usize {
+        N
+    }
+
+    unsafe fn get(&self, index: usize) -> Complex<f32> {
+        debug_assert!(index < self.num_taps());
+        *self.get_unchecked(index)
+    }
+}
+
+impl TapsAccessor for Vec<Complex<f32>> {
+    type TapType = Complex<f32>;
+
+    fn num_taps(&self) -> usize {
+        self.len()
+    }
+
+    unsafe fn get(&self, index: usize) -> Complex<f32> {
+        debug_assert!(index < self.num_taps());
+        *self.get_unchecked(index)
+    }
+}
@@ -68,7 +68,7 @@ pub fn gen_cos(len: usize, coeffs: &[f64], periodic: bool) -> Vec<f64> {
         })
         .collect();
     if truncate {
-        taps.remove(len);
+        taps.pop();
     }
     taps
 }
@@ -498,4 +498,14 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn periodic_window() {
+        let window = hann(8, true);
+        assert_eq!(window.len(), 8);
+        let symmetric = hann(9, false);
+        for (w, s) in window.iter().zip(symmetric.iter()) {
+            assert!((w - s).abs() < 1e-12);
+        }
+    }
 }
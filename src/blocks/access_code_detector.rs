@@ -0,0 +1,145 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+pub struct AccessCodeDetector {
+    code: u64,
+    mask: u64,
+    len: usize,
+    max_errors: u32,
+    shift_reg: u64,
+    n_bits: usize,
+    pending: Option<usize>,
+}
+
+impl AccessCodeDetector {
+    pub fn new(code: Vec<u8>, max_errors: u32) -> Block {
+        assert!(
+            !code.is_empty() && code.len() <= 64,
+            "access code must have 1 to 64 bits"
+        );
+        let len = code.len();
+
+        Block::new(
+            BlockMetaBuilder::new("AccessCodeDetector").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<u8>("in")
+                .add_typed_output::<u8>("out")
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            AccessCodeDetector {
+                code: code.iter().fold(0, |acc, b| (acc << 1) | (*b & 1) as u64),
+                mask: if len == 64 { u64::MAX } else { (1 << len) - 1 },
+                len,
+                max_errors,
+                shift_reg: 0,
+                n_bits: 0,
+                pending: None,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for AccessCodeDetector {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<u8>();
+
+        let n = cmp::min(i.len(), o.len());
+
+        for (k, b) in i.iter().take(n).enumerate() {
+            // tag the first bit after the access code
+            if let Some(errors) = self.pending.take() {
+                sio.output(0)
+                    .add_tag(k, Tag::NamedUsize("access_code".to_string(), errors));
+            }
+
+            self.shift_reg = (self.shift_reg << 1) | (*b & 1) as u64;
+            self.n_bits = cmp::min(self.n_bits + 1, self.len);
+
+            if self.n_bits == self.len {
+                let errors = ((self.shift_reg ^ self.code) & self.mask).count_ones();
+                if errors <= self.max_errors {
+                    self.pending = Some(errors as usize);
+                }
+            }
+        }
+
+        o[..n].copy_from_slice(&i[..n]);
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Detects an access code (sync word) in a stream of bits.
+///
+/// Consumes one bit per `u8` (only the least significant bit is considered)
+/// and passes the bits through. The first bit after an access code with at
+/// most `max_errors` bit errors is tagged with
+/// `Tag::NamedUsize("access_code", errors)`.
+///
+/// # Inputs
+///
+/// `in`: Unpacked bits
+///
+/// # Outputs
+///
+/// `out`: Tagged bits
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::AccessCodeDetectorBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let code = vec![1, 0, 1, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0, 1, 0, 0];
+/// let detector = fg.add_block(AccessCodeDetectorBuilder::new(code).max_errors(1).build());
+/// ```
+pub struct AccessCodeDetectorBuilder {
+    code: Vec<u8>,
+    max_errors: u32,
+}
+
+impl AccessCodeDetectorBuilder {
+    pub fn new(code: Vec<u8>) -> AccessCodeDetectorBuilder {
+        AccessCodeDetectorBuilder {
+            code,
+            max_errors: 0,
+        }
+    }
+
+    /// Maximum number of bit errors in a detected access code.
+    #[must_use]
+    pub fn max_errors(mut self, max_errors: u32) -> AccessCodeDetectorBuilder {
+        self.max_errors = max_errors;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        AccessCodeDetector::new(self.code, self.max_errors)
+    }
+}
@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -65,8 +63,8 @@ where
         Block::new(
             BlockMetaBuilder::new("Apply").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out", mem::size_of::<B>())
+                .add_typed_input::<A>("in")
+                .add_typed_output::<B>("out")
                 .build(),
             MessageIoBuilder::<Apply<A, B>>::new().build(),
             Apply { f: Box::new(f) },
@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -32,8 +30,8 @@ where
         Block::new(
             BlockMetaBuilder::new("ApplyIntoIter").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out", mem::size_of::<B::Item>())
+                .add_typed_input::<A>("in")
+                .add_typed_output::<B::Item>("out")
                 .build(),
             MessageIoBuilder::<ApplyIntoIter<A, B>>::new().build(),
             ApplyIntoIter {
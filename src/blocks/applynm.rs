@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -57,8 +55,8 @@ where
         Block::new(
             BlockMetaBuilder::new("ApplyNM").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out", mem::size_of::<B>())
+                .add_typed_input::<A>("in")
+                .add_typed_output::<B>("out")
                 .build(),
             MessageIoBuilder::<ApplyNM<A, B, N, M>>::new().build(),
             ApplyNM { f: Box::new(f) },
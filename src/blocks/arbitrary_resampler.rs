@@ -0,0 +1,199 @@
+use futures::FutureExt;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+use futuredsp::fir::ArbitraryResamplingFirKernel;
+use futuredsp::firdes;
+use futuredsp::StatefulUnaryKernel;
+
+/// Maximum passband ripple and stopband attenuation of the designed filters.
+const MAX_RIPPLE: f64 = 0.001;
+
+pub struct ArbitraryResampler<SampleType>
+where
+    SampleType: 'static + Send,
+    ArbitraryResamplingFirKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+{
+    core: ArbitraryResamplingFirKernel<SampleType>,
+    num_filters: usize,
+    /// Whether the taps are designed for the rate and redesigned on changes.
+    design_taps: bool,
+}
+
+unsafe impl<SampleType> Send for ArbitraryResampler<SampleType>
+where
+    SampleType: 'static + Send,
+    ArbitraryResamplingFirKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+{
+}
+
+impl<SampleType> ArbitraryResampler<SampleType>
+where
+    SampleType: 'static + Send,
+    ArbitraryResamplingFirKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+{
+    pub fn new(rate: f64, num_filters: usize, taps: Option<Vec<f32>>) -> Block {
+        let design_taps = taps.is_none();
+        let taps = taps.unwrap_or_else(|| {
+            firdes::kaiser::arbitrary_resampling::<f32>(rate, num_filters, MAX_RIPPLE)
+        });
+        Block::new(
+            BlockMetaBuilder::new("ArbitraryResampler").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<SampleType>("in")
+                .add_typed_output::<SampleType>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "rate",
+                    |block: &mut ArbitraryResampler<SampleType>,
+                     _mio: &mut MessageIo<ArbitraryResampler<SampleType>>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            match &p {
+                                Pmt::Double(r) if r.is_finite() && *r > 0.0 => {
+                                    block.set_rate(*r);
+                                }
+                                Pmt::Null => {}
+                                _ => {
+                                    warn!(
+                                        "ArbitraryResampler/rate Handler received wrong PMT {:?}",
+                                        &p
+                                    );
+                                }
+                            }
+                            Ok(Pmt::Double(block.core.rate()))
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            ArbitraryResampler {
+                core: ArbitraryResamplingFirKernel::new(rate, num_filters, taps),
+                num_filters,
+                design_taps,
+            },
+        )
+    }
+
+    fn set_rate(&mut self, rate: f64) {
+        self.core.set_rate(rate);
+        if self.design_taps {
+            let taps =
+                firdes::kaiser::arbitrary_resampling::<f32>(rate, self.num_filters, MAX_RIPPLE);
+            self.core.set_taps(self.num_filters, taps);
+        }
+    }
+}
+
+#[async_trait]
+impl<SampleType> Kernel for ArbitraryResampler<SampleType>
+where
+    SampleType: 'static + Send,
+    ArbitraryResamplingFirKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+{
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<SampleType>();
+        let o = sio.output(0).slice::<SampleType>();
+
+        let (consumed, produced, status) = self.core.work(i, o);
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        if sio.input(0).finished() && status.produced_all_samples() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Resamples by an arbitrary, real-valued ratio.
+///
+/// Uses a polyphase filter bank with linear interpolation between the arms
+/// ([ArbitraryResamplingFirKernel]). Unless custom taps are given, the
+/// prototype filter is designed for the ratio and redesigned when the ratio
+/// changes. Implementations exist for `f32` and `Complex<f32>` samples.
+///
+/// # Inputs
+///
+/// `in`: Input
+///
+/// **Message**: `rate`: set the resampling ratio (output rate / input rate);
+/// accepts a positive [`Pmt::Double`] and returns the current ratio
+///
+/// # Outputs
+///
+/// `out`: Output
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::ArbitraryResamplerBuilder;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let resamp = fg.add_block(ArbitraryResamplerBuilder::new(48e3 / 2.4e6).build::<f32>());
+/// let resamp = fg.add_block(
+///     ArbitraryResamplerBuilder::new(44.1e3 / 1e6)
+///         .num_filters(64)
+///         .build::<Complex<f32>>(),
+/// );
+/// ```
+pub struct ArbitraryResamplerBuilder {
+    rate: f64,
+    num_filters: usize,
+    taps: Option<Vec<f32>>,
+}
+
+impl ArbitraryResamplerBuilder {
+    /// Create a resampler with the ratio `rate` (output rate / input rate).
+    pub fn new(rate: f64) -> ArbitraryResamplerBuilder {
+        ArbitraryResamplerBuilder {
+            rate,
+            num_filters: 32,
+            taps: None,
+        }
+    }
+
+    /// Number of polyphase arms, i.e., the resolution of the fractional delay.
+    #[must_use]
+    pub fn num_filters(mut self, num_filters: usize) -> ArbitraryResamplerBuilder {
+        self.num_filters = num_filters;
+        self
+    }
+
+    /// Use custom prototype filter taps, designed for an interpolation by
+    /// `num_filters`. They are kept when the ratio changes.
+    #[must_use]
+    pub fn taps(mut self, taps: Vec<f32>) -> ArbitraryResamplerBuilder {
+        self.taps = Some(taps);
+        self
+    }
+
+    pub fn build<SampleType>(self) -> Block
+    where
+        SampleType: 'static + Send,
+        ArbitraryResamplingFirKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+    {
+        ArbitraryResampler::<SampleType>::new(self.rate, self.num_filters, self.taps)
+    }
+}
@@ -34,7 +34,9 @@ impl AudioSource {
     pub fn new(sample_rate: u32, channels: u16) -> Block {
         Block::new(
             BlockMetaBuilder::new("AudioSource").build(),
-            StreamIoBuilder::new().add_output("out", 4).build(),
+            StreamIoBuilder::new()
+                .add_typed_output::<f32>("out")
+                .build(),
             MessageIoBuilder::new().build(),
             AudioSource {
                 sample_rate,
@@ -26,7 +26,9 @@ impl FileSource {
 
         Block::new(
             BlockMetaBuilder::new("FileSource").build(),
-            StreamIoBuilder::new().add_output("out", 4).build(),
+            StreamIoBuilder::new()
+                .add_typed_output::<f32>("out")
+                .build(),
             MessageIoBuilder::new().build(),
             FileSource {
                 src: Box::new(source.convert_samples()),
@@ -53,9 +53,7 @@ impl<T: Send + 'static + hound::Sample + Copy> WavSink<T> {
         let writer = hound::WavWriter::create(file_name, spec).unwrap();
         Block::new(
             BlockMetaBuilder::new("WavSink").build(),
-            StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<T>())
-                .build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
             MessageIoBuilder::new().build(),
             WavSink::<T> {
                 writer,
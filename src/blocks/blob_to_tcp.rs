@@ -0,0 +1,153 @@
+use async_io::Async;
+use futures::io::AsyncWriteExt;
+use futures::FutureExt;
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Sends [`Pmt::Blob`] PDUs to TCP clients.
+///
+/// The block listens on a local address and sends each PDU to all connected
+/// clients, framed by its length as big-endian `u32`, like expected by
+/// [TcpToBlob](crate::blocks::TcpToBlobBuilder). PDUs are dropped, when no client is
+/// connected.
+///
+/// # Inputs
+///
+/// **Message**: `in`: PDUs as [`Pmt::Blob`]
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::BlobToTcp;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(BlobToTcp::new("127.0.0.1:5557"));
+/// ```
+pub struct BlobToTcp {
+    bind: String,
+    listener: Option<Arc<Async<TcpListener>>>,
+    clients: Vec<Async<TcpStream>>,
+}
+
+impl BlobToTcp {
+    /// Listen on a local address, e.g., `127.0.0.1:5557`.
+    pub fn new<S>(bind: S) -> Block
+    where
+        S: Into<String>,
+    {
+        Block::new(
+            BlockMetaBuilder::new("BlobToTcp").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut BlobToTcp,
+                     _mio: &mut MessageIo<BlobToTcp>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(v) = &p {
+                                block.send(v).await;
+                            } else if p != Pmt::Null {
+                                warn!("BlobToTcp/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            BlobToTcp {
+                bind: bind.into(),
+                listener: None,
+                clients: Vec::new(),
+            },
+        )
+    }
+
+    /// Sends a PDU to all clients, dropping clients that disconnected.
+    async fn send(&mut self, data: &[u8]) {
+        if self.clients.is_empty() {
+            debug!("BlobToTcp: no client connected, dropping PDU");
+            return;
+        }
+
+        let mut frame = (data.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(data);
+
+        let mut i = 0;
+        while i < self.clients.len() {
+            if let Err(e) = self.clients[i].write_all(&frame).await {
+                debug!("BlobToTcp: client disconnected: {:?}", e);
+                self.clients.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Kernel for BlobToTcp {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let listener = self.listener.as_ref().context("no listener")?.clone();
+        loop {
+            match listener.get_ref().accept() {
+                Ok((stream, addr)) => {
+                    debug!("BlobToTcp: accepted connection from {}", addr);
+                    self.clients.push(Async::new(stream)?);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    io.block_on(async move {
+                        let _ = listener.readable().await;
+                    });
+                    break;
+                }
+                Err(e) => return Err(e).context("BlobToTcp: accept failed"),
+            }
+        }
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let addr = self
+            .bind
+            .parse::<SocketAddr>()
+            .with_context(|| format!("BlobToTcp: invalid address {:?}", self.bind))?;
+        let listener = Async::<TcpListener>::bind(addr)
+            .with_context(|| format!("BlobToTcp: cannot bind to {}", addr))?;
+        self.listener = Some(Arc::new(listener));
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct Cfar {
+    fft_size: usize,
+    guard: usize,
+    train: usize,
+    /// Threshold factors indexed by the number of training cells.
+    factors: Vec<f32>,
+    /// Prefix sums of the current frame.
+    sums: Vec<f64>,
+}
+
+impl Cfar {
+    pub fn new(fft_size: usize, guard: usize, train: usize, pfa: f64) -> Block {
+        assert!(fft_size > 0, "fft_size must be positive");
+        assert!(train > 0, "need at least one training cell");
+        assert!(pfa > 0.0 && pfa < 1.0, "pfa must be in (0, 1)");
+
+        // for exponentially distributed noise power, scaling the mean of n
+        // cells by n (pfa^(-1/n) - 1) yields the false alarm probability pfa
+        let factors = (0..=2 * train)
+            .map(|n| {
+                if n == 0 {
+                    f32::INFINITY
+                } else {
+                    (n as f64 * (pfa.powf(-1.0 / n as f64) - 1.0)) as f32
+                }
+            })
+            .collect();
+
+        Block::new(
+            BlockMetaBuilder::new("Cfar").build(),
+            StreamIoBuilder::new()
+                .add_vector_input::<f32>("in", fft_size)
+                .build(),
+            MessageIoBuilder::new().add_output("detections").build(),
+            Cfar {
+                fft_size,
+                guard,
+                train,
+                factors,
+                sums: vec![0.0; fft_size + 1],
+            },
+        )
+    }
+
+    /// Detects peaks in a frame, returning `(bin, power, noise)` of the
+    /// strongest bin of each run of bins above the threshold.
+    fn detect(&mut self, frame: &[f32]) -> Vec<(usize, f32, f32)> {
+        let n = self.fft_size;
+        for (k, x) in frame.iter().enumerate() {
+            self.sums[k + 1] = self.sums[k] + *x as f64;
+        }
+        let sum = |a: usize, b: usize| self.sums[b] - self.sums[a];
+
+        let mut detections = Vec::new();
+        let mut current: Option<(usize, f32, f32)> = None;
+
+        for (k, x) in frame.iter().enumerate() {
+            // training cells on both sides, excluding the guard cells
+            let lo_end = k.saturating_sub(self.guard);
+            let lo_start = k.saturating_sub(self.guard + self.train);
+            let hi_start = cmp::min(k + self.guard + 1, n);
+            let hi_end = cmp::min(k + self.guard + self.train + 1, n);
+            let cells = (lo_end - lo_start) + (hi_end - hi_start);
+
+            let mut above = false;
+            let mut noise = 0.0;
+            if cells > 0 {
+                noise = ((sum(lo_start, lo_end) + sum(hi_start, hi_end)) / cells as f64) as f32;
+                above = *x > noise * self.factors[cells];
+            }
+
+            if above {
+                match current {
+                    Some((_, p, _)) if p >= *x => {}
+                    _ => current = Some((k, *x, noise)),
+                }
+            } else if let Some(d) = current.take() {
+                detections.push(d);
+            }
+        }
+        if let Some(d) = current.take() {
+            detections.push(d);
+        }
+        detections
+    }
+}
+
+#[async_trait]
+impl Kernel for Cfar {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<f32>();
+
+        for frame in i.chunks_exact(self.fft_size) {
+            for (bin, power, noise) in self.detect(frame) {
+                mio.post(0, Pmt::VecF32(vec![bin as f32, power, noise]))
+                    .await;
+            }
+        }
+
+        sio.input(0).consume(i.len());
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Cell-averaging CFAR (constant false alarm rate) detector on spectra.
+///
+/// Consumes frames of `fft_size` linear power bins, e.g., from a
+/// [Psd](crate::blocks::Psd) block with `db(false)`. The noise level of each
+/// bin is estimated as the mean of `train` cells on each side, skipping
+/// `guard` cells next to the bin. A bin is detected, if its power exceeds the
+/// noise level scaled for the false alarm probability `pfa`, assuming
+/// exponentially distributed noise power. At the edges of the frame, only the
+/// available cells are used. Adjacent detected bins are merged, reporting the
+/// strongest bin.
+///
+/// # Inputs
+///
+/// `in`: Power spectra, vector port of `fft_size` bins
+///
+/// # Outputs
+///
+/// **Message**: `detections`: one [`Pmt::VecF32`] per detection with the
+/// bin, its power, and the estimated noise level `[bin, power, noise]`
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::CfarBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let cfar = fg.add_block(
+///     CfarBuilder::new(2048)
+///         .guard(2)
+///         .train(16)
+///         .pfa(1e-6)
+///         .build(),
+/// );
+/// ```
+pub struct CfarBuilder {
+    fft_size: usize,
+    guard: usize,
+    train: usize,
+    pfa: f64,
+}
+
+impl CfarBuilder {
+    pub fn new(fft_size: usize) -> CfarBuilder {
+        CfarBuilder {
+            fft_size,
+            guard: 2,
+            train: 8,
+            pfa: 1e-4,
+        }
+    }
+
+    /// Number of guard cells on each side.
+    #[must_use]
+    pub fn guard(mut self, guard: usize) -> CfarBuilder {
+        self.guard = guard;
+        self
+    }
+
+    /// Number of training cells on each side.
+    #[must_use]
+    pub fn train(mut self, train: usize) -> CfarBuilder {
+        self.train = train;
+        self
+    }
+
+    /// Probability of false alarm per bin.
+    #[must_use]
+    pub fn pfa(mut self, pfa: f64) -> CfarBuilder {
+        self.pfa = pfa;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        Cfar::new(self.fft_size, self.guard, self.train, self.pfa)
+    }
+}
@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -53,9 +51,9 @@ where
         Block::new(
             BlockMetaBuilder::new("Combine").build(),
             StreamIoBuilder::new()
-                .add_input("in0", mem::size_of::<A>())
-                .add_input("in1", mem::size_of::<B>())
-                .add_output("out", mem::size_of::<C>())
+                .add_typed_input::<A>("in0")
+                .add_typed_input::<B>("in1")
+                .add_typed_output::<C>("out")
                 .build(),
             MessageIoBuilder::<Combine<A, B, C>>::new().build(),
             Combine { f: Box::new(f) },
@@ -0,0 +1,74 @@
+use crate::num_complex::Complex32;
+
+/// Gray-coded digital modulation schemes with unit average symbol energy.
+///
+/// Symbols are indexed by their bit label, with the first bit in the most
+/// significant position. For QAM, the upper half of the label selects the
+/// in-phase and the lower half the quadrature component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constellation {
+    Bpsk,
+    Qpsk,
+    Psk8,
+    Qam16,
+    Qam64,
+}
+
+fn gray(x: usize) -> usize {
+    x ^ (x >> 1)
+}
+
+/// Gray-coded PAM levels, indexed by label.
+fn pam(bits: usize) -> Vec<f32> {
+    let m = 1 << bits;
+    let mut levels = vec![0.0; m];
+    for k in 0..m {
+        levels[gray(k)] = (2 * k) as f32 - (m - 1) as f32;
+    }
+    levels
+}
+
+impl Constellation {
+    /// Number of bits carried by one symbol.
+    pub fn bits_per_symbol(&self) -> usize {
+        match self {
+            Constellation::Bpsk => 1,
+            Constellation::Qpsk => 2,
+            Constellation::Psk8 => 3,
+            Constellation::Qam16 => 4,
+            Constellation::Qam64 => 6,
+        }
+    }
+
+    /// Constellation points, indexed by their bit label.
+    pub fn points(&self) -> Vec<Complex32> {
+        match self {
+            Constellation::Bpsk => vec![Complex32::new(1.0, 0.0), Complex32::new(-1.0, 0.0)],
+            Constellation::Qpsk => Self::qam(1),
+            Constellation::Psk8 => {
+                let mut p = vec![Complex32::new(0.0, 0.0); 8];
+                for k in 0..8 {
+                    p[gray(k)] = Complex32::from_polar(1.0, k as f32 * std::f32::consts::FRAC_PI_4);
+                }
+                p
+            }
+            Constellation::Qam16 => Self::qam(2),
+            Constellation::Qam64 => Self::qam(3),
+        }
+    }
+
+    /// Square QAM with `bits` bits per dimension.
+    fn qam(bits: usize) -> Vec<Complex32> {
+        let levels = pam(bits);
+        let m = levels.len();
+        let energy = 2.0 * levels.iter().map(|x| x * x).sum::<f32>() / m as f32;
+        let scale = energy.sqrt().recip();
+        let mut p = Vec::with_capacity(m * m);
+        for i in levels.iter() {
+            for q in levels.iter() {
+                p.push(Complex32::new(*i, *q) * scale);
+            }
+        }
+        p
+    }
+}
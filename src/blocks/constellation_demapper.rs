@@ -0,0 +1,182 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::blocks::Constellation;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct ConstellationDemapper {
+    bits_per_symbol: usize,
+    points: Vec<Complex32>,
+    noise_variance: Option<f32>,
+}
+
+impl ConstellationDemapper {
+    /// Creates a demapper that outputs hard decisions, one bit per `u8`.
+    pub fn new_hard(constellation: Constellation) -> Block {
+        Self::new(constellation, None)
+    }
+
+    /// Creates a demapper that outputs log-likelihood ratios as `f32`, one per bit.
+    pub fn new_soft(constellation: Constellation, noise_variance: f32) -> Block {
+        Self::new(constellation, Some(noise_variance))
+    }
+
+    fn new(constellation: Constellation, noise_variance: Option<f32>) -> Block {
+        let sio = StreamIoBuilder::new().add_typed_input::<Complex32>("in");
+        let sio = if noise_variance.is_some() {
+            sio.add_typed_output::<f32>("out")
+        } else {
+            sio.add_typed_output::<u8>("out")
+        };
+
+        Block::new(
+            BlockMetaBuilder::new("ConstellationDemapper").build(),
+            sio.build(),
+            MessageIoBuilder::<Self>::new().build(),
+            ConstellationDemapper {
+                bits_per_symbol: constellation.bits_per_symbol(),
+                points: constellation.points(),
+                noise_variance,
+            },
+        )
+    }
+
+    fn hard(&self, y: Complex32, bits: &mut [u8]) {
+        let mut label = 0;
+        let mut best = f32::MAX;
+        for (l, p) in self.points.iter().enumerate() {
+            let d = (y - p).norm_sqr();
+            if d < best {
+                best = d;
+                label = l;
+            }
+        }
+        for (k, b) in bits.iter_mut().enumerate() {
+            *b = ((label >> (self.bits_per_symbol - 1 - k)) & 1) as u8;
+        }
+    }
+
+    /// Max-log approximation of the LLRs, positive values favor a zero bit.
+    fn soft(&self, y: Complex32, noise_variance: f32, llrs: &mut [f32]) {
+        for (k, llr) in llrs.iter_mut().enumerate() {
+            let shift = self.bits_per_symbol - 1 - k;
+            let mut d0 = f32::MAX;
+            let mut d1 = f32::MAX;
+            for (l, p) in self.points.iter().enumerate() {
+                let d = (y - p).norm_sqr();
+                if (l >> shift) & 1 == 0 {
+                    d0 = d0.min(d);
+                } else {
+                    d1 = d1.min(d);
+                }
+            }
+            *llr = (d1 - d0) / noise_variance;
+        }
+    }
+}
+
+#[async_trait]
+impl Kernel for ConstellationDemapper {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let bps = self.bits_per_symbol;
+
+        let n = if let Some(noise_variance) = self.noise_variance {
+            let o = sio.output(0).slice::<f32>();
+            let n = cmp::min(i.len(), o.len() / bps);
+            for (y, llrs) in i.iter().zip(o.chunks_exact_mut(bps)).take(n) {
+                self.soft(*y, noise_variance, llrs);
+            }
+            n
+        } else {
+            let o = sio.output(0).slice::<u8>();
+            let n = cmp::min(i.len(), o.len() / bps);
+            for (y, bits) in i.iter().zip(o.chunks_exact_mut(bps)).take(n) {
+                self.hard(*y, bits);
+            }
+            n
+        };
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n * bps);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Demaps constellation symbols to bits.
+///
+/// Outputs either hard decisions, one bit per `u8`, or soft decisions as
+/// log-likelihood ratios (`f32`) computed with the max-log approximation for
+/// the given noise variance. Positive LLRs favor a zero bit. Bits are output
+/// most significant bit of the symbol label first.
+///
+/// # Inputs
+///
+/// `in`: Symbols
+///
+/// # Outputs
+///
+/// `out`: Bits (`u8`) or LLRs (`f32`)
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::{Constellation, ConstellationDemapperBuilder};
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let hard = fg.add_block(ConstellationDemapperBuilder::new(Constellation::Qpsk).build_hard());
+/// let soft = fg.add_block(
+///     ConstellationDemapperBuilder::new(Constellation::Qam64)
+///         .noise_variance(0.1)
+///         .build_soft(),
+/// );
+/// ```
+pub struct ConstellationDemapperBuilder {
+    constellation: Constellation,
+    noise_variance: f32,
+}
+
+impl ConstellationDemapperBuilder {
+    pub fn new(constellation: Constellation) -> ConstellationDemapperBuilder {
+        ConstellationDemapperBuilder {
+            constellation,
+            noise_variance: 1.0,
+        }
+    }
+
+    /// Noise variance used to scale the LLRs.
+    #[must_use]
+    pub fn noise_variance(mut self, noise_variance: f32) -> ConstellationDemapperBuilder {
+        self.noise_variance = noise_variance;
+        self
+    }
+
+    pub fn build_hard(self) -> Block {
+        ConstellationDemapper::new_hard(self.constellation)
+    }
+
+    pub fn build_soft(self) -> Block {
+        ConstellationDemapper::new_soft(self.constellation, self.noise_variance)
+    }
+}
@@ -0,0 +1,95 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::blocks::Constellation;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Maps bits to constellation symbols.
+///
+/// Consumes one bit per `u8` (only the least significant bit is considered)
+/// and outputs one symbol for every [`Constellation::bits_per_symbol`] bits.
+/// The first bit is the most significant bit of the symbol label.
+///
+/// # Inputs
+///
+/// `in`: Unpacked bits
+///
+/// # Outputs
+///
+/// `out`: Symbols
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::{Constellation, ConstellationMapper};
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let mapper = fg.add_block(ConstellationMapper::new(Constellation::Qam16));
+/// ```
+pub struct ConstellationMapper {
+    bits_per_symbol: usize,
+    points: Vec<Complex32>,
+}
+
+impl ConstellationMapper {
+    pub fn new(constellation: Constellation) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ConstellationMapper").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<u8>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            ConstellationMapper {
+                bits_per_symbol: constellation.bits_per_symbol(),
+                points: constellation.points(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ConstellationMapper {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<Complex32>();
+
+        let n = cmp::min(i.len() / self.bits_per_symbol, o.len());
+
+        for (bits, y) in i
+            .chunks_exact(self.bits_per_symbol)
+            .zip(o.iter_mut())
+            .take(n)
+        {
+            let label = bits
+                .iter()
+                .fold(0usize, |acc, b| (acc << 1) | (*b & 1) as usize);
+            *y = self.points[label];
+        }
+
+        sio.input(0).consume(n * self.bits_per_symbol);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && i.len() / self.bits_per_symbol == n {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
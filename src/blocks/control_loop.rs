@@ -0,0 +1,72 @@
+use std::f32::consts::PI;
+
+/// Second-order loop filter shared by the carrier recovery blocks.
+///
+/// Loop gains are derived from the normalized loop bandwidth (rad/sample) and
+/// the damping factor, following the usual critically damped design.
+#[derive(Debug, Clone)]
+pub(crate) struct ControlLoop {
+    phase: f32,
+    freq: f32,
+    max_freq: f32,
+    min_freq: f32,
+    loop_bw: f32,
+    damping: f32,
+    alpha: f32,
+    beta: f32,
+}
+
+impl ControlLoop {
+    pub fn new(loop_bw: f32, max_freq: f32, min_freq: f32) -> ControlLoop {
+        let mut l = ControlLoop {
+            phase: 0.0,
+            freq: 0.0,
+            max_freq,
+            min_freq,
+            loop_bw,
+            damping: std::f32::consts::FRAC_1_SQRT_2,
+            alpha: 0.0,
+            beta: 0.0,
+        };
+        l.update_gains();
+        l
+    }
+
+    fn update_gains(&mut self) {
+        let denom = 1.0 + 2.0 * self.damping * self.loop_bw + self.loop_bw * self.loop_bw;
+        self.alpha = (4.0 * self.damping * self.loop_bw) / denom;
+        self.beta = (4.0 * self.loop_bw * self.loop_bw) / denom;
+    }
+
+    /// Feeds the phase error of the current sample into the loop.
+    pub fn advance(&mut self, error: f32) {
+        self.freq += self.beta * error;
+        self.phase += self.freq + self.alpha * error;
+
+        while self.phase > 2.0 * PI {
+            self.phase -= 2.0 * PI;
+        }
+        while self.phase < -2.0 * PI {
+            self.phase += 2.0 * PI;
+        }
+
+        self.freq = self.freq.clamp(self.min_freq, self.max_freq);
+    }
+
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    pub fn freq(&self) -> f32 {
+        self.freq
+    }
+
+    pub fn loop_bw(&self) -> f32 {
+        self.loop_bw
+    }
+
+    pub fn set_loop_bw(&mut self, loop_bw: f32) {
+        self.loop_bw = loop_bw;
+        self.update_gains();
+    }
+}
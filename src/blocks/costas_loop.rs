@@ -0,0 +1,173 @@
+use futures::FutureExt;
+use std::f32::consts::PI;
+
+use crate::anyhow::Result;
+use crate::blocks::control_loop::ControlLoop;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Modulation order the [CostasLoop] locks to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostasOrder {
+    Bpsk,
+    Qpsk,
+    Psk8,
+}
+
+impl CostasOrder {
+    fn phase_error(&self, s: Complex32) -> f32 {
+        match self {
+            CostasOrder::Bpsk => s.re * s.im,
+            CostasOrder::Qpsk => sign(s.re) * s.im - sign(s.im) * s.re,
+            CostasOrder::Psk8 => {
+                const K: f32 = std::f32::consts::SQRT_2 - 1.0;
+                if s.re.abs() >= s.im.abs() {
+                    sign(s.re) * s.im - K * sign(s.im) * s.re
+                } else {
+                    K * sign(s.re) * s.im - sign(s.im) * s.re
+                }
+            }
+        }
+    }
+}
+
+fn sign(x: f32) -> f32 {
+    if x > 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+pub struct CostasLoop {
+    order: CostasOrder,
+    control: ControlLoop,
+}
+
+impl CostasLoop {
+    pub fn new(loop_bw: f32, order: CostasOrder) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("CostasLoop").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "loop_bw",
+                    |block: &mut CostasLoop,
+                     _mio: &mut MessageIo<CostasLoop>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Double(bw) = &p {
+                                block.control.set_loop_bw(*bw as f32);
+                            } else if p != Pmt::Null {
+                                warn!("CostasLoop/loop_bw Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Double(block.control.loop_bw() as f64))
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            CostasLoop {
+                order,
+                control: ControlLoop::new(loop_bw, 1.0, -1.0),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for CostasLoop {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let o = sio.output(0).slice::<Complex32>();
+
+        let n = std::cmp::min(i.len(), o.len());
+
+        for (x, y) in i.iter().zip(o.iter_mut()).take(n) {
+            *y = x * Complex32::from_polar(1.0, -self.control.phase());
+            let error = self.order.phase_error(*y).clamp(-1.0, 1.0);
+            self.control.advance(error);
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Costas loop for carrier recovery of BPSK, QPSK, and 8PSK signals.
+///
+/// Derotates the input by the tracked carrier phase. The loop bandwidth is
+/// given in rad/sample, typical values are around `2π/100`.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// **Message**: `loop_bw`: set the loop bandwidth; accepts a [`Pmt::Double`]
+/// value and returns the current loop bandwidth
+///
+/// # Outputs
+///
+/// `out`: Phase-corrected samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::{CostasLoopBuilder, CostasOrder};
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let costas = fg.add_block(
+///     CostasLoopBuilder::new(CostasOrder::Qpsk)
+///         .loop_bw(0.05)
+///         .build(),
+/// );
+/// ```
+pub struct CostasLoopBuilder {
+    order: CostasOrder,
+    loop_bw: f32,
+}
+
+impl CostasLoopBuilder {
+    pub fn new(order: CostasOrder) -> CostasLoopBuilder {
+        CostasLoopBuilder {
+            order,
+            loop_bw: 2.0 * PI / 100.0,
+        }
+    }
+
+    #[must_use]
+    pub fn loop_bw(mut self, loop_bw: f32) -> CostasLoopBuilder {
+        self.loop_bw = loop_bw;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        CostasLoop::new(self.loop_bw, self.order)
+    }
+}
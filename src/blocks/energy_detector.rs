@@ -0,0 +1,174 @@
+use futures::FutureExt;
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::blocks::power_detector::PowerDetector;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+pub struct EnergyDetector {
+    detector: PowerDetector,
+    /// Length of the current burst.
+    len: usize,
+}
+
+impl EnergyDetector {
+    pub fn new(threshold_db: f32, hysteresis_db: f32, alpha: f32) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("EnergyDetector").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "threshold",
+                    |block: &mut EnergyDetector,
+                     _mio: &mut MessageIo<EnergyDetector>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Double(t) = &p {
+                                block.detector.set_threshold_db(*t as f32);
+                            } else if p != Pmt::Null {
+                                warn!(
+                                    "EnergyDetector/threshold Handler received wrong PMT {:?}",
+                                    &p
+                                );
+                            }
+                            Ok(Pmt::Double(block.detector.threshold_db() as f64))
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            EnergyDetector {
+                detector: PowerDetector::new(threshold_db, hysteresis_db, alpha),
+                len: 0,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for EnergyDetector {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let o = sio.output(0).slice::<Complex32>();
+
+        let n = cmp::min(i.len(), o.len());
+
+        for (k, x) in i.iter().take(n).enumerate() {
+            match self.detector.update(*x) {
+                Some(true) => {
+                    self.len = 0;
+                    sio.output(0).add_tag(
+                        k,
+                        Tag::NamedF32("burst_start".to_string(), self.detector.power_db()),
+                    );
+                }
+                Some(false) => {
+                    sio.output(0)
+                        .add_tag(k, Tag::NamedUsize("burst_end".to_string(), self.len));
+                }
+                None => {}
+            }
+            if self.detector.is_open() {
+                self.len += 1;
+            }
+        }
+
+        o[..n].copy_from_slice(&i[..n]);
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Detects bursts of energy and tags their start and end.
+///
+/// Passes the samples through. The power is averaged with a single-pole filter
+/// (weight `alpha` of the new sample). When the average rises above the
+/// threshold (in dB), the sample is tagged with
+/// `Tag::NamedF32("burst_start", power_db)`. When it falls below the threshold
+/// minus the hysteresis, the sample is tagged with
+/// `Tag::NamedUsize("burst_end", len)`, where `len` is the number of samples
+/// since the start. Start and end are delayed by the averaging.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// **Message**: `threshold`: set the threshold in dB; accepts a
+/// [`Pmt::Double`] value and returns the current threshold
+///
+/// # Outputs
+///
+/// `out`: Tagged samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::EnergyDetectorBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let detector = fg.add_block(
+///     EnergyDetectorBuilder::new(-20.0)
+///         .hysteresis(3.0)
+///         .alpha(0.05)
+///         .build(),
+/// );
+/// ```
+pub struct EnergyDetectorBuilder {
+    threshold_db: f32,
+    hysteresis_db: f32,
+    alpha: f32,
+}
+
+impl EnergyDetectorBuilder {
+    pub fn new(threshold_db: f32) -> EnergyDetectorBuilder {
+        EnergyDetectorBuilder {
+            threshold_db,
+            hysteresis_db: 3.0,
+            alpha: 0.01,
+        }
+    }
+
+    #[must_use]
+    pub fn hysteresis(mut self, hysteresis_db: f32) -> EnergyDetectorBuilder {
+        self.hysteresis_db = hysteresis_db;
+        self
+    }
+
+    #[must_use]
+    pub fn alpha(mut self, alpha: f32) -> EnergyDetectorBuilder {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        EnergyDetector::new(self.threshold_db, self.hysteresis_db, self.alpha)
+    }
+}
@@ -0,0 +1,145 @@
+use futuredsp::fec::ConvolutionalCode;
+use futures::FutureExt;
+
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Convolutional encoder for frames.
+///
+/// Each frame is encoded as one terminated block. Input bytes are unpacked
+/// most significant bit first, and the coded bits are output unpacked, one
+/// bit per byte.
+///
+/// # Inputs
+///
+/// `in`: Frames as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Unpacked coded bits as [Pmt::Blob]
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::ConvolutionalCode;
+/// use futuresdr::blocks::fec::ConvolutionalEncoder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let enc = fg.add_block(ConvolutionalEncoder::new(ConvolutionalCode::k7_rate_1_2()));
+/// ```
+pub struct ConvolutionalEncoder {
+    code: ConvolutionalCode,
+}
+
+impl ConvolutionalEncoder {
+    pub fn new(code: ConvolutionalCode) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ConvolutionalEncoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut ConvolutionalEncoder,
+                     mio: &mut MessageIo<ConvolutionalEncoder>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(v) = p {
+                                let bits: Vec<u8> = v
+                                    .iter()
+                                    .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1))
+                                    .collect();
+                                mio.post(0, Pmt::Blob(block.code.encode(&bits))).await;
+                            } else {
+                                warn!(
+                                    "ConvolutionalEncoder/in Handler received wrong PMT {:?}",
+                                    &p
+                                );
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            ConvolutionalEncoder { code },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ConvolutionalEncoder {}
+
+/// Soft-decision Viterbi decoder for frames.
+///
+/// Decodes one terminated block per message and packs the decoded bits most
+/// significant bit first. Incomplete trailing bytes are dropped.
+///
+/// # Inputs
+///
+/// `in`: Log-likelihood ratios as [Pmt::VecF32], positive values favor a zero bit
+///
+/// # Outputs
+///
+/// `out`: Decoded frames as [Pmt::Blob]
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::ConvolutionalCode;
+/// use futuresdr::blocks::fec::ViterbiDecoder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let code = ConvolutionalCode::k7_rate_1_2().punctured(ConvolutionalCode::RATE_3_4.to_vec());
+/// let dec = fg.add_block(ViterbiDecoder::new(code));
+/// ```
+pub struct ViterbiDecoder {
+    code: ConvolutionalCode,
+}
+
+impl ViterbiDecoder {
+    pub fn new(code: ConvolutionalCode) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ViterbiDecoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut ViterbiDecoder,
+                     mio: &mut MessageIo<ViterbiDecoder>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::VecF32(llrs) = p {
+                                let bits = block.code.decode(&llrs);
+                                let bytes: Vec<u8> = bits
+                                    .chunks_exact(8)
+                                    .map(|c| c.iter().fold(0, |acc, b| (acc << 1) | b))
+                                    .collect();
+                                mio.post(0, Pmt::Blob(bytes)).await;
+                            } else {
+                                warn!("ViterbiDecoder/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            ViterbiDecoder { code },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ViterbiDecoder {}
@@ -0,0 +1,134 @@
+use futuredsp::fec::Crc;
+use futures::FutureExt;
+
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Appends a CRC to frames.
+///
+/// # Inputs
+///
+/// `in`: Frames as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Frames with the checksum appended
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::Crc;
+/// use futuresdr::blocks::fec::CrcGenerator;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let crc = fg.add_block(CrcGenerator::new(Crc::crc32()));
+/// ```
+pub struct CrcGenerator {
+    crc: Crc,
+}
+
+impl CrcGenerator {
+    pub fn new(crc: Crc) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("CrcGenerator").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut CrcGenerator,
+                     mio: &mut MessageIo<CrcGenerator>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(mut v) = p {
+                                block.crc.append(&mut v);
+                                mio.post(0, Pmt::Blob(v)).await;
+                            } else {
+                                warn!("CrcGenerator/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            CrcGenerator { crc },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for CrcGenerator {}
+
+/// Checks and strips the CRC of frames.
+///
+/// Frames with a valid checksum are forwarded without the checksum. Other
+/// frames are dropped.
+///
+/// # Inputs
+///
+/// `in`: Frames with checksum as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Valid frames
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::Crc;
+/// use futuresdr::blocks::fec::CrcChecker;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let crc = fg.add_block(CrcChecker::new(Crc::crc16_ccitt()));
+/// ```
+pub struct CrcChecker {
+    crc: Crc,
+}
+
+impl CrcChecker {
+    pub fn new(crc: Crc) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("CrcChecker").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut CrcChecker,
+                     mio: &mut MessageIo<CrcChecker>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(mut v) = p {
+                                if block.crc.check(&v) {
+                                    v.truncate(v.len() - block.crc.num_bytes());
+                                    mio.post(0, Pmt::Blob(v)).await;
+                                } else {
+                                    debug!("CrcChecker: dropping frame with invalid checksum");
+                                }
+                            } else {
+                                warn!("CrcChecker/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            CrcChecker { crc },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for CrcChecker {}
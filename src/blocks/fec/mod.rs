@@ -0,0 +1,13 @@
+//! Forward error correction and error detection on PDUs.
+//!
+//! The blocks wrap the codes of [futuredsp::fec] and operate on messages.
+//! Frames are passed as [Pmt::Blob](crate::runtime::Pmt::Blob) and soft
+//! decisions as [Pmt::VecF32](crate::runtime::Pmt::VecF32).
+mod convolutional;
+pub use convolutional::{ConvolutionalEncoder, ViterbiDecoder};
+
+mod crc;
+pub use crc::{CrcChecker, CrcGenerator};
+
+mod reed_solomon;
+pub use reed_solomon::{ReedSolomonDecoder, ReedSolomonEncoder};
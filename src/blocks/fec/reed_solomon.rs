@@ -0,0 +1,155 @@
+use futuredsp::fec::ReedSolomon;
+use futures::FutureExt;
+
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Reed-Solomon encoder for frames.
+///
+/// Frames are split into blocks of `k` bytes, where the last block is padded
+/// with zeros, and the codewords are output back to back.
+///
+/// # Inputs
+///
+/// `in`: Frames as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Concatenated codewords as [Pmt::Blob]
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::ReedSolomon;
+/// use futuresdr::blocks::fec::ReedSolomonEncoder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let enc = fg.add_block(ReedSolomonEncoder::new(ReedSolomon::rs_255_223()));
+/// ```
+pub struct ReedSolomonEncoder {
+    rs: ReedSolomon,
+}
+
+impl ReedSolomonEncoder {
+    pub fn new(rs: ReedSolomon) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ReedSolomonEncoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut ReedSolomonEncoder,
+                     mio: &mut MessageIo<ReedSolomonEncoder>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(v) = p {
+                                let k = block.rs.k();
+                                let mut out = Vec::new();
+                                for chunk in v.chunks(k) {
+                                    let mut data = chunk.to_vec();
+                                    data.resize(k, 0);
+                                    out.extend(block.rs.encode(&data));
+                                }
+                                mio.post(0, Pmt::Blob(out)).await;
+                            } else {
+                                warn!("ReedSolomonEncoder/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            ReedSolomonEncoder { rs },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ReedSolomonEncoder {}
+
+/// Reed-Solomon decoder for frames.
+///
+/// Frames must consist of complete codewords of `n` bytes. The data bytes of
+/// all codewords are output as one frame. Frames with an uncorrectable
+/// codeword are dropped.
+///
+/// # Inputs
+///
+/// `in`: Concatenated codewords as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Decoded frames as [Pmt::Blob]
+///
+/// # Usage
+/// ```
+/// use futuredsp::fec::ReedSolomon;
+/// use futuresdr::blocks::fec::ReedSolomonDecoder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let dec = fg.add_block(ReedSolomonDecoder::new(ReedSolomon::rs_255_223()));
+/// ```
+pub struct ReedSolomonDecoder {
+    rs: ReedSolomon,
+}
+
+impl ReedSolomonDecoder {
+    pub fn new(rs: ReedSolomon) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ReedSolomonDecoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut ReedSolomonDecoder,
+                     mio: &mut MessageIo<ReedSolomonDecoder>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Blob(mut v) = p {
+                                let n = block.rs.n();
+                                let k = block.rs.k();
+                                if v.is_empty() || v.len() % n > 0 {
+                                    warn!("ReedSolomonDecoder: frame of {} bytes is not a multiple of the codeword length", v.len());
+                                    return Ok(Pmt::Null);
+                                }
+                                let mut out = Vec::with_capacity(v.len() / n * k);
+                                for codeword in v.chunks_exact_mut(n) {
+                                    match block.rs.decode(codeword) {
+                                        Some(_) => out.extend_from_slice(&codeword[..k]),
+                                        None => {
+                                            debug!("ReedSolomonDecoder: dropping uncorrectable frame");
+                                            return Ok(Pmt::Null);
+                                        }
+                                    }
+                                }
+                                mio.post(0, Pmt::Blob(out)).await;
+                            } else {
+                                warn!("ReedSolomonDecoder/in Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            ReedSolomonDecoder { rs },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ReedSolomonDecoder {}
@@ -1,7 +1,6 @@
 use rustfft::num_complex::Complex;
 use rustfft::{self, FftPlanner};
 use std::cmp;
-use std::mem::size_of;
 use std::sync::Arc;
 
 use crate::anyhow::Result;
@@ -28,8 +27,8 @@ impl Fft {
         Block::new(
             BlockMetaBuilder::new("Fft").build(),
             StreamIoBuilder::new()
-                .add_input("in", size_of::<Complex<f32>>())
-                .add_output("out", size_of::<Complex<f32>>())
+                .add_vector_input::<Complex<f32>>("in", 2048)
+                .add_vector_output::<Complex<f32>>("out", 2048)
                 .build(),
             MessageIoBuilder::<Fft>::new().build(),
             Fft {
@@ -52,22 +51,23 @@ impl Kernel for Fft {
         let i = unsafe { sio.input(0).slice_mut::<Complex<f32>>() };
         let o = sio.output(0).slice::<Complex<f32>>();
 
-        let m = cmp::min(i.len(), o.len());
-        let n = (m / 2048) * 2048;
+        // vector ports only provide whole frames
+        let n = cmp::min(i.len(), o.len());
 
-        if sio.input(0).finished() {
-            io.finished = true;
-        }
+        if n > 0 {
+            self.plan.process_outofplace_with_scratch(
+                &mut i[0..n],
+                &mut o[0..n],
+                &mut self.scratch,
+            );
 
-        if n == 0 {
-            return Ok(());
+            sio.input(0).consume(n);
+            sio.output(0).produce(n);
         }
 
-        self.plan
-            .process_outofplace_with_scratch(&mut i[0..n], &mut o[0..n], &mut self.scratch);
-
-        sio.input(0).consume(n);
-        sio.output(0).produce(n);
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
 
         Ok(())
     }
@@ -76,14 +76,15 @@ impl Kernel for Fft {
 /// Computes a FFT
 ///
 /// This block computes the FFT on 2048 samples at a time, outputting 2048 samples per FFT.
+/// A partial frame at the end of the stream is dropped.
 ///
 /// # Inputs
 ///
-/// `in`: Input samples
+/// `in`: Input samples, vector port of 2048 samples
 ///
 /// # Outputs
 ///
-/// `out`: FFT results
+/// `out`: FFT results, vector port of 2048 samples
 ///
 /// # Usage
 /// ```
@@ -1,64 +1,204 @@
 use async_fs::File;
 use futures::io::AsyncWriteExt;
-use std::fs::OpenOptions;
+use futures::FutureExt;
+use std::cmp;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
-use crate::anyhow::Result;
+use crate::anyhow::{Context, Result};
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
 use crate::runtime::Kernel;
 use crate::runtime::MessageIo;
 use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
 use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
-/// Writes samples to a file.
-///
-/// Samples are encoded using the in-memory format of the machine the runtime is
-/// running on, like for [FileSource]. For most machines, this means little
-/// endian. Complex numbers are written with the real component coming before
-/// the complex component.
-///
-/// # Inputs
-///
-/// `in`: Input
-///
-/// # Outputs
-///
-/// No outputs.
-///
-/// # Usage
-/// ```no_run
-/// use futuresdr::blocks::FileSink;
-/// use futuresdr::runtime::Flowgraph;
-/// use num_complex::Complex;
-///
-/// let mut fg = Flowgraph::new();
-///
-/// let sink = fg.add_block(FileSink::<Complex<f32>>::new("my_sink_filename.cf32"));
-/// ```
 pub struct FileSink<T: Send + 'static> {
     file_name: String,
     file: Option<File>,
-    _type: std::marker::PhantomData<T>,
+    item_size: usize,
+    max_size: Option<u64>,
+    max_duration: Option<Duration>,
+    /// Whether files get timestamped names, i.e., when rotating or triggered.
+    timestamped: bool,
+    /// Bytes written to and creation time of the current file.
+    written: u64,
+    opened: Instant,
+    recording: bool,
+    /// Items to record after a trigger.
+    post_trigger: Option<u64>,
+    /// Items left to record, before stopping.
+    remaining: Option<u64>,
+    /// Items kept before a trigger.
+    pre_trigger: usize,
+    pre: VecDeque<u8>,
+    _type: PhantomData<T>,
 }
 
 impl<T: Send + 'static> FileSink<T> {
+    /// Create a sink that writes all samples to one file.
     pub fn new<S: Into<String>>(file_name: S) -> Block {
+        FileSinkBuilder::<T>::new(file_name).build()
+    }
+
+    fn with_config(
+        file_name: String,
+        max_size: Option<u64>,
+        max_duration: Option<Duration>,
+        triggered: bool,
+        pre_trigger: usize,
+        post_trigger: Option<u64>,
+    ) -> Block {
+        let item_size = std::mem::size_of::<T>();
         Block::new(
             BlockMetaBuilder::new("FileSink").build(),
-            StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<T>())
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "start",
+                    |block: &mut FileSink<T>,
+                     _mio: &mut MessageIo<FileSink<T>>,
+                     _meta: &mut BlockMeta,
+                     _p: Pmt| {
+                        async move {
+                            block.recording = true;
+                            block.remaining = block.post_trigger;
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_input(
+                    "stop",
+                    |block: &mut FileSink<T>,
+                     _mio: &mut MessageIo<FileSink<T>>,
+                     _meta: &mut BlockMeta,
+                     _p: Pmt| {
+                        async move {
+                            block.recording = false;
+                            block.remaining = None;
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
                 .build(),
-            MessageIoBuilder::new().build(),
             FileSink::<T> {
-                file_name: file_name.into(),
+                file_name,
                 file: None,
-                _type: std::marker::PhantomData,
+                item_size,
+                max_size,
+                max_duration,
+                timestamped: triggered || max_size.is_some() || max_duration.is_some(),
+                written: 0,
+                opened: Instant::now(),
+                recording: !triggered,
+                post_trigger,
+                remaining: None,
+                pre_trigger,
+                pre: VecDeque::with_capacity(pre_trigger * item_size),
+                _type: PhantomData,
             },
         )
     }
+
+    /// Name of the next file, i.e., `<stem>_<UTC time>.<extension>`.
+    fn next_path(&self) -> PathBuf {
+        let base = Path::new(&self.file_name);
+        let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = base
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let time = timestamp(SystemTime::now());
+
+        let mut path = base.with_file_name(format!("{stem}_{time}{ext}"));
+        let mut i = 1;
+        while path.exists() {
+            path = base.with_file_name(format!("{stem}_{time}_{i}{ext}"));
+            i += 1;
+        }
+        path
+    }
+
+    async fn open(&mut self) -> Result<()> {
+        let path = if self.timestamped {
+            self.next_path()
+        } else {
+            PathBuf::from(&self.file_name)
+        };
+        let file = File::create(&path)
+            .await
+            .with_context(|| format!("FileSink: cannot create {:?}", path))?;
+        self.file = Some(file);
+        self.written = 0;
+        self.opened = Instant::now();
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(file) = self.file.take() {
+            file.sync_all()
+                .await
+                .with_context(|| format!("FileSink: cannot sync {:?}", self.file_name))?;
+        }
+        Ok(())
+    }
+
+    /// Writes items, rotating files when they exceed the maximum size or age.
+    async fn write(&mut self, mut data: &[u8]) -> Result<()> {
+        while !data.is_empty() {
+            if self.file.is_none() {
+                self.open().await?;
+            }
+
+            let mut n = data.len();
+            if let Some(max) = self.max_size {
+                let items = max.saturating_sub(self.written) / self.item_size as u64;
+                n = cmp::min(n as u64, cmp::max(items, 1) * self.item_size as u64) as usize;
+            }
+
+            self.file
+                .as_mut()
+                .unwrap()
+                .write_all(&data[..n])
+                .await
+                .with_context(|| format!("FileSink: writing to {:?} failed", self.file_name))?;
+            self.written += n as u64;
+            data = &data[n..];
+
+            if self.max_size.is_some_and(|m| self.written >= m)
+                || self
+                    .max_duration
+                    .is_some_and(|d| self.opened.elapsed() >= d)
+            {
+                self.close().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the last items for the pre-trigger buffer.
+    fn buffer(&mut self, data: &[u8]) {
+        let capacity = self.pre_trigger * self.item_size;
+        if capacity == 0 {
+            return;
+        }
+        let data = &data[data.len().saturating_sub(capacity)..];
+        let excess = (self.pre.len() + data.len()).saturating_sub(capacity);
+        self.pre.drain(..excess);
+        self.pre.extend(data);
+    }
 }
 
 #[async_trait]
@@ -72,15 +212,38 @@ impl<T: Send + 'static> Kernel for FileSink<T> {
     ) -> Result<()> {
         let i = sio.input(0).slice::<u8>();
 
-        let item_size = std::mem::size_of::<T>();
-        let items = i.len() / item_size;
+        let items = i.len() / self.item_size;
+        let mut data = &i[..items * self.item_size];
+
+        if self.recording {
+            if !self.pre.is_empty() {
+                let mut pre = std::mem::take(&mut self.pre);
+                self.write(pre.make_contiguous()).await?;
+                pre.clear();
+                self.pre = pre;
+            }
+
+            let n = match self.remaining {
+                Some(r) => cmp::min(r, items as u64) as usize,
+                None => items,
+            };
+            self.write(&data[..n * self.item_size]).await?;
+            data = &data[n * self.item_size..];
+
+            if let Some(r) = self.remaining.as_mut() {
+                *r -= n as u64;
+                if *r == 0 {
+                    self.recording = false;
+                    self.remaining = None;
+                }
+            }
+        }
 
-        if items > 0 {
-            let i = &i[..items * item_size];
-            match self.file.as_mut().unwrap().write_all(i).await {
-                Ok(()) => {}
-                Err(e) => panic!("FileSink: writing to {:?} failed: {:?}", self.file_name, e),
+        if !self.recording {
+            if self.timestamped {
+                self.close().await?;
             }
+            self.buffer(data);
         }
 
         if sio.input(0).finished() {
@@ -97,14 +260,9 @@ impl<T: Send + 'static> Kernel for FileSink<T> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(self.file_name.clone())
-            .unwrap();
-
-        self.file = Some(file.into());
+        if !self.timestamped {
+            self.open().await?;
+        }
         Ok(())
     }
 
@@ -114,7 +272,165 @@ impl<T: Send + 'static> Kernel for FileSink<T> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        self.file.as_mut().unwrap().sync_all().await.unwrap();
-        Ok(())
+        self.close().await
+    }
+}
+
+/// Formats a time as UTC in the ISO 8601 basic format, e.g.,
+/// `20221019T160000.500Z`.
+fn timestamp(time: SystemTime) -> String {
+    let d = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = d.as_secs();
+    let (hour, min, sec) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    // civil date from days since the epoch, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        hour,
+        min,
+        sec,
+        d.subsec_millis()
+    )
+}
+
+/// Writes samples to a file.
+///
+/// Samples are encoded using the in-memory format of the machine the runtime is
+/// running on, like for [FileSource](crate::blocks::FileSource). For most
+/// machines, this means little endian. Complex numbers are written with the
+/// real component coming before the complex component.
+///
+/// By default, all samples are written to one file. With a maximum size or
+/// duration, the sink rotates files, which are named after the UTC time they
+/// were created, e.g., `capture_20221019T160000.500Z.cf32` for
+/// `capture.cf32`.
+///
+/// A `triggered` sink only records between `start` and `stop` messages, each
+/// time to a new timestamped file. With `pre_trigger`, the recording includes
+/// the samples before the trigger; with `post_trigger`, it stops after a
+/// number of samples, unless it is triggered again.
+///
+/// # Inputs
+///
+/// `in`: Input
+///
+/// **Message**: `start`: start recording; any [`Pmt`] is a trigger, e.g., a
+/// detection event
+///
+/// **Message**: `stop`: stop recording; any [`Pmt`]
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{FileSink, FileSinkBuilder};
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+/// use std::time::Duration;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(FileSink::<Complex<f32>>::new("my_sink_filename.cf32"));
+///
+/// // Starts a new file every 1 GB or 10 minutes
+/// let sink = fg.add_block(
+///     FileSinkBuilder::<Complex<f32>>::new("capture.cf32")
+///         .max_size(1_000_000_000)
+///         .max_duration(Duration::from_secs(600))
+///         .build(),
+/// );
+///
+/// // Records 100k samples around each detection on the `start` port
+/// let sink = fg.add_block(
+///     FileSinkBuilder::<Complex<f32>>::new("detection.cf32")
+///         .triggered(true)
+///         .pre_trigger(20_000)
+///         .post_trigger(80_000)
+///         .build(),
+/// );
+/// ```
+pub struct FileSinkBuilder<T: Send + 'static> {
+    file_name: String,
+    max_size: Option<u64>,
+    max_duration: Option<Duration>,
+    triggered: bool,
+    pre_trigger: usize,
+    post_trigger: Option<u64>,
+    _type: PhantomData<T>,
+}
+
+impl<T: Send + 'static> FileSinkBuilder<T> {
+    pub fn new<S: Into<String>>(file_name: S) -> FileSinkBuilder<T> {
+        FileSinkBuilder {
+            file_name: file_name.into(),
+            max_size: None,
+            max_duration: None,
+            triggered: false,
+            pre_trigger: 0,
+            post_trigger: None,
+            _type: PhantomData,
+        }
+    }
+
+    /// Start a new file, when a file reaches this size in bytes.
+    #[must_use]
+    pub fn max_size(mut self, bytes: u64) -> FileSinkBuilder<T> {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Start a new file, when a file is open for this time.
+    #[must_use]
+    pub fn max_duration(mut self, duration: Duration) -> FileSinkBuilder<T> {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Only record after a `start` message.
+    #[must_use]
+    pub fn triggered(mut self, triggered: bool) -> FileSinkBuilder<T> {
+        self.triggered = triggered;
+        self
+    }
+
+    /// Number of items before a trigger to include in the recording.
+    #[must_use]
+    pub fn pre_trigger(mut self, items: usize) -> FileSinkBuilder<T> {
+        self.pre_trigger = items;
+        self
+    }
+
+    /// Number of items to record after a trigger.
+    #[must_use]
+    pub fn post_trigger(mut self, items: u64) -> FileSinkBuilder<T> {
+        self.post_trigger = Some(items);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        FileSink::<T>::with_config(
+            self.file_name,
+            self.max_size,
+            self.max_duration,
+            self.triggered,
+            self.pre_trigger,
+            self.post_trigger,
+        )
     }
 }
@@ -1,63 +1,198 @@
 use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+use futures::FutureExt;
+use std::cmp;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
 
-use crate::anyhow::Result;
+use crate::anyhow::{Context, Result};
+use crate::num_complex::Complex32;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
 use crate::runtime::Kernel;
 use crate::runtime::MessageIo;
 use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
 use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
-/// Loads samples from a file, then stops.
+/// Storage format of a file, which is converted to the output type.
 ///
-/// Samples are assumed to be encoded in the native format for the runtime. For
-/// example, on most machines, that means little endian. For complex samples,
-/// the real component must come before the complex component.
-///
-/// # Inputs
-///
-/// No inputs.
-///
-/// # Outputs
-///
-/// `out`: Output samples
-///
-/// # Usage
-/// ```no_run
-/// use futuresdr::blocks::FileSource;
-/// use futuresdr::runtime::Flowgraph;
-/// use num_complex::Complex;
-///
-/// let mut fg = Flowgraph::new();
-///
-/// // Loads 8-byte samples from the file
-/// let source = fg.add_block(FileSource::<Complex<f32>>::new("my_filename.cf32"));
-/// ```
+/// Integer formats are scaled to `[-1, 1]`. Multi-byte formats are little
+/// endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Interleaved unsigned 8-bit I/Q, e.g., from an RTL-SDR.
+    Cu8,
+    /// Interleaved signed 8-bit I/Q, e.g., from a HackRF.
+    Cs8,
+    /// Interleaved signed 16-bit I/Q.
+    Ci16,
+    /// Interleaved 32-bit float I/Q.
+    Cf32,
+}
+
+impl SampleFormat {
+    fn item_size(&self) -> usize {
+        match self {
+            SampleFormat::Cu8 | SampleFormat::Cs8 => 2,
+            SampleFormat::Ci16 => 4,
+            SampleFormat::Cf32 => 8,
+        }
+    }
+
+    fn converter(&self) -> Converter<Complex32> {
+        match self {
+            SampleFormat::Cu8 => convert_cu8,
+            SampleFormat::Cs8 => convert_cs8,
+            SampleFormat::Ci16 => convert_ci16,
+            SampleFormat::Cf32 => convert_cf32,
+        }
+    }
+}
+
+/// Converts items in the storage format to the output type.
+type Converter<T> = fn(&[u8], &mut [T]);
+
+pub(crate) fn convert_cu8(i: &[u8], o: &mut [Complex32]) {
+    for (x, y) in i.chunks_exact(2).zip(o.iter_mut()) {
+        *y = Complex32::new((x[0] as f32 - 127.5) / 127.5, (x[1] as f32 - 127.5) / 127.5);
+    }
+}
+
+fn convert_cs8(i: &[u8], o: &mut [Complex32]) {
+    for (x, y) in i.chunks_exact(2).zip(o.iter_mut()) {
+        *y = Complex32::new(x[0] as i8 as f32 / 128.0, x[1] as i8 as f32 / 128.0);
+    }
+}
+
+fn convert_ci16(i: &[u8], o: &mut [Complex32]) {
+    for (x, y) in i.chunks_exact(4).zip(o.iter_mut()) {
+        *y = Complex32::new(
+            i16::from_le_bytes([x[0], x[1]]) as f32 / 32768.0,
+            i16::from_le_bytes([x[2], x[3]]) as f32 / 32768.0,
+        );
+    }
+}
+
+fn convert_cf32(i: &[u8], o: &mut [Complex32]) {
+    for (x, y) in i.chunks_exact(8).zip(o.iter_mut()) {
+        *y = Complex32::new(
+            f32::from_le_bytes([x[0], x[1], x[2], x[3]]),
+            f32::from_le_bytes([x[4], x[5], x[6], x[7]]),
+        );
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(not(target_arch = "wasm32"))))]
 pub struct FileSource<T: Send + 'static> {
     file_name: String,
     file: Option<async_fs::File>,
-    _type: std::marker::PhantomData<T>,
+    /// Size of an item in the file.
+    item_size: usize,
+    convert: Option<Converter<T>>,
+    buf: Vec<u8>,
+    offset: u64,
+    length: Option<u64>,
+    repeat: bool,
+    /// Current position in items from the start of the file.
+    pos: u64,
+    /// Whether items were read since the last rewind, to stop repeating
+    /// empty files.
+    read_since_rewind: bool,
+    _type: PhantomData<T>,
 }
 
 impl<T: Send + 'static> FileSource<T> {
+    /// Create a source that reads the file once in the native format.
     pub fn new<S: Into<String>>(file_name: S) -> Block {
+        FileSourceBuilder::<T>::new(file_name).build()
+    }
+
+    fn with_config(
+        file_name: String,
+        item_size: usize,
+        convert: Option<Converter<T>>,
+        offset: u64,
+        length: Option<u64>,
+        repeat: bool,
+    ) -> Block {
         Block::new(
             BlockMetaBuilder::new("FileSource").build(),
-            StreamIoBuilder::new()
-                .add_output("out", std::mem::size_of::<T>())
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "seek",
+                    |block: &mut FileSource<T>,
+                     _mio: &mut MessageIo<FileSource<T>>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            let pos = match &p {
+                                Pmt::U64(v) => Some(*v),
+                                Pmt::U32(v) => Some(*v as u64),
+                                Pmt::Double(v) if *v >= 0.0 => Some(*v as u64),
+                                Pmt::Null => None,
+                                _ => {
+                                    warn!("FileSource/seek Handler received wrong PMT {:?}", &p);
+                                    None
+                                }
+                            };
+                            if let Some(pos) = pos {
+                                if let Err(e) = block.seek(pos).await {
+                                    warn!("FileSource: seeking to {} failed: {:?}", pos, e);
+                                }
+                            }
+                            Ok(Pmt::U64(block.pos))
+                        }
+                        .boxed()
+                    },
+                )
                 .build(),
-            MessageIoBuilder::new().build(),
             FileSource::<T> {
-                file_name: file_name.into(),
+                file_name,
                 file: None,
-                _type: std::marker::PhantomData,
+                item_size,
+                convert,
+                buf: Vec::new(),
+                offset,
+                length,
+                repeat,
+                pos: 0,
+                read_since_rewind: false,
+                _type: PhantomData,
             },
         )
     }
+
+    /// Seeks to an item, relative to the start of the file.
+    async fn seek(&mut self, pos: u64) -> Result<()> {
+        let file = self.file.as_mut().context("file not open")?;
+        file.seek(SeekFrom::Start(pos * self.item_size as u64))
+            .await
+            .with_context(|| format!("FileSource: seeking in {:?}", self.file_name))?;
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// Fills the buffer, returning less bytes only at the end of the file.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let file = self.file.as_mut().unwrap();
+        let mut i = 0;
+        while i < buf.len() {
+            match file.read(&mut buf[i..]).await {
+                Ok(0) => break,
+                Ok(n) => i += n,
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("FileSource: reading {:?}", self.file_name))
+                }
+            }
+        }
+        Ok(i)
+    }
 }
 
 #[async_trait]
@@ -69,27 +204,54 @@ impl<T: Send + 'static> Kernel for FileSource<T> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let out = sio.output(0).slice::<u8>();
-        let item_size = std::mem::size_of::<T>();
+        let out = sio.output(0).slice::<T>();
+        let mut produced = 0;
 
-        debug_assert_eq!(out.len() % item_size, 0);
+        while produced < out.len() {
+            let mut n = out.len() - produced;
+            if let Some(len) = self.length {
+                let left = (self.offset + len).saturating_sub(self.pos);
+                n = cmp::min(n as u64, left) as usize;
+            }
 
-        let mut i = 0;
+            let items = if n == 0 {
+                0
+            } else if let Some(convert) = self.convert {
+                let mut buf = std::mem::take(&mut self.buf);
+                buf.resize(n * self.item_size, 0);
+                let bytes = self.read(&mut buf).await?;
+                let items = bytes / self.item_size;
+                convert(
+                    &buf[..items * self.item_size],
+                    &mut out[produced..produced + items],
+                );
+                self.buf = buf;
+                items
+            } else {
+                let o = &mut sio.output(0).slice::<u8>()
+                    [produced * self.item_size..(produced + n) * self.item_size];
+                self.read(o).await? / self.item_size
+            };
 
-        while i < out.len() {
-            match self.file.as_mut().unwrap().read(&mut out[i..]).await {
-                Ok(0) => {
+            produced += items;
+            self.pos += items as u64;
+            if items > 0 {
+                self.read_since_rewind = true;
+            }
+
+            if items < n || n == 0 {
+                // end of file or of the requested section
+                if self.repeat && self.read_since_rewind {
+                    self.seek(self.offset).await?;
+                    self.read_since_rewind = false;
+                } else {
                     io.finished = true;
                     break;
                 }
-                Ok(written) => {
-                    i += written;
-                }
-                Err(e) => panic!("FileSource: Error reading from file: {:?}", e),
             }
         }
 
-        sio.output(0).produce(i / item_size);
+        sio.output(0).produce(produced);
 
         Ok(())
     }
@@ -100,7 +262,120 @@ impl<T: Send + 'static> Kernel for FileSource<T> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        self.file = Some(async_fs::File::open(self.file_name.clone()).await.unwrap());
+        let file = async_fs::File::open(&self.file_name)
+            .await
+            .with_context(|| format!("FileSource: cannot open {:?}", self.file_name))?;
+        self.file = Some(file);
+        if self.offset > 0 {
+            self.seek(self.offset).await?;
+        }
         Ok(())
     }
 }
+
+/// Loads samples from a file.
+///
+/// By default, samples are assumed to be encoded in the native format for the
+/// runtime. For example, on most machines, that means little endian. For
+/// complex samples, the real component must come before the complex
+/// component. For [`Complex32`] outputs, the file can use a different
+/// [`SampleFormat`], which is converted and scaled.
+///
+/// The source starts at an `offset` (in items of the file) and reads `length`
+/// items or up to the end of the file. It stops afterwards or, with
+/// `repeat`, starts again at the offset.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// **Message**: `seek`: seek to an item, counted from the start of the file;
+/// accepts a [`Pmt::U64`] value and returns the current position
+///
+/// # Outputs
+///
+/// `out`: Output samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{FileSource, FileSourceBuilder, SampleFormat};
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // Loads 8-byte samples from the file
+/// let source = fg.add_block(FileSource::<Complex<f32>>::new("my_filename.cf32"));
+///
+/// // Loops an RTL-SDR capture, skipping the first second
+/// let source = fg.add_block(
+///     FileSourceBuilder::<Complex<f32>>::new("capture.cu8")
+///         .format(SampleFormat::Cu8)
+///         .offset(2_048_000)
+///         .repeat(true)
+///         .build(),
+/// );
+/// ```
+pub struct FileSourceBuilder<T: Send + 'static> {
+    file_name: String,
+    item_size: usize,
+    convert: Option<Converter<T>>,
+    offset: u64,
+    length: Option<u64>,
+    repeat: bool,
+}
+
+impl<T: Send + 'static> FileSourceBuilder<T> {
+    pub fn new<S: Into<String>>(file_name: S) -> FileSourceBuilder<T> {
+        FileSourceBuilder {
+            file_name: file_name.into(),
+            item_size: std::mem::size_of::<T>(),
+            convert: None,
+            offset: 0,
+            length: None,
+            repeat: false,
+        }
+    }
+
+    /// Number of items to skip at the start of the file.
+    #[must_use]
+    pub fn offset(mut self, offset: u64) -> FileSourceBuilder<T> {
+        self.offset = offset;
+        self
+    }
+
+    /// Number of items to read, starting at the offset.
+    #[must_use]
+    pub fn length(mut self, length: u64) -> FileSourceBuilder<T> {
+        self.length = Some(length);
+        self
+    }
+
+    /// Start again at the offset, after reaching the end.
+    #[must_use]
+    pub fn repeat(mut self, repeat: bool) -> FileSourceBuilder<T> {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        FileSource::<T>::with_config(
+            self.file_name,
+            self.item_size,
+            self.convert,
+            self.offset,
+            self.length,
+            self.repeat,
+        )
+    }
+}
+
+impl FileSourceBuilder<Complex32> {
+    /// Storage format of the file.
+    #[must_use]
+    pub fn format(mut self, format: SampleFormat) -> FileSourceBuilder<Complex32> {
+        self.item_size = format.item_size();
+        self.convert = Some(format.converter());
+        self
+    }
+}
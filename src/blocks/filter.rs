@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -54,8 +52,8 @@ where
         Block::new(
             BlockMetaBuilder::new("Filter").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out", mem::size_of::<B>())
+                .add_typed_input::<A>("in")
+                .add_typed_output::<B>("out")
                 .build(),
             MessageIoBuilder::<Filter<A, B>>::new().build(),
             Filter { f: Box::new(f) },
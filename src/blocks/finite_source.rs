@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -25,9 +23,7 @@ where
     pub fn new(f: impl FnMut() -> Option<A> + Send + 'static) -> Block {
         Block::new(
             BlockMetaBuilder::new("FiniteSource").build(),
-            StreamIoBuilder::new()
-                .add_output("out", mem::size_of::<A>())
-                .build(),
+            StreamIoBuilder::new().add_typed_output::<A>("out").build(),
             MessageIoBuilder::<FiniteSource<A>>::new().build(),
             FiniteSource { f: Box::new(f) },
         )
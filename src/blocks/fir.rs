@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -12,14 +10,18 @@ use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 use futuredsp::fir::*;
 use futuredsp::firdes;
-use futuredsp::{TapsAccessor, UnaryKernel};
+use futuredsp::{StatefulUnaryKernel, TapsAccessor, UnaryKernel};
+use num_complex::Complex;
 use num_integer;
 
+/// Number of taps above which [FirBuilder::new] uses FFT-based fast convolution.
+const OVERLAP_SAVE_THRESHOLD: usize = 256;
+
 pub struct Fir<SampleType, TapType, Core>
 where
     SampleType: 'static + Send,
     TapType: 'static,
-    Core: 'static + UnaryKernel<SampleType>,
+    Core: 'static + StatefulUnaryKernel<SampleType>,
 {
     core: Core,
     _sampletype: std::marker::PhantomData<SampleType>,
@@ -30,7 +32,7 @@ unsafe impl<SampleType, TapType, Core> Send for Fir<SampleType, TapType, Core>
 where
     SampleType: 'static + Send,
     TapType: 'static,
-    Core: 'static + UnaryKernel<SampleType>,
+    Core: 'static + StatefulUnaryKernel<SampleType>,
 {
 }
 
@@ -38,14 +40,14 @@ impl<SampleType, TapType, Core> Fir<SampleType, TapType, Core>
 where
     SampleType: 'static + Send,
     TapType: 'static,
-    Core: 'static + UnaryKernel<SampleType>,
+    Core: 'static + StatefulUnaryKernel<SampleType>,
 {
     pub fn new(core: Core) -> Block {
         Block::new(
             BlockMetaBuilder::new("Fir").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<SampleType>())
-                .add_output("out", mem::size_of::<SampleType>())
+                .add_typed_input::<SampleType>("in")
+                .add_typed_output::<SampleType>("out")
                 .build(),
             MessageIoBuilder::<Fir<SampleType, TapType, Core>>::new().build(),
             Fir {
@@ -62,7 +64,7 @@ impl<SampleType, TapType, Core> Kernel for Fir<SampleType, TapType, Core>
 where
     SampleType: 'static + Send,
     TapType: 'static,
-    Core: 'static + UnaryKernel<SampleType>,
+    Core: 'static + StatefulUnaryKernel<SampleType>,
 {
     async fn work(
         &mut self,
@@ -90,13 +92,15 @@ where
 /// Creates a generic FIR filter.
 ///
 /// Uses the `futuredsp` to pick the optimal FIR implementation for the given
-/// constraints.
+/// constraints. Filters with more than 256 taps use FFT-based fast convolution
+/// ([OverlapSaveFirKernel]), shorter filters the direct form
+/// ([NonResamplingFirKernel]).
 ///
 /// Note that there must be an implementation of [futuredsp::TapsAccessor] for
 /// the taps object you pass in, see docs for details.
 ///
 /// Additionally, there must be an available core (implementation of
-/// [futuredsp::UnaryKernel]) available for the specified `SampleType` and
+/// [futuredsp::StatefulUnaryKernel]) available for the specified `SampleType` and
 /// `TapsType`. See the [futuredsp docs](futuredsp::fir) for available
 /// implementations.
 ///
@@ -119,6 +123,7 @@ where
 /// let fir = fg.add_block(FirBuilder::new::<f32, f32, _>([1.0, 2.0, 3.0]));
 /// let fir = fg.add_block(FirBuilder::new::<Complex<f32>, f32, _>(&[1.0, 2.0, 3.0]));
 /// let fir = fg.add_block(FirBuilder::new::<f32, f32, _>(vec![1.0, 2.0, 3.0]));
+/// let fir = fg.add_block(FirBuilder::new_overlap_save::<f32, f32, _>(vec![1.0, 2.0, 3.0]));
 ///
 /// let fir = fg.add_block(FirBuilder::new_resampling_with_taps::<f32, f32, _>(3, 2, vec![1.0, 2.0, 3.0]));
 /// ```
@@ -133,10 +138,31 @@ impl FirBuilder {
         SampleType: 'static + Send,
         TapType: 'static,
         Taps: 'static + TapsAccessor,
+        Taps::TapType: 'static + Into<Complex<f32>>,
         NonResamplingFirKernel<SampleType, Taps>: UnaryKernel<SampleType>,
+        OverlapSaveFirKernel<SampleType, Taps::TapType>: StatefulUnaryKernel<SampleType>,
+    {
+        if taps.num_taps() > OVERLAP_SAVE_THRESHOLD {
+            FirBuilder::new_overlap_save::<SampleType, TapType, Taps>(taps)
+        } else {
+            Fir::<SampleType, TapType, NonResamplingFirKernel<SampleType, Taps>>::new(
+                NonResamplingFirKernel::new(taps),
+            )
+        }
+    }
+
+    /// Create a new non-resampling FIR filter that uses FFT-based fast
+    /// convolution, independent of the number of taps.
+    pub fn new_overlap_save<SampleType, TapType, Taps>(taps: Taps) -> Block
+    where
+        SampleType: 'static + Send,
+        TapType: 'static,
+        Taps: 'static + TapsAccessor,
+        Taps::TapType: 'static + Into<Complex<f32>>,
+        OverlapSaveFirKernel<SampleType, Taps::TapType>: StatefulUnaryKernel<SampleType>,
     {
-        Fir::<SampleType, TapType, NonResamplingFirKernel<SampleType, Taps>>::new(
-            NonResamplingFirKernel::new(taps),
+        Fir::<SampleType, TapType, OverlapSaveFirKernel<SampleType, Taps::TapType>>::new(
+            OverlapSaveFirKernel::new(taps),
         )
     }
 
@@ -41,8 +41,8 @@ impl<T: Send + 'static> Head<T> {
         Block::new(
             BlockMetaBuilder::new("Head").build(),
             StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<T>())
-                .add_output("out", std::mem::size_of::<T>())
+                .add_typed_input::<T>("in")
+                .add_typed_output::<T>("out")
                 .build(),
             MessageIoBuilder::new().build(),
             Head::<T> {
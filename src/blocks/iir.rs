@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -11,6 +9,8 @@ use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 use futuredsp::iir::IirKernel;
+use futuredsp::iir::SosKernel;
+use futuredsp::iirdes::Sos;
 use futuredsp::{StatefulUnaryKernel, TapsAccessor};
 
 pub struct Iir<SampleType, TapType, Core>
@@ -42,8 +42,8 @@ where
         Block::new(
             BlockMetaBuilder::new("Iir").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<SampleType>())
-                .add_output("out", mem::size_of::<SampleType>())
+                .add_typed_input::<SampleType>("in")
+                .add_typed_output::<SampleType>("out")
                 .build(),
             MessageIoBuilder::<Iir<SampleType, TapType, Core>>::new().build(),
             Iir {
@@ -107,6 +107,10 @@ where
 /// and `TapsType`. See the [futuredsp docs](futuredsp::iir) for available
 /// implementations.
 ///
+/// Filters of higher order should be run as a cascade of second-order
+/// sections, e.g., as designed by [futuredsp::iirdes], using
+/// [IirBuilder::new_sos].
+///
 /// # Inputs
 ///
 /// `in`: Input
@@ -117,18 +121,24 @@ where
 ///
 /// # Usage
 /// ```
+/// use futuredsp::iirdes::BandType;
 /// use futuresdr::blocks::IirBuilder;
 /// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
 ///
 /// let mut fg = Flowgraph::new();
 ///
 /// let iir = fg.add_block(IirBuilder::new::<f32, f32, _>([1.0, 2.0, 3.0], [4.0, 5.0, 6.0]));
+///
+/// let sections = futuredsp::iirdes::elliptic(6, 0.5, 60.0, BandType::Lowpass(0.1));
+/// let iir = fg.add_block(IirBuilder::new_sos::<Complex<f32>>(&sections));
 /// ```
 pub struct IirBuilder {
     //
 }
 
 impl IirBuilder {
+    /// Create a new IIR filter in direct form with the specified taps.
     pub fn new<SampleType, TapType, Taps>(a_taps: Taps, b_taps: Taps) -> Block
     where
         SampleType: 'static + Send + Clone,
@@ -138,4 +148,13 @@ impl IirBuilder {
     {
         Iir::<SampleType, TapType, IirKernel<SampleType, Taps>>::new(IirKernel::new(a_taps, b_taps))
     }
+
+    /// Create a new IIR filter as a cascade of second-order sections.
+    pub fn new_sos<SampleType>(sections: &[Sos]) -> Block
+    where
+        SampleType: 'static + Send + Copy + num_traits::Zero,
+        SosKernel<SampleType>: StatefulUnaryKernel<SampleType>,
+    {
+        Iir::<SampleType, f32, SosKernel<SampleType>>::new(SosKernel::new(sections))
+    }
 }
@@ -10,7 +10,39 @@
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
 //! | [fir](FirBuilder) | Generic FIR filter, resampler | ✅ |
+//! | [ArbitraryResampler](ArbitraryResamplerBuilder) | Resamples by an arbitrary ratio | ✅ |
 //! | [fft](FftBuilder) | Computes FFT | ✅ |
+//! | [Psd](PsdBuilder) | Power spectral density with averaging, peak hold, and waterfall frames | ✅ |
+//! | [CostasLoop](CostasLoopBuilder) | Carrier recovery for BPSK, QPSK, and 8PSK | ✅ |
+//! | [PllCarrierTracking](PllCarrierTrackingBuilder) | Tracks and removes a carrier with a PLL | ✅ |
+//! | [SymbolSync](SymbolSyncBuilder) | Symbol timing recovery | ✅ |
+//! | [ConstellationMapper] | Maps bits to PSK/QAM symbols | ✅ |
+//! | [ConstellationDemapper](ConstellationDemapperBuilder) | Demaps PSK/QAM symbols to hard or soft bits | ✅ |
+//!
+//! ## Detection blocks
+//! | Block | Usage | WebAssembly? |
+//! |---|---|---|
+//! | [PowerSquelch](PowerSquelchBuilder) | Zeros or drops samples below a power threshold | ✅ |
+//! | [EnergyDetector](EnergyDetectorBuilder) | Tags start and end of bursts of energy | ✅ |
+//! | [Cfar](CfarBuilder) | CA-CFAR detector on spectra, emitting detections as messages | ✅ |
+//!
+//! ## Packet framing blocks
+//! | Block | Usage | WebAssembly? |
+//! |---|---|---|
+//! | [PreambleCorrelator](PreambleCorrelatorBuilder) | Detects a preamble and estimates timing, phase, and frequency offset | ✅ |
+//! | [AccessCodeDetector](AccessCodeDetectorBuilder) | Detects an access code in a bit stream | ✅ |
+//! | [TaggedStreamToPdu](TaggedStreamToPduBuilder) | Slices tagged bursts into PDUs | ✅ |
+//! | [PduToTaggedStream] | Converts PDUs into a stream with length tags | ✅ |
+//!
+//! ## FEC blocks
+//! | Block | Usage | WebAssembly? |
+//! |---|---|---|
+//! | [CrcGenerator](fec::CrcGenerator) | Appends a CRC to frames | ✅ |
+//! | [CrcChecker](fec::CrcChecker) | Checks and strips the CRC of frames | ✅ |
+//! | [ConvolutionalEncoder](fec::ConvolutionalEncoder) | Convolutional encoder with puncturing | ✅ |
+//! | [ViterbiDecoder](fec::ViterbiDecoder) | Soft-decision Viterbi decoder | ✅ |
+//! | [ReedSolomonEncoder](fec::ReedSolomonEncoder) | Reed-Solomon encoder | ✅ |
+//! | [ReedSolomonDecoder](fec::ReedSolomonDecoder) | Reed-Solomon decoder | ✅ |
 //!
 //! ## Limiting blocks
 //! | Block| Usage | WebAssembly? |
@@ -21,21 +53,42 @@
 //! ## Source/sink blocks
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
-//! | [FileSource] | Reads samples from a file | ❌ |
+//! | [FileSource](FileSourceBuilder) | Reads samples from a file | ❌ |
 //! | [SoapySink](SoapySinkBuilder) | Transmit samples with a soapy device | ❌ |
 //! | [SoapySource](SoapySourceBuilder) | Read samples from a soapy device | ❌ |
+//! | [RtlTcpSource](RtlTcpSourceBuilder) | Read samples from an `rtl_tcp` server | ❌ |
 //! | [Source] | Repeatedly apply a function to generate samples | ✅ |
 //! | [NullSource] | Generates a stream of zeros | ✅ |
-//! | [FileSink] | Writes samples to a file | ❌ |
+//! | [FileSink](FileSinkBuilder) | Writes samples to files, with rotation and triggered capture | ❌ |
+//! | [SigMfSource](sigmf::SigMfSourceBuilder) | Plays back a SigMF recording | ❌ |
+//! | [SigMfSink](sigmf::SigMfSinkBuilder) | Records samples with SigMF metadata | ❌ |
 //! | [NullSink] | Drops samples | ✅ |
 //! | [TagSink] | Drops samples, printing tags. | ✅ |
 //! | [WavSink] | Writes samples to a WAV file | ❌ |
 //!
+//! ## Network blocks
+//! | Block | Usage | WebAssembly? |
+//! |---|---|---|
+//! | [TcpSource](TcpSourceBuilder) | Reads samples from a TCP client or server, reconnecting | ❌ |
+//! | [TcpSink](TcpSinkBuilder) | Writes samples to TCP clients or a server, reconnecting | ❌ |
+//! | [UdpSource](UdpSourceBuilder) | Receives samples in UDP datagrams, detecting lost datagrams | ❌ |
+//! | [UdpSink](UdpSinkBuilder) | Sends samples in UDP datagrams with sequence numbers | ❌ |
+//! | [BlobToUdp] | Sends [`Pmt::Blob`](crate::runtime::Pmt::Blob) messages in UDP datagrams | ❌ |
+//! | [UdpToBlob] | Receives UDP datagrams as [`Pmt::Blob`](crate::runtime::Pmt::Blob) messages | ❌ |
+//! | [BlobToTcp] | Sends [`Pmt::Blob`](crate::runtime::Pmt::Blob) messages to TCP clients with a length prefix | ❌ |
+//! | [TcpToBlob](TcpToBlobBuilder) | Receives length-prefixed [`Pmt::Blob`](crate::runtime::Pmt::Blob) messages over TCP | ❌ |
+//! | [WebsocketSink](WebsocketSinkBuilder) | Sends samples to websocket clients, dropping frames per client | ❌ |
+//! | [WebsocketSource](WebsocketSourceBuilder) | Receives samples from a websocket client | ❌ |
+//! | [WebsocketPmt](WebsocketPmtBuilder) | Exchanges JSON-serialized messages with websocket clients | ❌ |
+//!
 //! ## Message blocks
 //! | Block | Usage | WebAssembly? |
 //! |---|---|---|
 //! | [MessageSource](MessageSourceBuilder) | Repeats a fixed message on an interval | ❌ |
 
+mod access_code_detector;
+pub use access_code_detector::{AccessCodeDetector, AccessCodeDetectorBuilder};
+
 mod apply;
 pub use apply::Apply;
 
@@ -45,24 +98,51 @@ pub use applynm::ApplyNM;
 mod applyintoiter;
 pub use applyintoiter::ApplyIntoIter;
 
+mod arbitrary_resampler;
+pub use arbitrary_resampler::{ArbitraryResampler, ArbitraryResamplerBuilder};
+
 pub mod audio;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod blob_to_tcp;
+#[cfg(not(target_arch = "wasm32"))]
+pub use blob_to_tcp::BlobToTcp;
 #[cfg(not(target_arch = "wasm32"))]
 mod blob_to_udp;
 #[cfg(not(target_arch = "wasm32"))]
 pub use blob_to_udp::BlobToUdp;
 
+mod cfar;
+pub use cfar::{Cfar, CfarBuilder};
+
 mod combine;
 pub use combine::Combine;
 
 mod console_sink;
 pub use console_sink::ConsoleSink;
 
+mod constellation;
+pub use constellation::Constellation;
+mod constellation_demapper;
+pub use constellation_demapper::{ConstellationDemapper, ConstellationDemapperBuilder};
+mod constellation_mapper;
+pub use constellation_mapper::ConstellationMapper;
+
+mod control_loop;
+
 mod copy;
 pub use copy::Copy;
 mod copy_rand;
 pub use copy_rand::{CopyRand, CopyRandBuilder};
 
+mod costas_loop;
+pub use costas_loop::{CostasLoop, CostasLoopBuilder, CostasOrder};
+
+mod energy_detector;
+pub use energy_detector::{EnergyDetector, EnergyDetectorBuilder};
+
+pub mod fec;
+
 mod filter;
 pub use filter::Filter;
 
@@ -75,12 +155,12 @@ pub use fft::{Fft, FftBuilder};
 #[cfg(not(target_arch = "wasm32"))]
 mod file_sink;
 #[cfg(not(target_arch = "wasm32"))]
-pub use file_sink::FileSink;
+pub use file_sink::{FileSink, FileSinkBuilder};
 
 #[cfg(not(target_arch = "wasm32"))]
 mod file_source;
 #[cfg(not(target_arch = "wasm32"))]
-pub use file_source::FileSource;
+pub use file_source::{FileSource, FileSourceBuilder, SampleFormat};
 
 mod finite_source;
 pub use finite_source::FiniteSource;
@@ -110,6 +190,30 @@ pub use null_sink::NullSink;
 mod null_source;
 pub use null_source::NullSource;
 
+mod pdu_to_tagged_stream;
+pub use pdu_to_tagged_stream::PduToTaggedStream;
+
+mod pll_carrier_tracking;
+pub use pll_carrier_tracking::{PllCarrierTracking, PllCarrierTrackingBuilder};
+
+mod power_detector;
+mod power_squelch;
+pub use power_squelch::{PowerSquelch, PowerSquelchBuilder};
+
+mod preamble_correlator;
+pub use preamble_correlator::{PreambleCorrelator, PreambleCorrelatorBuilder};
+
+mod psd;
+pub use psd::{Psd, PsdAveraging, PsdBuilder};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod rtl_tcp_source;
+#[cfg(not(target_arch = "wasm32"))]
+pub use rtl_tcp_source::{RtlTcpSource, RtlTcpSourceBuilder, RtlTuner};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sigmf;
+
 #[cfg(feature = "soapy")]
 mod soapy_snk;
 #[cfg(feature = "soapy")]
@@ -124,9 +228,15 @@ pub use source::Source;
 mod split;
 pub use split::Split;
 
+mod symbol_sync;
+pub use symbol_sync::{SymbolSync, SymbolSyncBuilder, SymbolSyncSample, TimingErrorDetector};
+
 mod tag_debug;
 pub use tag_debug::TagDebug;
 
+mod tagged_stream_to_pdu;
+pub use tagged_stream_to_pdu::{TaggedStreamToPdu, TaggedStreamToPduBuilder};
+
 #[cfg(not(target_arch = "wasm32"))]
 mod tcp_sink;
 #[cfg(not(target_arch = "wasm32"))]
@@ -136,12 +246,29 @@ pub use tcp_sink::{TcpSink, TcpSinkBuilder};
 mod tcp_source;
 #[cfg(not(target_arch = "wasm32"))]
 pub use tcp_source::{TcpSource, TcpSourceBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+mod tcp_to_blob;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tcp_to_blob::{TcpToBlob, TcpToBlobBuilder};
 
 #[cfg(not(target_arch = "wasm32"))]
 mod throttle;
 #[cfg(not(target_arch = "wasm32"))]
 pub use throttle::Throttle;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod udp_sink;
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_sink::{UdpSink, UdpSinkBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+mod udp_source;
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_source::{UdpSource, UdpSourceBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+mod udp_to_blob;
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_to_blob::UdpToBlob;
+
 mod vector_sink;
 pub use vector_sink::{VectorSink, VectorSinkBuilder};
 mod vector_source;
@@ -161,11 +288,21 @@ mod wasm_freq;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_freq::WasmFreq;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket_pmt;
+#[cfg(not(target_arch = "wasm32"))]
+pub use websocket_pmt::{WebsocketPmt, WebsocketPmtBuilder};
+
 #[cfg(not(target_arch = "wasm32"))]
 mod websocket_sink;
 #[cfg(not(target_arch = "wasm32"))]
 pub use websocket_sink::{WebsocketSink, WebsocketSinkBuilder, WebsocketSinkMode};
 
+#[cfg(not(target_arch = "wasm32"))]
+mod websocket_source;
+#[cfg(not(target_arch = "wasm32"))]
+pub use websocket_source::{WebsocketSource, WebsocketSourceBuilder, WebsocketSourceMode};
+
 #[cfg(feature = "wgpu")]
 mod wgpu;
 #[cfg(feature = "wgpu")]
@@ -37,9 +37,7 @@ impl<T: Send + 'static> NullSource<T> {
     pub fn new() -> Block {
         Block::new(
             BlockMetaBuilder::new("NullSource").build(),
-            StreamIoBuilder::new()
-                .add_output("out", std::mem::size_of::<T>())
-                .build(),
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
             MessageIoBuilder::new().build(),
             NullSource::<T> {
                 _type: std::marker::PhantomData,
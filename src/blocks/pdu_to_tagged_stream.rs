@@ -0,0 +1,142 @@
+use futures::FutureExt;
+use std::collections::VecDeque;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+const MAX_PDUS: usize = 1024;
+
+/// Converts PDUs into a tagged stream.
+///
+/// The bytes of each [Pmt::Blob] are interpreted as items of type `T` and
+/// written to the output. The first item of each PDU is tagged with its length
+/// in items, i.e., `Tag::NamedUsize(key, len)`. PDUs that do not contain a
+/// whole number of items are dropped.
+///
+/// # Inputs
+///
+/// `in`: PDUs as [Pmt::Blob]
+///
+/// # Outputs
+///
+/// `out`: Tagged stream
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::PduToTaggedStream;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let stream = fg.add_block(PduToTaggedStream::<u8>::new("packet_len"));
+/// ```
+pub struct PduToTaggedStream<T: Send + 'static> {
+    key: String,
+    pdus: VecDeque<Vec<u8>>,
+    current: Vec<u8>,
+    index: usize,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: Send + 'static> PduToTaggedStream<T> {
+    pub fn new(key: impl Into<String>) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PduToTaggedStream").build(),
+            StreamIoBuilder::new()
+                .add_typed_output::<T>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut PduToTaggedStream<T>,
+                     _mio: &mut MessageIo<PduToTaggedStream<T>>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            match p {
+                                Pmt::Blob(v) => {
+                                    if v.is_empty() || v.len() % std::mem::size_of::<T>() > 0 {
+                                        warn!(
+                                            "PduToTaggedStream: PDU of {} bytes is not a whole number of items. Dropping.",
+                                            v.len()
+                                        );
+                                    } else if block.pdus.len() >= MAX_PDUS {
+                                        warn!(
+                                            "PduToTaggedStream: max number of PDUs already queued ({}). Dropping.",
+                                            MAX_PDUS
+                                        );
+                                    } else {
+                                        block.pdus.push_back(v);
+                                    }
+                                }
+                                _ => {
+                                    warn!("PduToTaggedStream/in Handler received wrong PMT {:?}", &p);
+                                }
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            PduToTaggedStream::<T> {
+                key: key.into(),
+                pdus: VecDeque::new(),
+                current: Vec::new(),
+                index: 0,
+                _type: std::marker::PhantomData,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Kernel for PduToTaggedStream<T> {
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+        let capacity = o.len() / item_size * item_size;
+
+        let mut written = 0;
+        while written < capacity {
+            if self.index == self.current.len() {
+                match self.pdus.pop_front() {
+                    Some(pdu) => {
+                        self.current = pdu;
+                        self.index = 0;
+                        sio.output(0).add_tag(
+                            written / item_size,
+                            Tag::NamedUsize(self.key.clone(), self.current.len() / item_size),
+                        );
+                    }
+                    None => break,
+                }
+            }
+
+            let n = std::cmp::min(capacity - written, self.current.len() - self.index);
+            o[written..written + n].copy_from_slice(&self.current[self.index..self.index + n]);
+            written += n;
+            self.index += n;
+        }
+
+        sio.output(0).produce(written / item_size);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use futures::FutureExt;
+use std::f32::consts::PI;
+
+use crate::anyhow::Result;
+use crate::blocks::control_loop::ControlLoop;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+fn mod_2pi(mut x: f32) -> f32 {
+    while x > PI {
+        x -= 2.0 * PI;
+    }
+    while x < -PI {
+        x += 2.0 * PI;
+    }
+    x
+}
+
+pub struct PllCarrierTracking {
+    control: ControlLoop,
+}
+
+impl PllCarrierTracking {
+    pub fn new(loop_bw: f32, max_freq: f32, min_freq: f32) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PllCarrierTracking").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "loop_bw",
+                    |block: &mut PllCarrierTracking,
+                     _mio: &mut MessageIo<PllCarrierTracking>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Double(bw) = &p {
+                                block.control.set_loop_bw(*bw as f32);
+                            } else if p != Pmt::Null {
+                                warn!(
+                                    "PllCarrierTracking/loop_bw Handler received wrong PMT {:?}",
+                                    &p
+                                );
+                            }
+                            Ok(Pmt::Double(block.control.loop_bw() as f64))
+                        }
+                        .boxed()
+                    },
+                )
+                .add_input(
+                    "freq",
+                    |block: &mut PllCarrierTracking,
+                     _mio: &mut MessageIo<PllCarrierTracking>,
+                     _meta: &mut BlockMeta,
+                     _p: Pmt| {
+                        async move { Ok(Pmt::Double(block.control.freq() as f64)) }.boxed()
+                    },
+                )
+                .build(),
+            PllCarrierTracking {
+                control: ControlLoop::new(loop_bw, max_freq, min_freq),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PllCarrierTracking {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let o = sio.output(0).slice::<Complex32>();
+
+        let n = std::cmp::min(i.len(), o.len());
+
+        for (x, y) in i.iter().zip(o.iter_mut()).take(n) {
+            let phase = self.control.phase();
+            *y = x * Complex32::from_polar(1.0, -phase);
+            let error = mod_2pi(x.arg() - phase);
+            self.control.advance(error);
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// PLL that tracks the carrier of the input signal and mixes it down to baseband.
+///
+/// The loop locks onto the strongest carrier within `[min_freq, max_freq]`
+/// (in rad/sample). The loop bandwidth is given in rad/sample.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// **Message**: `loop_bw`: set the loop bandwidth; accepts a [`Pmt::Double`]
+/// value and returns the current loop bandwidth
+///
+/// **Message**: `freq`: returns the current frequency estimate in rad/sample
+///
+/// # Outputs
+///
+/// `out`: Derotated samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::PllCarrierTrackingBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let pll = fg.add_block(
+///     PllCarrierTrackingBuilder::new()
+///         .loop_bw(0.01)
+///         .freq_range(-0.5, 0.5)
+///         .build(),
+/// );
+/// ```
+pub struct PllCarrierTrackingBuilder {
+    loop_bw: f32,
+    max_freq: f32,
+    min_freq: f32,
+}
+
+impl PllCarrierTrackingBuilder {
+    pub fn new() -> PllCarrierTrackingBuilder {
+        PllCarrierTrackingBuilder {
+            loop_bw: 2.0 * PI / 200.0,
+            max_freq: PI,
+            min_freq: -PI,
+        }
+    }
+
+    #[must_use]
+    pub fn loop_bw(mut self, loop_bw: f32) -> PllCarrierTrackingBuilder {
+        self.loop_bw = loop_bw;
+        self
+    }
+
+    #[must_use]
+    pub fn freq_range(mut self, min_freq: f32, max_freq: f32) -> PllCarrierTrackingBuilder {
+        self.min_freq = min_freq;
+        self.max_freq = max_freq;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        PllCarrierTracking::new(self.loop_bw, self.max_freq, self.min_freq)
+    }
+}
+
+impl Default for PllCarrierTrackingBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
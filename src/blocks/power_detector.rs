@@ -0,0 +1,77 @@
+use crate::num_complex::Complex32;
+
+/// Averaged power with a hysteresis threshold, shared by the squelch and
+/// energy detector blocks.
+///
+/// The power is smoothed with a single-pole IIR filter. The detector opens
+/// when the smoothed power rises above the threshold and closes when it falls
+/// below the threshold minus the hysteresis.
+#[derive(Debug, Clone)]
+pub(crate) struct PowerDetector {
+    alpha: f32,
+    avg: f32,
+    open_threshold: f32,
+    close_threshold: f32,
+    threshold_db: f32,
+    hysteresis_db: f32,
+    open: bool,
+}
+
+impl PowerDetector {
+    pub fn new(threshold_db: f32, hysteresis_db: f32, alpha: f32) -> PowerDetector {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        assert!(hysteresis_db >= 0.0, "hysteresis must not be negative");
+        let mut d = PowerDetector {
+            alpha,
+            avg: 0.0,
+            open_threshold: 0.0,
+            close_threshold: 0.0,
+            threshold_db,
+            hysteresis_db,
+            open: false,
+        };
+        d.update_thresholds();
+        d
+    }
+
+    fn update_thresholds(&mut self) {
+        self.open_threshold = 10f32.powf(self.threshold_db / 10.0);
+        self.close_threshold = 10f32.powf((self.threshold_db - self.hysteresis_db) / 10.0);
+    }
+
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db;
+        self.update_thresholds();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Smoothed power in dB.
+    pub fn power_db(&self) -> f32 {
+        10.0 * self.avg.log10()
+    }
+
+    /// Updates the detector with a sample and returns the new state if it
+    /// changed.
+    pub fn update(&mut self, x: Complex32) -> Option<bool> {
+        let p = x.norm_sqr();
+        if p.is_finite() {
+            self.avg += self.alpha * (p - self.avg);
+        }
+        if !self.open && self.avg > self.open_threshold {
+            self.open = true;
+            Some(true)
+        } else if self.open && self.avg < self.close_threshold {
+            self.open = false;
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use futures::FutureExt;
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::blocks::power_detector::PowerDetector;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct PowerSquelch {
+    detector: PowerDetector,
+    gate: bool,
+}
+
+impl PowerSquelch {
+    pub fn new(threshold_db: f32, hysteresis_db: f32, alpha: f32, gate: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PowerSquelch").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "threshold",
+                    |block: &mut PowerSquelch,
+                     _mio: &mut MessageIo<PowerSquelch>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::Double(t) = &p {
+                                block.detector.set_threshold_db(*t as f32);
+                            } else if p != Pmt::Null {
+                                warn!("PowerSquelch/threshold Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Double(block.detector.threshold_db() as f64))
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            PowerSquelch {
+                detector: PowerDetector::new(threshold_db, hysteresis_db, alpha),
+                gate,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PowerSquelch {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let o = sio.output(0).slice::<Complex32>();
+
+        // in gate mode, each input sample produces at most one output sample
+        let n = cmp::min(i.len(), o.len());
+        let mut produced = 0;
+
+        for x in i.iter().take(n) {
+            self.detector.update(*x);
+            if self.detector.is_open() {
+                o[produced] = *x;
+                produced += 1;
+            } else if !self.gate {
+                o[produced] = Complex32::new(0.0, 0.0);
+                produced += 1;
+            }
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(produced);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Power squelch with hysteresis.
+///
+/// Averages the power of the input with a single-pole filter (weight `alpha`
+/// of the new sample). The squelch opens when the average rises above the
+/// threshold (in dB) and closes when it falls below the threshold minus the
+/// hysteresis. While closed, samples are replaced by zeros or, in gate mode,
+/// dropped.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// **Message**: `threshold`: set the threshold in dB; accepts a
+/// [`Pmt::Double`] value and returns the current threshold
+///
+/// # Outputs
+///
+/// `out`: Squelched samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::PowerSquelchBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let squelch = fg.add_block(
+///     PowerSquelchBuilder::new(-30.0)
+///         .hysteresis(3.0)
+///         .gate(true)
+///         .build(),
+/// );
+/// ```
+pub struct PowerSquelchBuilder {
+    threshold_db: f32,
+    hysteresis_db: f32,
+    alpha: f32,
+    gate: bool,
+}
+
+impl PowerSquelchBuilder {
+    pub fn new(threshold_db: f32) -> PowerSquelchBuilder {
+        PowerSquelchBuilder {
+            threshold_db,
+            hysteresis_db: 3.0,
+            alpha: 0.01,
+            gate: false,
+        }
+    }
+
+    #[must_use]
+    pub fn hysteresis(mut self, hysteresis_db: f32) -> PowerSquelchBuilder {
+        self.hysteresis_db = hysteresis_db;
+        self
+    }
+
+    #[must_use]
+    pub fn alpha(mut self, alpha: f32) -> PowerSquelchBuilder {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Drop samples while the squelch is closed instead of zeroing them.
+    #[must_use]
+    pub fn gate(mut self, gate: bool) -> PowerSquelchBuilder {
+        self.gate = gate;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        PowerSquelch::new(self.threshold_db, self.hysteresis_db, self.alpha, self.gate)
+    }
+}
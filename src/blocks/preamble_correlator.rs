@@ -0,0 +1,205 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+pub struct PreambleCorrelator {
+    preamble: Vec<Complex32>,
+    energy: f32,
+    threshold: f32,
+    prev: f32,
+    holdoff: usize,
+}
+
+impl PreambleCorrelator {
+    pub fn new(preamble: Vec<Complex32>, threshold: f32) -> Block {
+        assert!(
+            preamble.len() >= 2,
+            "preamble must have at least two samples"
+        );
+        let energy = preamble.iter().map(|x| x.norm_sqr()).sum();
+
+        Block::new(
+            BlockMetaBuilder::new("PreambleCorrelator").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<Complex32>("in")
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            PreambleCorrelator {
+                preamble: preamble.iter().map(|x| x.conj()).collect(),
+                energy,
+                threshold,
+                prev: 0.0,
+                holdoff: 0,
+            },
+        )
+    }
+
+    /// Correlation of both halves of the preamble with the window.
+    fn correlate(&self, window: &[Complex32]) -> (Complex32, Complex32) {
+        let half = self.preamble.len() / 2;
+        let c = |x: &[Complex32], p: &[Complex32]| -> Complex32 {
+            x.iter().zip(p.iter()).map(|(x, p)| x * p).sum()
+        };
+        (
+            c(&window[..half], &self.preamble[..half]),
+            c(&window[half..], &self.preamble[half..]),
+        )
+    }
+
+    /// Normalized correlation magnitude in `[0, 1]`.
+    fn magnitude(&self, window: &[Complex32]) -> f32 {
+        let (c1, c2) = self.correlate(window);
+        let energy = window.iter().map(|x| x.norm_sqr()).sum::<f32>() * self.energy;
+        if energy > 0.0 {
+            (c1 + c2).norm() / energy.sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    fn estimate(&self, window: &[Complex32], prev: f32, cur: f32, next: f32) -> Vec<Tag> {
+        let l = self.preamble.len();
+        let (c1, c2) = self.correlate(window);
+
+        // phase advance between the halves
+        let freq = (c2 * c1.conj()).arg() / (l / 2) as f32;
+        // the correlation measures the phase at the center of the preamble
+        let phase = Complex32::from_polar(1.0, (c1 + c2).arg() - freq * (l - 1) as f32 / 2.0).arg();
+        // parabolic interpolation of the peak
+        let denom = prev - 2.0 * cur + next;
+        let time = if denom.abs() > f32::EPSILON {
+            0.5 * (prev - next) / denom
+        } else {
+            0.0
+        };
+
+        vec![
+            Tag::NamedF32("corr_est".to_string(), cur),
+            Tag::NamedF32("time_est".to_string(), time),
+            Tag::NamedF32("phase_est".to_string(), phase),
+            Tag::NamedF32("freq_est".to_string(), freq),
+        ]
+    }
+}
+
+#[async_trait]
+impl Kernel for PreambleCorrelator {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex32>();
+        let o = sio.output(0).slice::<Complex32>();
+        let l = self.preamble.len();
+
+        // a peak at k is only confirmed with the correlation at k + 1
+        let n = cmp::min(i.len().saturating_sub(l), o.len());
+
+        if n > 0 {
+            let mut cur = self.magnitude(&i[0..l]);
+            for k in 0..n {
+                let next = self.magnitude(&i[k + 1..k + 1 + l]);
+                if self.holdoff > 0 {
+                    self.holdoff -= 1;
+                } else if cur >= self.threshold && cur >= self.prev && cur > next {
+                    for tag in self.estimate(&i[k..k + l], self.prev, cur, next) {
+                        sio.output(0).add_tag(k, tag);
+                    }
+                    self.holdoff = l;
+                }
+                self.prev = cur;
+                cur = next;
+            }
+            o[..n].copy_from_slice(&i[..n]);
+        }
+
+        // flush the tail, which is too short to contain a preamble
+        let mut m = n;
+        if sio.input(0).finished() && n == i.len().saturating_sub(l) {
+            m = cmp::min(i.len(), o.len());
+            o[n..m].copy_from_slice(&i[n..m]);
+            if m == i.len() {
+                io.finished = true;
+            }
+        }
+
+        sio.input(0).consume(m);
+        sio.output(0).produce(m);
+
+        Ok(())
+    }
+}
+
+/// Detects a known preamble in a complex stream.
+///
+/// Computes the correlation with the preamble, normalized by the energy of the
+/// preamble and the input. At correlation peaks above the threshold, tags are
+/// added to the first sample of the preamble:
+///
+/// - `corr_est`: normalized correlation magnitude (`Tag::NamedF32`)
+/// - `time_est`: fractional timing offset of the peak in samples (`Tag::NamedF32`)
+/// - `phase_est`: phase offset at the start of the preamble in radians (`Tag::NamedF32`)
+/// - `freq_est`: frequency offset in radians per sample (`Tag::NamedF32`)
+///
+/// The frequency offset is estimated from the phase difference between the
+/// two halves of the preamble, which limits its range to `2π / len`.
+/// Samples are passed through unmodified.
+///
+/// # Inputs
+///
+/// `in`: Samples
+///
+/// # Outputs
+///
+/// `out`: Tagged samples
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::PreambleCorrelatorBuilder;
+/// use futuresdr::num_complex::Complex32;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let preamble = vec![Complex32::new(1.0, 0.0), Complex32::new(-1.0, 0.0), Complex32::new(1.0, 0.0), Complex32::new(1.0, 0.0)];
+/// let corr = fg.add_block(PreambleCorrelatorBuilder::new(preamble).threshold(0.9).build());
+/// ```
+pub struct PreambleCorrelatorBuilder {
+    preamble: Vec<Complex32>,
+    threshold: f32,
+}
+
+impl PreambleCorrelatorBuilder {
+    pub fn new(preamble: Vec<Complex32>) -> PreambleCorrelatorBuilder {
+        PreambleCorrelatorBuilder {
+            preamble,
+            threshold: 0.8,
+        }
+    }
+
+    /// Detection threshold for the normalized correlation in `[0, 1]`.
+    #[must_use]
+    pub fn threshold(mut self, threshold: f32) -> PreambleCorrelatorBuilder {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        PreambleCorrelator::new(self.preamble, self.threshold)
+    }
+}
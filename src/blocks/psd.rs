@@ -0,0 +1,358 @@
+use futures::FutureExt;
+use rustfft::num_complex::Complex;
+use rustfft::{self, FftPlanner};
+use std::sync::Arc;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+use futuredsp::windows;
+
+/// How the [Psd] block combines the spectra of consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PsdAveraging {
+    /// Output the spectrum of the last frame.
+    None,
+    /// Exponential moving average with the given weight of the new frame.
+    Exponential(f32),
+    /// Mean of all frames since the last output (Welch's method).
+    Linear,
+    /// Maximum of each bin since the start or the last reset.
+    MaxHold,
+    /// Minimum of each bin since the start or the last reset.
+    MinHold,
+}
+
+pub struct Psd {
+    plan: Arc<dyn rustfft::Fft<f32>>,
+    scratch: Vec<Complex<f32>>,
+    buf: Vec<Complex<f32>>,
+    window: Vec<f32>,
+    /// Samples between the starts of consecutive frames.
+    hop: usize,
+    averaging: PsdAveraging,
+    decimation: usize,
+    width: usize,
+    db: bool,
+    fft_shift: bool,
+    /// Combined power spectrum of the frames.
+    acc: Vec<f32>,
+    /// Frames combined in `acc` since the last reset or output.
+    frames: usize,
+    /// Frames since the last output.
+    count: usize,
+}
+
+impl Psd {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        fft_size: usize,
+        window: Vec<f32>,
+        overlap: usize,
+        averaging: PsdAveraging,
+        decimation: usize,
+        width: usize,
+        db: bool,
+        fft_shift: bool,
+    ) -> Block {
+        assert!(fft_size > 0, "fft_size must be positive");
+        assert_eq!(window.len(), fft_size, "window must have fft_size taps");
+        assert!(overlap < fft_size, "overlap must be smaller than fft_size");
+        assert!(decimation > 0, "decimation must be positive");
+        assert!(
+            width > 0 && fft_size.is_multiple_of(width),
+            "width must divide fft_size"
+        );
+        if let PsdAveraging::Exponential(alpha) = averaging {
+            assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        }
+
+        let mut planner = FftPlanner::<f32>::new();
+        let plan = planner.plan_fft_forward(fft_size);
+        let scratch = vec![Complex::new(0.0, 0.0); plan.get_inplace_scratch_len()];
+
+        // normalize to the coherent gain, so that tones keep their power
+        let norm = window.iter().sum::<f32>();
+        let window = window.iter().map(|w| w / norm).collect();
+
+        Block::new(
+            BlockMetaBuilder::new("Psd").build(),
+            StreamIoBuilder::new()
+                .add_min_items_input::<Complex<f32>>("in", fft_size)
+                .add_vector_output::<f32>("out", width)
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "reset",
+                    |block: &mut Psd, _mio: &mut MessageIo<Psd>, _meta: &mut BlockMeta, _p: Pmt| {
+                        async move {
+                            block.frames = 0;
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            Psd {
+                plan,
+                scratch,
+                buf: vec![Complex::new(0.0, 0.0); fft_size],
+                window,
+                hop: fft_size - overlap,
+                averaging,
+                decimation,
+                width,
+                db,
+                fft_shift,
+                acc: vec![0.0; fft_size],
+                frames: 0,
+                count: 0,
+            },
+        )
+    }
+
+    /// Adds the power spectrum in `buf` to the accumulated spectrum.
+    fn accumulate(&mut self) {
+        let first = self.frames == 0;
+        for (a, x) in self.acc.iter_mut().zip(self.buf.iter()) {
+            let mut p = x.norm_sqr();
+            if !p.is_finite() {
+                p = 0.0;
+            }
+            *a = match self.averaging {
+                _ if first => p,
+                PsdAveraging::None => p,
+                PsdAveraging::Exponential(alpha) => (1.0 - alpha) * *a + alpha * p,
+                PsdAveraging::Linear => *a + p,
+                PsdAveraging::MaxHold => a.max(p),
+                PsdAveraging::MinHold => a.min(p),
+            };
+        }
+        self.frames += 1;
+    }
+
+    /// Writes the accumulated spectrum to `o`.
+    fn emit(&mut self, o: &mut [f32]) {
+        let n = self.acc.len();
+        let scale = if self.averaging == PsdAveraging::Linear {
+            1.0 / self.frames as f32
+        } else {
+            1.0
+        };
+        let group = n / self.width;
+        let offset = if self.fft_shift { n / 2 } else { 0 };
+
+        for (i, y) in o.iter_mut().enumerate() {
+            let mut v = 0.0f32;
+            for k in i * group..(i + 1) * group {
+                v = v.max(self.acc[(k + offset) % n]);
+            }
+            v *= scale;
+            *y = if self.db {
+                10.0 * v.max(1e-20).log10()
+            } else {
+                v
+            };
+        }
+
+        if self.averaging == PsdAveraging::Linear {
+            self.frames = 0;
+        }
+    }
+}
+
+#[async_trait]
+impl Kernel for Psd {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<f32>();
+
+        let fft_size = self.buf.len();
+        let mut consumed = 0;
+        let mut produced = 0;
+
+        while consumed + fft_size <= i.len() {
+            let output = self.count + 1 == self.decimation;
+            if output && produced + self.width > o.len() {
+                break;
+            }
+
+            for ((b, x), w) in self
+                .buf
+                .iter_mut()
+                .zip(i[consumed..].iter())
+                .zip(self.window.iter())
+            {
+                *b = x * w;
+            }
+            self.plan
+                .process_with_scratch(&mut self.buf, &mut self.scratch);
+            self.accumulate();
+            consumed += self.hop;
+            self.count += 1;
+
+            if output {
+                self.emit(&mut o[produced..produced + self.width]);
+                self.count = 0;
+                produced += self.width;
+            }
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        if sio.input(0).finished() && consumed + fft_size > i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Estimates the power spectral density.
+///
+/// Splits the input into windowed, overlapping frames, computes their power
+/// spectra, and combines them according to [PsdAveraging]. One spectrum is
+/// output every `decimation` frames. The spectrum is normalized to the
+/// coherent gain of the window, i.e., a complex tone with unit amplitude has a
+/// peak of 0 dB. By default, the output is centered at DC and in dB.
+///
+/// In waterfall mode, each spectrum is reduced to frames of `width` bins by
+/// taking the maximum of adjacent bins, so that narrow peaks remain visible.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// **Message**: `reset`: restart the averaging, including max and min hold;
+/// accepts any [`Pmt`] and returns [`Pmt::Null`]
+///
+/// # Outputs
+///
+/// `out`: Spectra, vector port of `width` (by default `fft_size`) `f32` bins
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::{PsdAveraging, PsdBuilder};
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let psd = fg.add_block(
+///     PsdBuilder::new(2048)
+///         .averaging(PsdAveraging::Exponential(0.1))
+///         .decimation(10)
+///         .build(),
+/// );
+/// let waterfall = fg.add_block(
+///     PsdBuilder::new(4096)
+///         .averaging(PsdAveraging::MaxHold)
+///         .waterfall(512)
+///         .build(),
+/// );
+/// ```
+pub struct PsdBuilder {
+    fft_size: usize,
+    window: Vec<f32>,
+    overlap: usize,
+    averaging: PsdAveraging,
+    decimation: usize,
+    width: usize,
+    db: bool,
+    fft_shift: bool,
+}
+
+impl PsdBuilder {
+    /// Create a PSD estimator with frames of `fft_size` samples, a Hann
+    /// window, and 50% overlap.
+    pub fn new(fft_size: usize) -> PsdBuilder {
+        PsdBuilder {
+            fft_size,
+            window: windows::hann(fft_size, true)
+                .into_iter()
+                .map(|w| w as f32)
+                .collect(),
+            overlap: fft_size / 2,
+            averaging: PsdAveraging::None,
+            decimation: 1,
+            width: fft_size,
+            db: true,
+            fft_shift: true,
+        }
+    }
+
+    /// Window applied to each frame; must have `fft_size` taps.
+    #[must_use]
+    pub fn window(mut self, window: Vec<f32>) -> PsdBuilder {
+        self.window = window;
+        self
+    }
+
+    /// Number of samples shared by consecutive frames.
+    #[must_use]
+    pub fn overlap(mut self, overlap: usize) -> PsdBuilder {
+        self.overlap = overlap;
+        self
+    }
+
+    #[must_use]
+    pub fn averaging(mut self, averaging: PsdAveraging) -> PsdBuilder {
+        self.averaging = averaging;
+        self
+    }
+
+    /// Output one spectrum every `decimation` frames.
+    #[must_use]
+    pub fn decimation(mut self, decimation: usize) -> PsdBuilder {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Emit frames of `width` bins; `width` must divide `fft_size`.
+    #[must_use]
+    pub fn waterfall(mut self, width: usize) -> PsdBuilder {
+        self.width = width;
+        self
+    }
+
+    /// Output in dB (default) or linear power.
+    #[must_use]
+    pub fn db(mut self, db: bool) -> PsdBuilder {
+        self.db = db;
+        self
+    }
+
+    /// Center DC in the output (default) or keep the FFT order.
+    #[must_use]
+    pub fn fft_shift(mut self, fft_shift: bool) -> PsdBuilder {
+        self.fft_shift = fft_shift;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        Psd::new(
+            self.fft_size,
+            self.window,
+            self.overlap,
+            self.averaging,
+            self.decimation,
+            self.width,
+            self.db,
+            self.fft_shift,
+        )
+    }
+}
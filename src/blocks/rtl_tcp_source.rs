@@ -0,0 +1,427 @@
+use async_io::Async;
+use futures::io::AsyncReadExt;
+use futures::io::AsyncWriteExt;
+use futures::FutureExt;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use crate::anyhow::{bail, Context, Result};
+use crate::blocks::file_source::convert_cu8;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+const SET_FREQUENCY: u8 = 0x01;
+const SET_SAMPLE_RATE: u8 = 0x02;
+const SET_GAIN_MODE: u8 = 0x03;
+const SET_GAIN: u8 = 0x04;
+const SET_FREQ_CORRECTION: u8 = 0x05;
+
+/// Tuner of the dongle, as reported in the header of the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtlTuner {
+    Unknown,
+    E4000,
+    Fc0012,
+    Fc0013,
+    Fc2580,
+    R820t,
+    R828d,
+}
+
+impl From<u32> for RtlTuner {
+    fn from(t: u32) -> RtlTuner {
+        match t {
+            1 => RtlTuner::E4000,
+            2 => RtlTuner::Fc0012,
+            3 => RtlTuner::Fc0013,
+            4 => RtlTuner::Fc2580,
+            5 => RtlTuner::R820t,
+            6 => RtlTuner::R828d,
+            _ => RtlTuner::Unknown,
+        }
+    }
+}
+
+/// Parses a numeric [`Pmt`].
+fn value(p: &Pmt) -> Option<f64> {
+    match p {
+        Pmt::U32(v) => Some(*v as f64),
+        Pmt::U64(v) => Some(*v as f64),
+        Pmt::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+pub struct RtlTcpSource {
+    remote: String,
+    conn: Option<Arc<Async<TcpStream>>>,
+    frequency: f64,
+    sample_rate: f64,
+    gain: Option<f64>,
+    ppm: i32,
+    tuner: RtlTuner,
+    gain_count: u32,
+    buf: Vec<u8>,
+    /// Odd byte of an incomplete sample.
+    partial: Option<u8>,
+    /// Tags for the next output sample.
+    tags: Vec<Tag>,
+}
+
+impl RtlTcpSource {
+    fn with_config(
+        remote: String,
+        frequency: f64,
+        sample_rate: f64,
+        gain: Option<f64>,
+        ppm: i32,
+    ) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("RtlTcpSource").build(),
+            StreamIoBuilder::new()
+                .add_typed_output::<Complex32>("out")
+                .build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "freq",
+                    |block: &mut RtlTcpSource,
+                     _mio: &mut MessageIo<RtlTcpSource>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Some(f) = value(&p) {
+                                block.set_frequency(f).await?;
+                            } else if p != Pmt::Null {
+                                warn!("RtlTcpSource/freq Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Double(block.frequency))
+                        }
+                        .boxed()
+                    },
+                )
+                .add_input(
+                    "sample_rate",
+                    |block: &mut RtlTcpSource,
+                     _mio: &mut MessageIo<RtlTcpSource>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Some(r) = value(&p) {
+                                block.set_sample_rate(r).await?;
+                            } else if p != Pmt::Null {
+                                warn!(
+                                    "RtlTcpSource/sample_rate Handler received wrong PMT {:?}",
+                                    &p
+                                );
+                            }
+                            Ok(Pmt::Double(block.sample_rate))
+                        }
+                        .boxed()
+                    },
+                )
+                .add_input(
+                    "gain",
+                    |block: &mut RtlTcpSource,
+                     _mio: &mut MessageIo<RtlTcpSource>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            match (&p, value(&p)) {
+                                (_, Some(g)) => block.set_gain(Some(g)).await?,
+                                (Pmt::String(s), _) if s == "auto" => block.set_gain(None).await?,
+                                (Pmt::Null, _) => {}
+                                _ => warn!("RtlTcpSource/gain Handler received wrong PMT {:?}", &p),
+                            }
+                            Ok(block
+                                .gain
+                                .map_or(Pmt::String("auto".to_string()), Pmt::Double))
+                        }
+                        .boxed()
+                    },
+                )
+                .add_input(
+                    "tuner",
+                    |block: &mut RtlTcpSource,
+                     _mio: &mut MessageIo<RtlTcpSource>,
+                     _meta: &mut BlockMeta,
+                     _p: Pmt| {
+                        async move { Ok(Pmt::String(format!("{:?}", block.tuner))) }.boxed()
+                    },
+                )
+                .add_input(
+                    "gain_count",
+                    |block: &mut RtlTcpSource,
+                     _mio: &mut MessageIo<RtlTcpSource>,
+                     _meta: &mut BlockMeta,
+                     _p: Pmt| {
+                        async move { Ok(Pmt::U32(block.gain_count)) }.boxed()
+                    },
+                )
+                .build(),
+            RtlTcpSource {
+                remote,
+                conn: None,
+                frequency,
+                sample_rate,
+                gain,
+                ppm,
+                tuner: RtlTuner::Unknown,
+                gain_count: 0,
+                buf: Vec::new(),
+                partial: None,
+                tags: Vec::new(),
+            },
+        )
+    }
+
+    /// Sends a command with its big-endian parameter to the server.
+    async fn command(&mut self, cmd: u8, param: u32) -> Result<()> {
+        let conn = self.conn.as_ref().context("not connected")?;
+        let mut c = [cmd, 0, 0, 0, 0];
+        c[1..].copy_from_slice(&param.to_be_bytes());
+        (&**conn)
+            .write_all(&c)
+            .await
+            .with_context(|| format!("RtlTcpSource: sending command to {} failed", self.remote))
+    }
+
+    async fn set_frequency(&mut self, freq: f64) -> Result<()> {
+        self.command(SET_FREQUENCY, freq as u32).await?;
+        self.frequency = freq;
+        self.tags.push(Tag::NamedF64("freq".to_string(), freq));
+        Ok(())
+    }
+
+    async fn set_sample_rate(&mut self, rate: f64) -> Result<()> {
+        self.command(SET_SAMPLE_RATE, rate as u32).await?;
+        self.sample_rate = rate;
+        self.tags
+            .push(Tag::NamedF64("sample_rate".to_string(), rate));
+        Ok(())
+    }
+
+    /// Sets a manual gain in dB or, with `None`, automatic gain.
+    async fn set_gain(&mut self, gain: Option<f64>) -> Result<()> {
+        if let Some(g) = gain {
+            self.command(SET_GAIN_MODE, 1).await?;
+            self.command(SET_GAIN, (g * 10.0).round() as u32).await?;
+        } else {
+            self.command(SET_GAIN_MODE, 0).await?;
+        }
+        self.gain = gain;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Kernel for RtlTcpSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let out = sio.output(0).slice::<Complex32>();
+        if out.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.conn.as_ref().context("not connected")?.clone();
+        let p = usize::from(self.partial.is_some());
+        self.buf.resize(out.len() * 2, 0);
+        if let Some(b) = self.partial.take() {
+            self.buf[0] = b;
+        }
+
+        match conn.get_ref().read(&mut self.buf[p..]) {
+            Ok(0) => {
+                debug!("RtlTcpSource: server closed the connection");
+                io.finished = true;
+            }
+            Ok(n) => {
+                let items = (p + n) / 2;
+                convert_cu8(&self.buf[..items * 2], &mut out[..items]);
+                if (p + n) % 2 == 1 {
+                    self.partial = Some(self.buf[p + n - 1]);
+                }
+                if items > 0 {
+                    for t in std::mem::take(&mut self.tags) {
+                        sio.output(0).add_tag(0, t);
+                    }
+                }
+                sio.output(0).produce(items);
+                io.call_again = true;
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                if p == 1 {
+                    self.partial = Some(self.buf[0]);
+                }
+                io.block_on(async move {
+                    let _ = conn.readable().await;
+                });
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("RtlTcpSource: reading from {}", self.remote))
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let addr = async_net::resolve(self.remote.as_str())
+            .await
+            .ok()
+            .and_then(|a| a.into_iter().next())
+            .with_context(|| format!("RtlTcpSource: cannot resolve {:?}", self.remote))?;
+        let conn = Async::<TcpStream>::connect(addr)
+            .await
+            .with_context(|| format!("RtlTcpSource: cannot connect to {}", addr))?;
+
+        let mut header = [0u8; 12];
+        (&conn)
+            .read_exact(&mut header)
+            .await
+            .with_context(|| format!("RtlTcpSource: no header from {}", addr))?;
+        if &header[..4] != b"RTL0" {
+            bail!("RtlTcpSource: {} is not an rtl_tcp server", addr);
+        }
+        self.tuner = u32::from_be_bytes([header[4], header[5], header[6], header[7]]).into();
+        self.gain_count = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+        info!(
+            "RtlTcpSource: connected to {}, tuner {:?} with {} gains",
+            addr, self.tuner, self.gain_count
+        );
+        self.conn = Some(Arc::new(conn));
+
+        self.set_sample_rate(self.sample_rate).await?;
+        self.set_frequency(self.frequency).await?;
+        self.set_gain(self.gain).await?;
+        if self.ppm != 0 {
+            self.command(SET_FREQ_CORRECTION, self.ppm as u32).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives samples from an `rtl_tcp` server.
+///
+/// Connects to a server, e.g., `rtl_tcp -a 0.0.0.0`, checks its header, and
+/// configures the frequency, sample rate, and gain. The unsigned 8-bit I/Q
+/// samples of the dongle are converted to [`Complex32`] in `[-1, 1]`. The
+/// sample rate and frequency are tagged as `Tag::NamedF64("sample_rate", _)`
+/// and `Tag::NamedF64("freq", _)` on the first sample and after they change.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// **Message**: `freq`: retune; accepts a [`Pmt::U32`], [`Pmt::U64`], or
+/// [`Pmt::Double`] value in Hz and returns the current frequency
+///
+/// **Message**: `sample_rate`: set the sample rate; accepts a numeric value in
+/// Hz, like `freq`, and returns the current sample rate
+///
+/// **Message**: `gain`: set the gain; accepts a numeric value in dB or the
+/// [`Pmt::String`] `auto` for automatic gain control
+///
+/// **Message**: `tuner`: returns the tuner of the dongle, e.g.,
+/// `Pmt::String("R820t")`, see [`RtlTuner`]
+///
+/// **Message**: `gain_count`: returns the number of gain steps of the tuner as
+/// [`Pmt::U32`]
+///
+/// # Outputs
+///
+/// `out`: Samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::RtlTcpSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     RtlTcpSourceBuilder::new("127.0.0.1:1234")
+///         .frequency(100e6)
+///         .sample_rate(2.048e6)
+///         .gain(30.0)
+///         .build(),
+/// );
+/// ```
+pub struct RtlTcpSourceBuilder {
+    remote: String,
+    frequency: f64,
+    sample_rate: f64,
+    gain: Option<f64>,
+    ppm: i32,
+}
+
+impl RtlTcpSourceBuilder {
+    /// Connect to a server, e.g., `127.0.0.1:1234`.
+    pub fn new<S: Into<String>>(remote: S) -> RtlTcpSourceBuilder {
+        RtlTcpSourceBuilder {
+            remote: remote.into(),
+            frequency: 100e6,
+            sample_rate: 2.048e6,
+            gain: None,
+            ppm: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn frequency(mut self, freq: f64) -> RtlTcpSourceBuilder {
+        self.frequency = freq;
+        self
+    }
+
+    #[must_use]
+    pub fn sample_rate(mut self, rate: f64) -> RtlTcpSourceBuilder {
+        self.sample_rate = rate;
+        self
+    }
+
+    /// Manual gain in dB, instead of automatic gain control.
+    #[must_use]
+    pub fn gain(mut self, gain: f64) -> RtlTcpSourceBuilder {
+        self.gain = Some(gain);
+        self
+    }
+
+    /// Frequency correction in ppm.
+    #[must_use]
+    pub fn ppm(mut self, ppm: i32) -> RtlTcpSourceBuilder {
+        self.ppm = ppm;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        RtlTcpSource::with_config(
+            self.remote,
+            self.frequency,
+            self.sample_rate,
+            self.gain,
+            self.ppm,
+        )
+    }
+}
@@ -0,0 +1,188 @@
+//! [SigMF](https://sigmf.org) recordings.
+//!
+//! A recording is a pair of a `.sigmf-data` file with the raw samples and a
+//! `.sigmf-meta` JSON file with the datatype, sample rate, capture segments,
+//! and annotations. The sample rate and center frequency are passed as stream
+//! tags `sample_rate` and `freq`, using [`Tag::NamedF64`] (the sink also
+//! accepts [`Tag::NamedF32`] and [`Tag::NamedUsize`]).
+use num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::runtime::Tag;
+
+mod sink;
+pub use sink::{SigMfSink, SigMfSinkBuilder};
+
+mod source;
+pub use source::{SigMfSource, SigMfSourceBuilder};
+
+/// SigMF version written to the metadata.
+pub const VERSION: &str = "1.0.0";
+
+/// Stream types that can be stored in a SigMF recording.
+///
+/// Samples are stored in the in-memory format of the machine, so the
+/// datatype has the native endianness.
+pub trait Datatype: Send + 'static {
+    /// Datatype string, e.g., `cf32_le`.
+    fn datatype() -> String;
+}
+
+macro_rules! datatype {
+    ($t:ty, $s:literal, $endian:literal) => {
+        impl Datatype for $t {
+            fn datatype() -> String {
+                if $endian {
+                    if cfg!(target_endian = "little") {
+                        format!("{}_le", $s)
+                    } else {
+                        format!("{}_be", $s)
+                    }
+                } else {
+                    $s.to_string()
+                }
+            }
+        }
+    };
+}
+
+datatype!(Complex<f64>, "cf64", true);
+datatype!(Complex<f32>, "cf32", true);
+datatype!(Complex<i32>, "ci32", true);
+datatype!(Complex<i16>, "ci16", true);
+datatype!(Complex<i8>, "ci8", false);
+datatype!(Complex<u32>, "cu32", true);
+datatype!(Complex<u16>, "cu16", true);
+datatype!(Complex<u8>, "cu8", false);
+datatype!(f64, "rf64", true);
+datatype!(f32, "rf32", true);
+datatype!(i32, "ri32", true);
+datatype!(i16, "ri16", true);
+datatype!(i8, "ri8", false);
+datatype!(u32, "ru32", true);
+datatype!(u16, "ru16", true);
+datatype!(u8, "ru8", false);
+
+/// Contents of a `.sigmf-meta` file.
+///
+/// Only the core fields are supported, other fields are ignored when reading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Meta {
+    pub global: Global,
+    #[serde(default)]
+    pub captures: Vec<Capture>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Global {
+    #[serde(rename = "core:datatype")]
+    pub datatype: String,
+    #[serde(rename = "core:version")]
+    pub version: String,
+    #[serde(
+        rename = "core:sample_rate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_rate: Option<f64>,
+    #[serde(
+        rename = "core:description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(
+        rename = "core:author",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub author: Option<String>,
+    #[serde(rename = "core:hw", default, skip_serializing_if = "Option::is_none")]
+    pub hw: Option<String>,
+}
+
+/// Capture segment, starting at a sample with a new center frequency.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    #[serde(rename = "core:sample_start")]
+    pub sample_start: u64,
+    #[serde(
+        rename = "core:frequency",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub frequency: Option<f64>,
+    #[serde(
+        rename = "core:datetime",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub datetime: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(rename = "core:sample_start")]
+    pub sample_start: u64,
+    #[serde(
+        rename = "core:sample_count",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sample_count: Option<u64>,
+    #[serde(
+        rename = "core:freq_lower_edge",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub freq_lower_edge: Option<f64>,
+    #[serde(
+        rename = "core:freq_upper_edge",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub freq_upper_edge: Option<f64>,
+    #[serde(
+        rename = "core:label",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub label: Option<String>,
+    #[serde(
+        rename = "core:comment",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub comment: Option<String>,
+}
+
+/// Data and metadata file names of a recording.
+///
+/// The path can be given with or without the `.sigmf-data`, `.sigmf-meta`, or
+/// `.sigmf` extension.
+pub fn file_names<P: AsRef<Path>>(path: P) -> (PathBuf, PathBuf) {
+    let path = path.as_ref();
+    let base = match path.extension().and_then(|e| e.to_str()) {
+        Some("sigmf-data" | "sigmf-meta" | "sigmf") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+    let mut data = base.clone().into_os_string();
+    data.push(".sigmf-data");
+    let mut meta = base.into_os_string();
+    meta.push(".sigmf-meta");
+    (data.into(), meta.into())
+}
+
+/// Value of a numeric tag with the given name.
+fn tag_value(tag: &Tag, name: &str) -> Option<f64> {
+    match tag {
+        Tag::NamedF64(k, v) if k == name => Some(*v),
+        Tag::NamedF32(k, v) if k == name => Some(*v as f64),
+        Tag::NamedUsize(k, v) if k == name => Some(*v as f64),
+        _ => None,
+    }
+}
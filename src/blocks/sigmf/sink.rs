@@ -0,0 +1,293 @@
+use futures::io::AsyncWriteExt;
+use futures::FutureExt;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::anyhow::{Context, Result};
+use crate::blocks::sigmf::file_names;
+use crate::blocks::sigmf::tag_value;
+use crate::blocks::sigmf::Annotation;
+use crate::blocks::sigmf::Capture;
+use crate::blocks::sigmf::Datatype;
+use crate::blocks::sigmf::Global;
+use crate::blocks::sigmf::Meta;
+use crate::blocks::sigmf::VERSION;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct SigMfSink<T: Datatype> {
+    data_path: PathBuf,
+    meta_path: PathBuf,
+    file: Option<async_fs::File>,
+    meta: Meta,
+    /// Number of samples written.
+    items: u64,
+    _type: PhantomData<T>,
+}
+
+impl<T: Datatype> SigMfSink<T> {
+    pub fn new<P: AsRef<Path>>(path: P, meta: Meta) -> Block {
+        let (data_path, meta_path) = file_names(path);
+        Block::new(
+            BlockMetaBuilder::new("SigMfSink").build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "annotations",
+                    |block: &mut SigMfSink<T>,
+                     _mio: &mut MessageIo<SigMfSink<T>>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            if let Pmt::String(s) = &p {
+                                block.annotate(s);
+                            } else if p != Pmt::Null {
+                                warn!("SigMfSink/annotations Handler received wrong PMT {:?}", &p);
+                            }
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            SigMfSink::<T> {
+                data_path,
+                meta_path,
+                file: None,
+                meta,
+                items: 0,
+                _type: PhantomData,
+            },
+        )
+    }
+
+    /// Adds an annotation, given as JSON object or, otherwise, as comment for
+    /// the current sample.
+    fn annotate(&mut self, s: &str) {
+        let a = serde_json::from_str::<Annotation>(s).unwrap_or_else(|_| Annotation {
+            sample_start: self.items,
+            comment: Some(s.to_string()),
+            ..Annotation::default()
+        });
+        self.meta.annotations.push(a);
+    }
+
+    fn set_sample_rate(&mut self, rate: f64) {
+        match self.meta.global.sample_rate {
+            Some(r) if r != rate => {
+                warn!(
+                    "SigMfSink: sample rate changed from {} to {}, which SigMF does not support",
+                    r, rate
+                );
+            }
+            _ => self.meta.global.sample_rate = Some(rate),
+        }
+    }
+
+    /// Starts a new capture segment, unless it starts at the same sample as
+    /// the previous one.
+    fn set_frequency(&mut self, sample: u64, freq: f64) {
+        match self.meta.captures.last_mut() {
+            Some(c) if c.sample_start == sample => c.frequency = Some(freq),
+            Some(c) if c.frequency == Some(freq) => {}
+            _ => self.meta.captures.push(Capture {
+                sample_start: sample,
+                frequency: Some(freq),
+                ..Capture::default()
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Datatype> Kernel for SigMfSink<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+
+        let item_size = std::mem::size_of::<T>();
+        let items = i.len() / item_size;
+
+        let tags: Vec<_> = sio
+            .input(0)
+            .tags()
+            .iter()
+            .filter(|t| t.index < items)
+            .cloned()
+            .collect();
+        for t in tags {
+            let sample = self.items + t.index as u64;
+            if let Some(rate) = tag_value(&t.tag, "sample_rate") {
+                self.set_sample_rate(rate);
+            }
+            if let Some(freq) = tag_value(&t.tag, "freq") {
+                self.set_frequency(sample, freq);
+            }
+        }
+
+        if items > 0 {
+            self.file
+                .as_mut()
+                .unwrap()
+                .write_all(&i[..items * item_size])
+                .await
+                .with_context(|| format!("SigMfSink: writing to {:?} failed", self.data_path))?;
+            self.items += items as u64;
+        }
+
+        sio.input(0).consume(items);
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let file = async_fs::File::create(&self.data_path)
+            .await
+            .with_context(|| format!("SigMfSink: cannot create {:?}", self.data_path))?;
+        self.file = Some(file);
+        Ok(())
+    }
+
+    async fn deinit(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        self.file.as_mut().unwrap().sync_all().await?;
+
+        self.meta.annotations.sort_by_key(|a| a.sample_start);
+        let json = serde_json::to_string_pretty(&self.meta)?;
+        async_fs::write(&self.meta_path, json)
+            .await
+            .with_context(|| format!("SigMfSink: cannot write {:?}", self.meta_path))?;
+        Ok(())
+    }
+}
+
+/// Records samples to a SigMF recording.
+///
+/// Writes the samples to `<path>.sigmf-data` and, when the flowgraph
+/// terminates, the metadata to `<path>.sigmf-meta`. The datatype is inferred
+/// from the stream type, e.g., `cf32_le` for `Complex<f32>`. The sample rate
+/// and center frequency are set in the builder or through `sample_rate` and
+/// `freq` tags. Each change of the frequency starts a new capture segment.
+///
+/// # Inputs
+///
+/// `in`: Samples
+///
+/// **Message**: `annotations`: add an annotation; accepts a [`Pmt::String`]
+/// with a SigMF annotation JSON object, e.g.,
+/// `{"core:sample_start": 1000, "core:sample_count": 200, "core:label": "burst"}`.
+/// Other strings are added as comment for the current sample.
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::sigmf::SigMfSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(
+///     SigMfSinkBuilder::<Complex<f32>>::new("recording")
+///         .sample_rate(1e6)
+///         .frequency(100e6)
+///         .description("FM broadcast")
+///         .build(),
+/// );
+/// ```
+pub struct SigMfSinkBuilder<T: Datatype> {
+    path: PathBuf,
+    global: Global,
+    frequency: Option<f64>,
+    _type: PhantomData<T>,
+}
+
+impl<T: Datatype> SigMfSinkBuilder<T> {
+    pub fn new<P: AsRef<Path>>(path: P) -> SigMfSinkBuilder<T> {
+        SigMfSinkBuilder {
+            path: path.as_ref().to_path_buf(),
+            global: Global {
+                datatype: T::datatype(),
+                version: VERSION.to_string(),
+                ..Global::default()
+            },
+            frequency: None,
+            _type: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn sample_rate(mut self, rate: f64) -> SigMfSinkBuilder<T> {
+        self.global.sample_rate = Some(rate);
+        self
+    }
+
+    /// Center frequency of the first capture segment.
+    #[must_use]
+    pub fn frequency(mut self, freq: f64) -> SigMfSinkBuilder<T> {
+        self.frequency = Some(freq);
+        self
+    }
+
+    #[must_use]
+    pub fn description<S: Into<String>>(mut self, description: S) -> SigMfSinkBuilder<T> {
+        self.global.description = Some(description.into());
+        self
+    }
+
+    #[must_use]
+    pub fn author<S: Into<String>>(mut self, author: S) -> SigMfSinkBuilder<T> {
+        self.global.author = Some(author.into());
+        self
+    }
+
+    /// Description of the hardware used for the recording.
+    #[must_use]
+    pub fn hw<S: Into<String>>(mut self, hw: S) -> SigMfSinkBuilder<T> {
+        self.global.hw = Some(hw.into());
+        self
+    }
+
+    pub fn build(self) -> Block {
+        let meta = Meta {
+            global: self.global,
+            captures: vec![Capture {
+                sample_start: 0,
+                frequency: self.frequency,
+                ..Capture::default()
+            }],
+            annotations: Vec::new(),
+        };
+        SigMfSink::<T>::new(self.path, meta)
+    }
+}
@@ -0,0 +1,195 @@
+use futures::AsyncReadExt;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::anyhow::{bail, Context, Result};
+use crate::blocks::sigmf::file_names;
+use crate::blocks::sigmf::Datatype;
+use crate::blocks::sigmf::Meta;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+pub struct SigMfSource<T: Datatype> {
+    data_path: PathBuf,
+    meta_path: PathBuf,
+    file: Option<async_fs::File>,
+    meta: Meta,
+    /// Number of samples read.
+    items: u64,
+    /// Next capture and annotation to emit.
+    capture: usize,
+    annotation: usize,
+    _type: PhantomData<T>,
+}
+
+impl<T: Datatype> SigMfSource<T> {
+    pub fn new<P: AsRef<Path>>(path: P) -> Block {
+        let (data_path, meta_path) = file_names(path);
+        Block::new(
+            BlockMetaBuilder::new("SigMfSource").build(),
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
+            MessageIoBuilder::new().add_output("annotations").build(),
+            SigMfSource::<T> {
+                data_path,
+                meta_path,
+                file: None,
+                meta: Meta::default(),
+                items: 0,
+                capture: 0,
+                annotation: 0,
+                _type: PhantomData,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<T: Datatype> Kernel for SigMfSource<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let out = sio.output(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+
+        let mut i = 0;
+        while i < out.len() {
+            match self.file.as_mut().unwrap().read(&mut out[i..]).await {
+                Ok(0) => {
+                    io.finished = true;
+                    break;
+                }
+                Ok(n) => i += n,
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("SigMfSource: reading {:?}", self.data_path))
+                }
+            }
+        }
+        let n = i / item_size;
+
+        if self.items == 0 && n > 0 {
+            if let Some(rate) = self.meta.global.sample_rate {
+                sio.output(0)
+                    .add_tag(0, Tag::NamedF64("sample_rate".to_string(), rate));
+            }
+        }
+
+        let end = self.items + n as u64;
+        while let Some(c) = self.meta.captures.get(self.capture) {
+            if c.sample_start >= end {
+                break;
+            }
+            if let Some(freq) = c.frequency {
+                let index = c.sample_start.saturating_sub(self.items) as usize;
+                sio.output(0)
+                    .add_tag(index, Tag::NamedF64("freq".to_string(), freq));
+            }
+            self.capture += 1;
+        }
+
+        while let Some(a) = self.meta.annotations.get(self.annotation) {
+            if a.sample_start >= end {
+                break;
+            }
+            mio.post(0, Pmt::String(serde_json::to_string(a)?)).await;
+            self.annotation += 1;
+        }
+
+        self.items = end;
+        sio.output(0).produce(n);
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let json = async_fs::read_to_string(&self.meta_path)
+            .await
+            .with_context(|| format!("SigMfSource: cannot read {:?}", self.meta_path))?;
+        self.meta = serde_json::from_str(&json)
+            .with_context(|| format!("SigMfSource: invalid metadata in {:?}", self.meta_path))?;
+
+        if self.meta.global.datatype != T::datatype() {
+            bail!(
+                "SigMfSource: recording has datatype {}, but the block outputs {}",
+                self.meta.global.datatype,
+                T::datatype()
+            );
+        }
+
+        self.meta.captures.sort_by_key(|c| c.sample_start);
+        self.meta.annotations.sort_by_key(|a| a.sample_start);
+
+        let file = async_fs::File::open(&self.data_path)
+            .await
+            .with_context(|| format!("SigMfSource: cannot open {:?}", self.data_path))?;
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+/// Plays back a SigMF recording, then stops.
+///
+/// Reads `<path>.sigmf-meta` and the samples from `<path>.sigmf-data`. The
+/// datatype of the recording has to match the stream type, e.g., `cf32_le`
+/// for `Complex<f32>`. The sample rate is tagged on the first sample as
+/// `Tag::NamedF64("sample_rate", rate)` and the center frequency of each
+/// capture segment on its first sample as `Tag::NamedF64("freq", freq)`.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Samples
+///
+/// **Message**: `annotations`: the annotations of the recording as
+/// [`Pmt::String`] with the SigMF JSON object, sent when the first annotated
+/// sample is output
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::sigmf::SigMfSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(SigMfSourceBuilder::<Complex<f32>>::new("recording").build());
+/// ```
+pub struct SigMfSourceBuilder<T: Datatype> {
+    path: PathBuf,
+    _type: PhantomData<T>,
+}
+
+impl<T: Datatype> SigMfSourceBuilder<T> {
+    pub fn new<P: AsRef<Path>>(path: P) -> SigMfSourceBuilder<T> {
+        SigMfSourceBuilder {
+            path: path.as_ref().to_path_buf(),
+            _type: PhantomData,
+        }
+    }
+
+    pub fn build(self) -> Block {
+        SigMfSource::<T>::new(self.path)
+    }
+}
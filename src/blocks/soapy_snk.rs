@@ -1,7 +1,6 @@
 use futures::FutureExt;
 use soapysdr::Direction::Tx;
 use std::cmp;
-use std::mem;
 
 use crate::anyhow::{Context, Result};
 use crate::num_complex::Complex;
@@ -47,7 +46,7 @@ impl SoapySink {
         Block::new(
             BlockMetaBuilder::new("SoapySink").blocking().build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_typed_input::<Complex<f32>>("in")
                 .build(),
             MessageIoBuilder::new()
                 .add_input(
@@ -1,7 +1,6 @@
 use futures::FutureExt;
 use soapysdr::Direction::Rx;
 use std::cmp;
-use std::mem;
 
 use crate::anyhow::{Context, Result};
 use crate::num_complex::Complex;
@@ -49,7 +48,7 @@ impl SoapySource {
         Block::new(
             BlockMetaBuilder::new("SoapySource").blocking().build(),
             StreamIoBuilder::new()
-                .add_output("out", mem::size_of::<Complex<f32>>())
+                .add_typed_output::<Complex<f32>>("out")
                 .build(),
             MessageIoBuilder::new()
                 .add_input(
@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -45,9 +43,7 @@ where
     pub fn new(f: impl FnMut() -> A + Send + 'static) -> Block {
         Block::new(
             BlockMetaBuilder::new("Source").build(),
-            StreamIoBuilder::new()
-                .add_output("out", mem::size_of::<A>())
-                .build(),
+            StreamIoBuilder::new().add_typed_output::<A>("out").build(),
             MessageIoBuilder::<Source<A>>::new().build(),
             Source { f: Box::new(f) },
         )
@@ -1,5 +1,3 @@
-use std::mem;
-
 use crate::anyhow::Result;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -30,9 +28,9 @@ where
         Block::new(
             BlockMetaBuilder::new("Split").build(),
             StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out0", mem::size_of::<B>())
-                .add_output("out1", mem::size_of::<C>())
+                .add_typed_input::<A>("in")
+                .add_typed_output::<B>("out0")
+                .add_typed_output::<C>("out1")
                 .build(),
             MessageIoBuilder::<Split<A, B, C>>::new().build(),
             Split { f: Box::new(f) },
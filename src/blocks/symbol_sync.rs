@@ -0,0 +1,390 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+/// Sample types supported by [SymbolSync].
+///
+/// The error functions follow the convention that a positive error means the
+/// symbol was sampled too late.
+pub trait SymbolSyncSample: Copy + Send + 'static {
+    fn zero() -> Self;
+    fn mac(self, x: Self, c: f32) -> Self;
+    fn gardner_error(prev: Self, mid: Self, cur: Self) -> f32;
+    fn mm_error(prev: Self, cur: Self) -> f32;
+    fn pfb_error(y: Self, dy: Self) -> f32;
+}
+
+fn slice(x: f32) -> f32 {
+    if x > 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+impl SymbolSyncSample for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+    fn mac(self, x: Self, c: f32) -> Self {
+        self + x * c
+    }
+    fn gardner_error(prev: Self, mid: Self, cur: Self) -> f32 {
+        (cur - prev) * mid
+    }
+    fn mm_error(prev: Self, cur: Self) -> f32 {
+        slice(cur) * prev - slice(prev) * cur
+    }
+    fn pfb_error(y: Self, dy: Self) -> f32 {
+        -y * dy
+    }
+}
+
+impl SymbolSyncSample for Complex32 {
+    fn zero() -> Self {
+        Complex32::new(0.0, 0.0)
+    }
+    fn mac(self, x: Self, c: f32) -> Self {
+        self + x * c
+    }
+    fn gardner_error(prev: Self, mid: Self, cur: Self) -> f32 {
+        ((cur - prev) * mid.conj()).re
+    }
+    fn mm_error(prev: Self, cur: Self) -> f32 {
+        f32::mm_error(prev.re, cur.re) + f32::mm_error(prev.im, cur.im)
+    }
+    fn pfb_error(y: Self, dy: Self) -> f32 {
+        -(y.conj() * dy).re
+    }
+}
+
+/// Timing error detector used by [SymbolSync].
+#[derive(Debug, Clone)]
+pub enum TimingErrorDetector {
+    /// Gardner TED, works on two samples per symbol and is independent of the carrier phase.
+    Gardner,
+    /// Mueller and Müller TED, decision directed, one sample per symbol.
+    MuellerMuller,
+    /// Polyphase filterbank TED with `nfilts` arms. `taps` is the prototype
+    /// (typically matched) filter, designed at `nfilts` times the input sample
+    /// rate. The output is the filtered signal.
+    Polyphase { taps: Vec<f32>, nfilts: usize },
+}
+
+pub struct SymbolSync<T: SymbolSyncSample> {
+    ted: TimingErrorDetector,
+    omega: f32,
+    omega_mid: f32,
+    omega_limit: f32,
+    alpha: f32,
+    beta: f32,
+    mu: f32,
+    prev: T,
+    mid: T,
+    at_mid: bool,
+    bank: Vec<Vec<f32>>,
+    dbank: Vec<Vec<f32>>,
+    skip: usize,
+    n_consumed: u64,
+    symbol_tags: bool,
+}
+
+impl<T: SymbolSyncSample> SymbolSync<T> {
+    pub fn new(
+        sps: f32,
+        ted: TimingErrorDetector,
+        loop_bw: f32,
+        damping: f32,
+        max_deviation: f32,
+        symbol_tags: bool,
+    ) -> Block {
+        assert!(sps > 1.0, "SymbolSync: sps must be greater than one");
+
+        let denom = 1.0 + 2.0 * damping * loop_bw + loop_bw * loop_bw;
+        let alpha = 4.0 * damping * loop_bw / denom;
+        let beta = 4.0 * loop_bw * loop_bw / denom;
+
+        let (bank, dbank) = if let TimingErrorDetector::Polyphase { taps, nfilts } = &ted {
+            Self::design_banks(taps, *nfilts)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        Block::new(
+            BlockMetaBuilder::new("SymbolSync").build(),
+            StreamIoBuilder::new()
+                .add_typed_input::<T>("in")
+                .add_typed_output::<T>("out")
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            SymbolSync {
+                ted,
+                omega: sps,
+                omega_mid: sps,
+                omega_limit: sps * max_deviation,
+                alpha,
+                beta,
+                mu: 0.0,
+                prev: T::zero(),
+                mid: T::zero(),
+                at_mid: false,
+                bank,
+                dbank,
+                skip: 0,
+                n_consumed: 0,
+                symbol_tags,
+            },
+        )
+    }
+
+    fn design_banks(taps: &[f32], nfilts: usize) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+        assert!(nfilts > 0, "SymbolSync: nfilts must be positive");
+        let n_arm = num_integer::Integer::div_ceil(&taps.len(), &nfilts);
+        let tap = |n: isize| -> f32 {
+            if n < 0 || n as usize >= taps.len() {
+                0.0
+            } else {
+                taps[n as usize]
+            }
+        };
+
+        // normalize, so that the matched filter output of a unit symbol is one
+        let gain = taps.iter().map(|x| x * x).sum::<f32>() / nfilts as f32;
+        let gain = if gain > 0.0 { gain } else { 1.0 };
+
+        let mut bank = vec![vec![0.0; n_arm]; nfilts];
+        let mut dbank = vec![vec![0.0; n_arm]; nfilts];
+        for k in 0..nfilts {
+            for m in 0..n_arm {
+                let n = (m * nfilts + k) as isize;
+                bank[k][m] = tap(n) / gain;
+                dbank[k][m] = (tap(n + 1) - tap(n - 1)) / 2.0 * nfilts as f32 / gain;
+            }
+        }
+        (bank, dbank)
+    }
+
+    /// Number of input samples needed to interpolate at `ii + mu`.
+    fn history(&self) -> usize {
+        match self.ted {
+            TimingErrorDetector::Polyphase { .. } => self.bank[0].len(),
+            _ => 4,
+        }
+    }
+
+    /// Input index of the symbol interpolated at `ii + mu`, relative to `ii + mu`.
+    fn delay(&self) -> f32 {
+        match &self.ted {
+            TimingErrorDetector::Polyphase { taps, nfilts } => {
+                (self.bank[0].len() - 1) as f32 - (taps.len() - 1) as f32 / (2 * nfilts) as f32
+            }
+            _ => 1.0,
+        }
+    }
+
+    fn interpolate(&self, i: &[T], ii: usize, mu: f32) -> (T, T) {
+        match self.ted {
+            TimingErrorDetector::Polyphase { nfilts, .. } => {
+                let k = cmp::min((mu * nfilts as f32) as usize, nfilts - 1);
+                let n_arm = self.bank[k].len();
+                let mut y = T::zero();
+                let mut dy = T::zero();
+                for m in 0..n_arm {
+                    let x = i[ii + n_arm - 1 - m];
+                    y = y.mac(x, self.bank[k][m]);
+                    dy = dy.mac(x, self.dbank[k][m]);
+                }
+                (y, dy)
+            }
+            _ => {
+                // cubic Lagrange interpolation between i[ii + 1] and i[ii + 2]
+                let t = mu;
+                let c0 = -t * (t - 1.0) * (t - 2.0) / 6.0;
+                let c1 = (t + 1.0) * (t - 1.0) * (t - 2.0) / 2.0;
+                let c2 = -(t + 1.0) * t * (t - 2.0) / 2.0;
+                let c3 = (t + 1.0) * t * (t - 1.0) / 6.0;
+                let y = T::zero()
+                    .mac(i[ii], c0)
+                    .mac(i[ii + 1], c1)
+                    .mac(i[ii + 2], c2)
+                    .mac(i[ii + 3], c3);
+                (y, T::zero())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T: SymbolSyncSample> Kernel for SymbolSync<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<T>();
+        let o = sio.output(0).slice::<T>();
+
+        let history = self.history();
+        let gardner = matches!(self.ted, TimingErrorDetector::Gardner);
+
+        let mut ii = self.skip;
+        let mut oo = 0;
+
+        while ii + history <= i.len() && oo < o.len() {
+            let (y, dy) = self.interpolate(i, ii, self.mu);
+
+            let step = if gardner && !self.at_mid {
+                self.mid = y;
+                self.at_mid = true;
+                self.omega / 2.0
+            } else {
+                let error = match self.ted {
+                    TimingErrorDetector::Gardner => T::gardner_error(self.prev, self.mid, y),
+                    TimingErrorDetector::MuellerMuller => T::mm_error(self.prev, y),
+                    TimingErrorDetector::Polyphase { .. } => T::pfb_error(y, dy),
+                }
+                .clamp(-1.0, 1.0);
+
+                o[oo] = y;
+                if self.symbol_tags {
+                    let t = (self.n_consumed + ii as u64) as f64 + (self.delay() + self.mu) as f64;
+                    sio.output(0).add_tag(oo, Tag::Data(Pmt::Double(t)));
+                }
+                oo += 1;
+                self.prev = y;
+                self.at_mid = false;
+
+                self.omega = self.omega_mid
+                    + (self.omega - self.beta * error - self.omega_mid)
+                        .clamp(-self.omega_limit, self.omega_limit);
+                let step = self.omega - self.alpha * error;
+                if gardner {
+                    step / 2.0
+                } else {
+                    step
+                }
+            };
+
+            self.mu += step;
+            ii += self.mu.floor() as usize;
+            self.mu -= self.mu.floor();
+        }
+
+        // the loop might have stepped beyond the available input
+        let consumed = cmp::min(ii, i.len());
+        self.skip = ii - consumed;
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(oo);
+        self.n_consumed += consumed as u64;
+
+        if sio.input(0).finished() && i.len() < ii + history {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Symbol timing recovery.
+///
+/// Recovers the symbol timing of a real or complex input stream with a
+/// fractional number of samples per symbol and outputs one sample per symbol.
+/// The timing loop is a second-order loop, configured through the normalized
+/// loop bandwidth (rad/symbol) and the damping factor. The symbol rate may
+/// deviate from the nominal rate by `max_deviation` (relative).
+///
+/// Each output symbol is tagged with a [`Pmt::Double`] holding its (fractional)
+/// index in the input stream, unless disabled with `symbol_tags(false)`.
+///
+/// # Inputs
+///
+/// `in`: Input samples
+///
+/// # Outputs
+///
+/// `out`: Symbols
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::{SymbolSyncBuilder, TimingErrorDetector};
+/// use futuresdr::num_complex::Complex32;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sync = fg.add_block(
+///     SymbolSyncBuilder::new(4.0, TimingErrorDetector::Gardner)
+///         .loop_bw(0.02)
+///         .build::<Complex32>(),
+/// );
+/// ```
+pub struct SymbolSyncBuilder {
+    sps: f32,
+    ted: TimingErrorDetector,
+    loop_bw: f32,
+    damping: f32,
+    max_deviation: f32,
+    symbol_tags: bool,
+}
+
+impl SymbolSyncBuilder {
+    pub fn new(sps: f32, ted: TimingErrorDetector) -> SymbolSyncBuilder {
+        SymbolSyncBuilder {
+            sps,
+            ted,
+            loop_bw: 0.045,
+            damping: std::f32::consts::FRAC_1_SQRT_2,
+            max_deviation: 0.005,
+            symbol_tags: true,
+        }
+    }
+
+    #[must_use]
+    pub fn loop_bw(mut self, loop_bw: f32) -> SymbolSyncBuilder {
+        self.loop_bw = loop_bw;
+        self
+    }
+
+    #[must_use]
+    pub fn damping(mut self, damping: f32) -> SymbolSyncBuilder {
+        self.damping = damping;
+        self
+    }
+
+    #[must_use]
+    pub fn max_deviation(mut self, max_deviation: f32) -> SymbolSyncBuilder {
+        self.max_deviation = max_deviation;
+        self
+    }
+
+    #[must_use]
+    pub fn symbol_tags(mut self, symbol_tags: bool) -> SymbolSyncBuilder {
+        self.symbol_tags = symbol_tags;
+        self
+    }
+
+    pub fn build<T: SymbolSyncSample>(self) -> Block {
+        SymbolSync::<T>::new(
+            self.sps,
+            self.ted,
+            self.loop_bw,
+            self.damping,
+            self.max_deviation,
+            self.symbol_tags,
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+pub struct TaggedStreamToPdu<T: Send + 'static> {
+    key: String,
+    fixed_length: Option<usize>,
+    burst: Vec<u8>,
+    remaining: usize,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: Send + 'static> TaggedStreamToPdu<T> {
+    pub fn new(key: impl Into<String>, fixed_length: Option<usize>) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("TaggedStreamToPdu").build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            TaggedStreamToPdu::<T> {
+                key: key.into(),
+                fixed_length,
+                burst: Vec::new(),
+                remaining: 0,
+                _type: std::marker::PhantomData,
+            },
+        )
+    }
+
+    /// Burst length for a tag, if it starts a burst.
+    fn burst_length(&self, tag: &Tag) -> Option<usize> {
+        match tag {
+            Tag::NamedUsize(k, len) if *k == self.key => Some(self.fixed_length.unwrap_or(*len)),
+            Tag::NamedF32(k, _) if *k == self.key => self.fixed_length,
+            Tag::NamedF64(k, _) if *k == self.key => self.fixed_length,
+            Tag::String(k) if *k == self.key => self.fixed_length,
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Kernel for TaggedStreamToPdu<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+        let n = i.len() / item_size;
+
+        let mut starts: Vec<(usize, usize)> = sio
+            .input(0)
+            .tags()
+            .iter()
+            .filter(|t| t.index < n)
+            .filter_map(|t| self.burst_length(&t.tag).map(|l| (t.index, l)))
+            .collect();
+        starts.sort_by_key(|x| x.0);
+        let mut starts = starts.into_iter();
+
+        let mut index = 0;
+        while index < n {
+            if self.remaining == 0 {
+                // bursts do not overlap, skip tags inside the previous burst
+                match starts.find(|(s, _)| *s >= index) {
+                    Some((s, len)) if len > 0 => {
+                        index = s;
+                        self.remaining = len;
+                    }
+                    Some((s, _)) => {
+                        index = s + 1;
+                        continue;
+                    }
+                    None => {
+                        index = n;
+                        break;
+                    }
+                }
+            }
+
+            let m = std::cmp::min(self.remaining, n - index);
+            self.burst
+                .extend_from_slice(&i[index * item_size..(index + m) * item_size]);
+            self.remaining -= m;
+            index += m;
+
+            if self.remaining == 0 {
+                mio.post(0, Pmt::Blob(std::mem::take(&mut self.burst)))
+                    .await;
+            }
+        }
+
+        sio.input(0).consume(index);
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Slices tagged bursts from a stream into PDUs.
+///
+/// A burst starts at a tag with the configured key. Its length, in items, is
+/// either the value of a `Tag::NamedUsize` tag or a fixed length, which
+/// allows to trigger on any tag with the given key, e.g., the `corr_est` tag
+/// of the [PreambleCorrelator](crate::blocks::PreambleCorrelatorBuilder).
+/// Each burst is posted as [Pmt::Blob] containing the raw bytes of the items.
+///
+/// # Inputs
+///
+/// `in`: Tagged stream
+///
+/// # Outputs
+///
+/// `out`: PDUs as [Pmt::Blob]
+///
+/// # Usage
+/// ```
+/// use futuresdr::blocks::TaggedStreamToPduBuilder;
+/// use futuresdr::num_complex::Complex32;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let bytes = fg.add_block(TaggedStreamToPduBuilder::<u8>::new("packet_len").build());
+/// let samples = fg.add_block(
+///     TaggedStreamToPduBuilder::<Complex32>::new("corr_est")
+///         .fixed_length(1024)
+///         .build(),
+/// );
+/// ```
+pub struct TaggedStreamToPduBuilder<T: Send + 'static> {
+    key: String,
+    fixed_length: Option<usize>,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<T: Send + 'static> TaggedStreamToPduBuilder<T> {
+    pub fn new(key: impl Into<String>) -> TaggedStreamToPduBuilder<T> {
+        TaggedStreamToPduBuilder {
+            key: key.into(),
+            fixed_length: None,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Use a fixed burst length, instead of taking it from the tag.
+    #[must_use]
+    pub fn fixed_length(mut self, len: usize) -> TaggedStreamToPduBuilder<T> {
+        self.fixed_length = Some(len);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        TaggedStreamToPdu::<T>::new(self.key, self.fixed_length)
+    }
+}
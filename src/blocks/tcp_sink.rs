@@ -1,7 +1,14 @@
+use async_io::Timer;
 use async_net::{TcpListener, TcpStream};
 use futures::AsyncWriteExt;
+use futures::FutureExt;
+use std::io;
+use std::io::ErrorKind;
+use std::marker::PhantomData;
+use std::time::Duration;
 
 use crate::anyhow::{bail, Context, Result};
+use crate::blocks::tcp_source::Backoff;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
@@ -12,66 +19,191 @@ use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
-pub struct TcpSink {
-    port: u32,
+/// Client of a multi-client sink.
+struct Client {
+    socket: TcpStream,
+    /// Rest of a chunk that did not fit into the socket buffer.
+    pending: Vec<u8>,
+}
+
+impl Client {
+    /// Writes `data`, as far as possible without waiting. Chunks that the
+    /// client cannot take are dropped, but a chunk that was started is
+    /// completed first, so that the client stays aligned to items. `Err`, if
+    /// the client disconnected.
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        while !self.pending.is_empty() {
+            match self.socket.write(&self.pending).now_or_never() {
+                Some(Ok(0)) => return Err(ErrorKind::WriteZero.into()),
+                Some(Ok(n)) => {
+                    self.pending.drain(..n);
+                }
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            }
+        }
+
+        let mut sent = 0;
+        while sent < data.len() {
+            match self.socket.write(&data[sent..]).now_or_never() {
+                Some(Ok(0)) => return Err(ErrorKind::WriteZero.into()),
+                Some(Ok(n)) => sent += n,
+                Some(Err(e)) => return Err(e),
+                None => {
+                    if sent > 0 {
+                        self.pending = data[sent..].to_vec();
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct TcpSink<T: Send + 'static> {
+    addr: String,
+    client: bool,
+    multi_client: bool,
+    reconnect: bool,
+    backoff: Backoff,
     listener: Option<TcpListener>,
-    socket: Option<TcpStream>,
+    sockets: Vec<TcpStream>,
+    clients: Vec<Client>,
+    _type: PhantomData<T>,
 }
 
-impl TcpSink {
+impl<T: Send + 'static> TcpSink<T> {
+    /// Create a sink that accepts one connection on `127.0.0.1:<port>`.
     pub fn new(port: u32) -> Block {
+        TcpSinkBuilder::<T>::new(port).build()
+    }
+
+    fn with_config(
+        addr: String,
+        client: bool,
+        multi_client: bool,
+        reconnect: bool,
+        backoff: Backoff,
+    ) -> Block {
         Block::new(
             BlockMetaBuilder::new("TcpSink").build(),
-            StreamIoBuilder::new().add_input("in", 1).build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
             MessageIoBuilder::new().build(),
-            TcpSink {
-                port,
+            TcpSink::<T> {
+                addr,
+                client,
+                multi_client,
+                reconnect,
+                backoff,
                 listener: None,
-                socket: None,
+                sockets: Vec::new(),
+                clients: Vec::new(),
+                _type: PhantomData,
             },
         )
     }
-}
 
-#[async_trait]
-impl Kernel for TcpSink {
-    async fn work(
-        &mut self,
-        io: &mut WorkIo,
-        sio: &mut StreamIo,
-        _mio: &mut MessageIo<Self>,
-        _meta: &mut BlockMeta,
-    ) -> Result<()> {
-        if self.socket.is_none() {
+    /// Connects to the server or waits for a client. Returns `false`, if the
+    /// block should be called again later.
+    async fn connect(&mut self, io: &mut WorkIo) -> Result<bool> {
+        if self.client {
+            match TcpStream::connect(self.addr.as_str()).await {
+                Ok(socket) => {
+                    debug!("tcp sink connected to {}", self.addr);
+                    self.sockets.push(socket);
+                    self.backoff.reset();
+                }
+                Err(e) if self.reconnect => {
+                    let delay = self.backoff.next();
+                    debug!(
+                        "tcp sink cannot connect to {}: {:?}, retrying in {:?}",
+                        self.addr, e, delay
+                    );
+                    io.block_on(async move {
+                        Timer::after(delay).await;
+                    });
+                    return Ok(false);
+                }
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("TcpSink: cannot connect to {}", self.addr))
+                }
+            }
+        } else {
             let (socket, _) = self
                 .listener
                 .as_mut()
                 .context("no listener")?
                 .accept()
                 .await?;
-            self.socket = Some(socket);
+            self.sockets.push(socket);
             debug!("tcp sink accepted connection");
         }
+        Ok(true)
+    }
 
+    /// Accepts all pending clients, without waiting.
+    fn accept_pending(&mut self) -> Result<()> {
+        let listener = self.listener.as_mut().context("no listener")?;
+        while let Some(res) = listener.accept().now_or_never() {
+            let (socket, addr) = res?;
+            debug!("tcp sink accepted connection from {}", addr);
+            self.clients.push(Client {
+                socket,
+                pending: Vec::new(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Kernel for TcpSink<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
         let i = sio.input(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+        let items = i.len() / item_size;
+        let data = &i[..items * item_size];
+
+        if self.multi_client {
+            // samples are dropped, while no client is connected
+            self.accept_pending()?;
+            self.clients.retain_mut(|c| match c.write(data) {
+                Ok(()) => true,
+                Err(e) => {
+                    debug!("tcp sink client disconnected: {:?}", e);
+                    false
+                }
+            });
+        } else {
+            if self.sockets.is_empty() && !self.connect(io).await? {
+                return Ok(());
+            }
 
-        match self
-            .socket
-            .as_mut()
-            .context("no socket")?
-            .write_all(i)
-            .await
-        {
-            Ok(()) => {}
-            Err(_) => bail!("tcp sink socket error"),
+            if let Err(e) = self.sockets[0].write_all(data).await {
+                if !self.reconnect {
+                    bail!("tcp sink socket error: {:?}", e);
+                }
+                debug!("tcp sink socket closed: {:?}", e);
+                self.sockets.clear();
+                io.call_again = true;
+                return Ok(());
+            }
         }
 
         if sio.input(0).finished() {
             io.finished = true;
         }
 
-        debug!("tcp sink wrote bytes {}", i.len());
-        sio.input(0).consume(i.len());
+        debug!("tcp sink wrote bytes {}", data.len());
+        sio.input(0).consume(items);
 
         Ok(())
     }
@@ -82,21 +214,122 @@ impl Kernel for TcpSink {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        self.listener = Some(TcpListener::bind(format!("127.0.0.1:{}", self.port)).await?);
+        if !self.client {
+            self.listener = Some(
+                TcpListener::bind(self.addr.as_str())
+                    .await
+                    .with_context(|| format!("TcpSink: cannot bind to {}", self.addr))?,
+            );
+        }
         Ok(())
     }
 }
 
-pub struct TcpSinkBuilder {
-    port: u32,
+/// Writes samples to a TCP connection.
+///
+/// By default, the sink listens on `127.0.0.1:<port>` and waits for one
+/// client, before it consumes samples. In client mode, it connects to a remote
+/// server and reconnects with exponential backoff, when the connection fails or
+/// is closed. With `reconnect`, a server waits for the next client, instead of
+/// failing. Samples are not consumed, while the sink is not connected.
+///
+/// With `multi_client`, a server sends the samples to all connected clients
+/// and drops them, while no client is connected. Writes do not wait for the
+/// clients, so that a client that does not keep up cannot stall the others:
+/// it misses the samples that do not fit into its socket buffer. Samples are
+/// dropped in whole chunks, so that each client stays aligned to items.
+///
+/// Samples are sent in the native format of the machine.
+///
+/// # Inputs
+///
+/// `in`: Samples to send
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{TcpSink, TcpSinkBuilder};
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // Waits for a connection on port 1234
+/// let sink = fg.add_block(TcpSink::<u8>::new(1234));
+///
+/// // Streams to all clients on port 1235
+/// let sink = fg.add_block(
+///     TcpSinkBuilder::<Complex<f32>>::new(1235)
+///         .multi_client(true)
+///         .build(),
+/// );
+/// ```
+pub struct TcpSinkBuilder<T: Send + 'static> {
+    addr: String,
+    client: bool,
+    multi_client: bool,
+    reconnect: bool,
+    backoff: Backoff,
+    _type: PhantomData<T>,
 }
 
-impl TcpSinkBuilder {
-    pub fn new(port: u32) -> TcpSinkBuilder {
-        TcpSinkBuilder { port }
+impl<T: Send + 'static> TcpSinkBuilder<T> {
+    /// Listen on `127.0.0.1:<port>`.
+    pub fn new(port: u32) -> TcpSinkBuilder<T> {
+        TcpSinkBuilder {
+            addr: format!("127.0.0.1:{}", port),
+            client: false,
+            multi_client: false,
+            reconnect: false,
+            backoff: Backoff::default(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Connect to a remote server, e.g., `192.168.1.10:1234`.
+    pub fn client<S: Into<String>>(remote: S) -> TcpSinkBuilder<T> {
+        TcpSinkBuilder {
+            addr: remote.into(),
+            client: true,
+            multi_client: false,
+            reconnect: true,
+            backoff: Backoff::default(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Send samples to all connected clients. Has no effect for clients.
+    #[must_use]
+    pub fn multi_client(mut self, multi_client: bool) -> TcpSinkBuilder<T> {
+        self.multi_client = multi_client && !self.client;
+        self
+    }
+
+    /// Reconnect or wait for the next client, instead of failing, when the
+    /// connection is closed. Enabled by default for clients.
+    #[must_use]
+    pub fn reconnect(mut self, reconnect: bool) -> TcpSinkBuilder<T> {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Initial and maximum delay between connection attempts of a client.
+    #[must_use]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> TcpSinkBuilder<T> {
+        self.backoff = Backoff::new(initial, max);
+        self
     }
 
     pub fn build(self) -> Block {
-        TcpSink::new(self.port)
+        TcpSink::<T>::with_config(
+            self.addr,
+            self.client,
+            self.multi_client,
+            self.reconnect,
+            self.backoff,
+        )
     }
 }
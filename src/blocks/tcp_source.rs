@@ -1,5 +1,9 @@
+use async_io::Timer;
 use async_net::{TcpListener, TcpStream};
 use futures::AsyncReadExt;
+use std::cmp;
+use std::marker::PhantomData;
+use std::time::Duration;
 
 use crate::anyhow::{Context, Result};
 use crate::runtime::Block;
@@ -12,29 +16,80 @@ use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
-pub struct TcpSource {
-    port: u32,
+/// Exponential backoff for reconnecting TCP clients.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    pub(crate) fn new(initial: Duration, max: Duration) -> Backoff {
+        Backoff {
+            initial,
+            max,
+            current: initial,
+        }
+    }
+
+    /// Returns the next delay and doubles it for the next attempt.
+    pub(crate) fn next(&mut self) -> Duration {
+        let d = self.current;
+        self.current = cmp::min(self.current * 2, self.max);
+        d
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.current = self.initial;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff::new(Duration::from_millis(100), Duration::from_secs(5))
+    }
+}
+
+pub struct TcpSource<T: Send + 'static> {
+    addr: String,
+    client: bool,
+    reconnect: bool,
+    backoff: Backoff,
     listener: Option<TcpListener>,
     socket: Option<TcpStream>,
+    /// Bytes of an incomplete item.
+    partial: Vec<u8>,
+    _type: PhantomData<T>,
 }
 
-impl TcpSource {
+impl<T: Send + 'static> TcpSource<T> {
+    /// Create a source that accepts one connection on `127.0.0.1:<port>`.
     pub fn new(port: u32) -> Block {
+        TcpSourceBuilder::<T>::new(port).build()
+    }
+
+    fn with_config(addr: String, client: bool, reconnect: bool, backoff: Backoff) -> Block {
         Block::new(
             BlockMetaBuilder::new("TcpSource").build(),
-            StreamIoBuilder::new().add_output("out", 1).build(),
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
             MessageIoBuilder::new().build(),
-            TcpSource {
-                port,
+            TcpSource::<T> {
+                addr,
+                client,
+                reconnect,
+                backoff,
                 listener: None,
                 socket: None,
+                partial: Vec::new(),
+                _type: PhantomData,
             },
         )
     }
 }
 
 #[async_trait]
-impl Kernel for TcpSource {
+impl<T: Send + 'static> Kernel for TcpSource<T> {
     async fn work(
         &mut self,
         io: &mut WorkIo,
@@ -43,14 +98,39 @@ impl Kernel for TcpSource {
         _meta: &mut BlockMeta,
     ) -> Result<()> {
         if self.socket.is_none() {
-            let (socket, _) = self
-                .listener
-                .as_mut()
-                .context("no listener")?
-                .accept()
-                .await?;
-            self.socket = Some(socket);
-            debug!("tcp source accepted connection");
+            if self.client {
+                match TcpStream::connect(self.addr.as_str()).await {
+                    Ok(socket) => {
+                        debug!("tcp source connected to {}", self.addr);
+                        self.socket = Some(socket);
+                        self.backoff.reset();
+                    }
+                    Err(e) if self.reconnect => {
+                        let delay = self.backoff.next();
+                        debug!(
+                            "tcp source cannot connect to {}: {:?}, retrying in {:?}",
+                            self.addr, e, delay
+                        );
+                        io.block_on(async move {
+                            Timer::after(delay).await;
+                        });
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        return Err(e)
+                            .with_context(|| format!("TcpSource: cannot connect to {}", self.addr))
+                    }
+                }
+            } else {
+                let (socket, _) = self
+                    .listener
+                    .as_mut()
+                    .context("no listener")?
+                    .accept()
+                    .await?;
+                self.socket = Some(socket);
+                debug!("tcp source accepted connection");
+            }
         }
 
         let out = sio.output(0).slice::<u8>();
@@ -58,20 +138,43 @@ impl Kernel for TcpSource {
             return Ok(());
         }
 
+        let item_size = std::mem::size_of::<T>();
+        let p = self.partial.len();
+        out[..p].copy_from_slice(&self.partial);
+
         match self
             .socket
             .as_mut()
             .context("no socket")?
-            .read_exact(out)
+            .read(&mut out[p..])
             .await
         {
-            Ok(_) => {
-                debug!("tcp source read bytes {}", out.len());
-                sio.output(0).produce(out.len());
-            }
-            Err(_) => {
+            Ok(0) => {
                 debug!("tcp source socket closed");
-                io.finished = true;
+                self.socket = None;
+                self.partial.clear();
+                if self.reconnect {
+                    io.call_again = true;
+                } else {
+                    io.finished = true;
+                }
+            }
+            Ok(n) => {
+                debug!("tcp source read bytes {}", n);
+                let items = (p + n) / item_size;
+                self.partial = out[items * item_size..p + n].to_vec();
+                sio.output(0).produce(items);
+            }
+            Err(e) if self.reconnect => {
+                debug!("tcp source socket error: {:?}, reconnecting", e);
+                self.socket = None;
+                self.partial.clear();
+                io.call_again = true;
+            }
+            Err(e) => {
+                warn!("tcp source socket error: {:?}", e);
+                return Err(e)
+                    .with_context(|| format!("TcpSource: cannot read from {}", self.addr));
             }
         }
 
@@ -84,21 +187,99 @@ impl Kernel for TcpSource {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        self.listener = Some(TcpListener::bind(format!("127.0.0.1:{}", self.port)).await?);
+        if !self.client {
+            self.listener = Some(
+                TcpListener::bind(self.addr.as_str())
+                    .await
+                    .with_context(|| format!("TcpSource: cannot bind to {}", self.addr))?,
+            );
+        }
         Ok(())
     }
 }
 
-pub struct TcpSourceBuilder {
-    port: u32,
+/// Reads samples from a TCP connection.
+///
+/// By default, the source listens on `127.0.0.1:<port>`, accepts one
+/// connection, and finishes when it is closed. In client mode, it connects to
+/// a remote server, e.g., an `rtl_tcp` server, and reconnects with
+/// exponential backoff, when the connection fails or is closed. With
+/// `reconnect`, a server accepts the next connection instead of finishing.
+///
+/// Samples are received in the native format of the machine. Incomplete
+/// samples of a closed connection are dropped.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{TcpSource, TcpSourceBuilder};
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // Accepts a connection on port 1234
+/// let source = fg.add_block(TcpSource::<u8>::new(1234));
+///
+/// // Connects to a server
+/// let source = fg.add_block(
+///     TcpSourceBuilder::<Complex<f32>>::client("192.168.1.10:1234").build(),
+/// );
+/// ```
+pub struct TcpSourceBuilder<T: Send + 'static> {
+    addr: String,
+    client: bool,
+    reconnect: bool,
+    backoff: Backoff,
+    _type: PhantomData<T>,
 }
 
-impl TcpSourceBuilder {
-    pub fn new(port: u32) -> TcpSourceBuilder {
-        TcpSourceBuilder { port }
+impl<T: Send + 'static> TcpSourceBuilder<T> {
+    /// Listen on `127.0.0.1:<port>`.
+    pub fn new(port: u32) -> TcpSourceBuilder<T> {
+        TcpSourceBuilder {
+            addr: format!("127.0.0.1:{}", port),
+            client: false,
+            reconnect: false,
+            backoff: Backoff::default(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Connect to a remote server, e.g., `192.168.1.10:1234`.
+    pub fn client<S: Into<String>>(remote: S) -> TcpSourceBuilder<T> {
+        TcpSourceBuilder {
+            addr: remote.into(),
+            client: true,
+            reconnect: true,
+            backoff: Backoff::default(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Reconnect or accept the next connection, instead of finishing, when
+    /// the connection is closed. Enabled by default for clients.
+    #[must_use]
+    pub fn reconnect(mut self, reconnect: bool) -> TcpSourceBuilder<T> {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Initial and maximum delay between connection attempts of a client.
+    #[must_use]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> TcpSourceBuilder<T> {
+        self.backoff = Backoff::new(initial, max);
+        self
     }
 
     pub fn build(self) -> Block {
-        TcpSource::new(self.port)
+        TcpSource::<T>::with_config(self.addr, self.client, self.reconnect, self.backoff)
     }
 }
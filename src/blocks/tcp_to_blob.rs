@@ -0,0 +1,233 @@
+use async_io::Async;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Default maximum size of a PDU in bytes.
+const TCP_MAX_PDU_SIZE: usize = 1 << 20;
+
+pub struct TcpToBlob {
+    bind: String,
+    max_pdu_size: usize,
+    listener: Option<Arc<Async<TcpListener>>>,
+    conn: Option<Arc<Async<TcpStream>>>,
+    buf: Vec<u8>,
+    /// Received bytes of incomplete PDUs.
+    pending: Vec<u8>,
+}
+
+impl TcpToBlob {
+    /// Listen on a local address, e.g., `127.0.0.1:5556`.
+    pub fn new<S>(bind: S) -> Block
+    where
+        S: Into<String>,
+    {
+        TcpToBlobBuilder::new(bind).build()
+    }
+
+    fn with_config(bind: String, max_pdu_size: usize) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("TcpToBlob").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            TcpToBlob {
+                bind,
+                max_pdu_size,
+                listener: None,
+                conn: None,
+                buf: vec![0; 65536],
+                pending: Vec::new(),
+            },
+        )
+    }
+
+    /// Removes the next complete PDU from the pending bytes. `Err`, if the
+    /// length prefix exceeds the maximum PDU size.
+    fn next_pdu(&mut self) -> std::result::Result<Option<Vec<u8>>, usize> {
+        if self.pending.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes([
+            self.pending[0],
+            self.pending[1],
+            self.pending[2],
+            self.pending[3],
+        ]) as usize;
+        if len > self.max_pdu_size {
+            return Err(len);
+        }
+        if self.pending.len() < 4 + len {
+            return Ok(None);
+        }
+        let pdu = self.pending[4..4 + len].to_vec();
+        self.pending.drain(..4 + len);
+        Ok(Some(pdu))
+    }
+
+    /// Drops the connection and the bytes of incomplete PDUs.
+    fn disconnect(&mut self) {
+        self.conn = None;
+        self.pending.clear();
+    }
+}
+
+#[async_trait]
+impl Kernel for TcpToBlob {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if self.conn.is_none() {
+            let listener = self.listener.as_ref().context("no listener")?.clone();
+            match listener.get_ref().accept() {
+                Ok((stream, addr)) => {
+                    debug!("TcpToBlob: accepted connection from {}", addr);
+                    self.conn = Some(Arc::new(Async::new(stream)?));
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    io.block_on(async move {
+                        let _ = listener.readable().await;
+                    });
+                    return Ok(());
+                }
+                Err(e) => return Err(e).context("TcpToBlob: accept failed"),
+            }
+        }
+
+        let conn = self.conn.as_ref().context("no connection")?.clone();
+        loop {
+            match conn.get_ref().read(&mut self.buf) {
+                Ok(0) => {
+                    debug!("TcpToBlob: client disconnected");
+                    self.disconnect();
+                    io.call_again = true;
+                    break;
+                }
+                Ok(n) => {
+                    self.pending.extend_from_slice(&self.buf[..n]);
+                    loop {
+                        match self.next_pdu() {
+                            Ok(Some(pdu)) => mio.post(0, Pmt::Blob(pdu)).await,
+                            Ok(None) => break,
+                            Err(len) => {
+                                warn!(
+                                    "TcpToBlob: PDU of {} bytes exceeds maximum of {} bytes, dropping connection",
+                                    len, self.max_pdu_size
+                                );
+                                self.disconnect();
+                                break;
+                            }
+                        }
+                    }
+                    if self.conn.is_none() {
+                        io.call_again = true;
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    io.block_on(async move {
+                        let _ = conn.readable().await;
+                    });
+                    break;
+                }
+                Err(e) => {
+                    debug!("TcpToBlob: connection failed: {:?}", e);
+                    self.disconnect();
+                    io.call_again = true;
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let addr = self
+            .bind
+            .parse::<SocketAddr>()
+            .with_context(|| format!("TcpToBlob: invalid address {:?}", self.bind))?;
+        let listener = Async::<TcpListener>::bind(addr)
+            .with_context(|| format!("TcpToBlob: cannot bind to {}", addr))?;
+        self.listener = Some(Arc::new(listener));
+        Ok(())
+    }
+}
+
+/// Receives length-prefixed PDUs over TCP and posts them as [`Pmt::Blob`].
+///
+/// The block listens on a local address and accepts one client at a time.
+/// Each PDU is framed by its length as big-endian `u32`, like the ones sent by
+/// [BlobToTcp](crate::blocks::BlobToTcp). When the client disconnects, the
+/// block waits for the next one. A client that announces a PDU larger than
+/// `max_pdu_size` is disconnected.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// **Message**: `out`: received PDUs as [`Pmt::Blob`]
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::TcpToBlobBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     TcpToBlobBuilder::new("127.0.0.1:5556")
+///         .max_pdu_size(65536)
+///         .build(),
+/// );
+/// ```
+pub struct TcpToBlobBuilder {
+    bind: String,
+    max_pdu_size: usize,
+}
+
+impl TcpToBlobBuilder {
+    /// Listen on a local address, e.g., `127.0.0.1:5556`.
+    pub fn new<S: Into<String>>(bind: S) -> TcpToBlobBuilder {
+        TcpToBlobBuilder {
+            bind: bind.into(),
+            max_pdu_size: TCP_MAX_PDU_SIZE,
+        }
+    }
+
+    /// Maximum size of a PDU in bytes.
+    #[must_use]
+    pub fn max_pdu_size(mut self, bytes: usize) -> TcpToBlobBuilder {
+        self.max_pdu_size = bytes;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        TcpToBlob::with_config(self.bind, self.max_pdu_size)
+    }
+}
@@ -49,8 +49,8 @@ impl<T: Send + 'static> Throttle<T> {
         Block::new(
             BlockMetaBuilder::new("Throttle").build(),
             StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<T>())
-                .add_output("out", std::mem::size_of::<T>())
+                .add_typed_input::<T>("in")
+                .add_typed_output::<T>("out")
                 .build(),
             MessageIoBuilder::<Self>::new().build(),
             Throttle::<T> {
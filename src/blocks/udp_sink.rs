@@ -0,0 +1,216 @@
+use async_net::UdpSocket;
+use std::cmp;
+use std::io::ErrorKind;
+use std::marker::PhantomData;
+
+use crate::anyhow::{Context, Result};
+use crate::blocks::udp_source::UDP_PAYLOAD_SIZE;
+use crate::blocks::udp_source::UDP_SEQUENCE_SIZE;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct UdpSink<T: Send + 'static> {
+    remote: String,
+    socket: Option<UdpSocket>,
+    payload_size: usize,
+    sequence: Option<u64>,
+    /// Datagram being assembled, including the sequence number.
+    datagram: Vec<u8>,
+    _type: PhantomData<T>,
+}
+
+impl<T: Send + 'static> UdpSink<T> {
+    fn with_config(remote: String, payload_size: usize, sequence: bool) -> Block {
+        let item_size = std::mem::size_of::<T>();
+        let payload_size = cmp::max(payload_size / item_size, 1) * item_size;
+        let header = if sequence { UDP_SEQUENCE_SIZE } else { 0 };
+        Block::new(
+            BlockMetaBuilder::new("UdpSink").build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
+            MessageIoBuilder::new().build(),
+            UdpSink::<T> {
+                remote,
+                socket: None,
+                payload_size,
+                sequence: sequence.then_some(0),
+                datagram: vec![0; header],
+                _type: PhantomData,
+            },
+        )
+    }
+
+    fn header_size(&self) -> usize {
+        if self.sequence.is_some() {
+            UDP_SEQUENCE_SIZE
+        } else {
+            0
+        }
+    }
+
+    async fn send(&mut self) -> Result<()> {
+        if let Some(seq) = self.sequence.as_mut() {
+            self.datagram[..UDP_SEQUENCE_SIZE].copy_from_slice(&seq.to_be_bytes());
+            *seq = seq.wrapping_add(1);
+        }
+        match self
+            .socket
+            .as_ref()
+            .context("no socket")?
+            .send(&self.datagram)
+            .await
+        {
+            Ok(_) => {}
+            // ICMP port unreachable of an earlier datagram, e.g., while the
+            // receiver is restarting
+            Err(e) if e.kind() == ErrorKind::ConnectionRefused => {
+                debug!("UdpSink: {} refused datagram, dropping it", self.remote);
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("UdpSink: sending to {} failed", self.remote))
+            }
+        }
+        self.datagram.truncate(self.header_size());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Kernel for UdpSink<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+        let items = i.len() / item_size;
+
+        let mut data = &i[..items * item_size];
+        while !data.is_empty() {
+            let space = self.payload_size + self.header_size() - self.datagram.len();
+            let n = cmp::min(space, data.len());
+            self.datagram.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if n == space {
+                self.send().await?;
+            }
+        }
+
+        sio.input(0).consume(items);
+
+        if sio.input(0).finished() {
+            if self.datagram.len() > self.header_size() {
+                self.send().await?;
+            }
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let remote = async_net::resolve(self.remote.as_str())
+            .await
+            .ok()
+            .and_then(|a| a.into_iter().next())
+            .with_context(|| format!("UdpSink: cannot resolve {:?}", self.remote))?;
+        let local = if remote.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local).await?;
+        socket
+            .connect(remote)
+            .await
+            .with_context(|| format!("UdpSink: cannot connect to {}", remote))?;
+        self.socket = Some(socket);
+        Ok(())
+    }
+}
+
+/// Sends samples in UDP datagrams.
+///
+/// Samples are packed into datagrams with a fixed payload size, rounded down
+/// to whole samples. At the end of the stream, the remaining samples are sent
+/// in a shorter datagram. With `sequence_header`, each datagram starts with a
+/// big-endian `u64` sequence number, which lets a
+/// [UdpSource](crate::blocks::UdpSource) detect lost datagrams.
+///
+/// Datagrams refused by the remote host, e.g., while the receiver is down or
+/// restarting, are dropped like datagrams lost on the link.
+///
+/// # Inputs
+///
+/// `in`: Samples to send
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::UdpSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(
+///     UdpSinkBuilder::<Complex<f32>>::new("192.168.1.10:5000")
+///         .payload_size(1024)
+///         .sequence_header(true)
+///         .build(),
+/// );
+/// ```
+pub struct UdpSinkBuilder<T: Send + 'static> {
+    remote: String,
+    payload_size: usize,
+    sequence: bool,
+    _type: PhantomData<T>,
+}
+
+impl<T: Send + 'static> UdpSinkBuilder<T> {
+    /// Send to a remote address, e.g., `192.168.1.10:5000`.
+    pub fn new<S: Into<String>>(remote: S) -> UdpSinkBuilder<T> {
+        UdpSinkBuilder {
+            remote: remote.into(),
+            payload_size: UDP_PAYLOAD_SIZE,
+            sequence: false,
+            _type: PhantomData,
+        }
+    }
+
+    /// Payload size of a datagram in bytes.
+    #[must_use]
+    pub fn payload_size(mut self, bytes: usize) -> UdpSinkBuilder<T> {
+        self.payload_size = bytes;
+        self
+    }
+
+    /// Put a sequence number in front of the payload.
+    #[must_use]
+    pub fn sequence_header(mut self, sequence: bool) -> UdpSinkBuilder<T> {
+        self.sequence = sequence;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        UdpSink::<T>::with_config(self.remote, self.payload_size, self.sequence)
+    }
+}
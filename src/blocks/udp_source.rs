@@ -0,0 +1,253 @@
+use async_io::Async;
+use std::cmp;
+use std::io::ErrorKind;
+use std::marker::PhantomData;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::net::UdpSocket;
+use std::sync::Arc;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::Tag;
+use crate::runtime::WorkIo;
+
+/// Default payload size, which fits datagrams with sequence numbers in a
+/// 1500-byte Ethernet frame.
+pub(crate) const UDP_PAYLOAD_SIZE: usize = 1464;
+
+/// Size of the big-endian `u64` sequence number in front of the payload.
+pub(crate) const UDP_SEQUENCE_SIZE: usize = 8;
+
+pub struct UdpSource<T: Send + 'static> {
+    bind: String,
+    multicast: Option<(Ipv4Addr, Ipv4Addr)>,
+    socket: Option<Arc<Async<UdpSocket>>>,
+    sequence: bool,
+    next_sequence: Option<u64>,
+    buf: Vec<u8>,
+    /// Received bytes, not yet output.
+    pending: Vec<u8>,
+    /// Tags with their offset in the pending bytes.
+    tags: Vec<(usize, Tag)>,
+    _type: PhantomData<T>,
+}
+
+impl<T: Send + 'static> UdpSource<T> {
+    fn with_config(
+        bind: String,
+        payload_size: usize,
+        sequence: bool,
+        multicast: Option<(Ipv4Addr, Ipv4Addr)>,
+    ) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("UdpSource").build(),
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
+            MessageIoBuilder::new().build(),
+            UdpSource::<T> {
+                bind,
+                multicast,
+                socket: None,
+                sequence,
+                next_sequence: None,
+                buf: vec![0; payload_size + UDP_SEQUENCE_SIZE],
+                pending: Vec::new(),
+                tags: Vec::new(),
+                _type: PhantomData,
+            },
+        )
+    }
+
+    /// Strips the sequence number of a datagram and queues its payload.
+    fn receive(&mut self, len: usize) {
+        let mut payload = &self.buf[..len];
+
+        if self.sequence {
+            if len < UDP_SEQUENCE_SIZE {
+                warn!("UdpSource: dropping datagram without sequence number");
+                return;
+            }
+            let mut s = [0; UDP_SEQUENCE_SIZE];
+            s.copy_from_slice(&payload[..UDP_SEQUENCE_SIZE]);
+            let seq = u64::from_be_bytes(s);
+            payload = &payload[UDP_SEQUENCE_SIZE..];
+
+            if let Some(expected) = self.next_sequence {
+                if seq != expected {
+                    let lost = seq.saturating_sub(expected);
+                    debug!("UdpSource: expected datagram {}, got {}", expected, seq);
+                    self.tags.push((
+                        self.pending.len(),
+                        Tag::NamedUsize("discontinuity".to_string(), lost as usize),
+                    ));
+                }
+            }
+            self.next_sequence = Some(seq.wrapping_add(1));
+        }
+
+        self.pending.extend_from_slice(payload);
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Kernel for UdpSource<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let out = sio.output(0).slice::<u8>();
+        let item_size = std::mem::size_of::<T>();
+        let socket = self.socket.as_ref().context("no socket")?.clone();
+
+        let mut produced = 0;
+        loop {
+            let n = cmp::min(self.pending.len(), out.len() - produced) / item_size * item_size;
+            out[produced..produced + n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+
+            for (offset, tag) in std::mem::take(&mut self.tags) {
+                if offset < n {
+                    sio.output(0).add_tag((produced + offset) / item_size, tag);
+                } else {
+                    self.tags.push((offset - n, tag));
+                }
+            }
+            produced += n;
+
+            if self.pending.len() >= item_size {
+                // output buffer full
+                break;
+            }
+
+            match socket.get_ref().recv(&mut self.buf) {
+                Ok(len) => self.receive(len),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    io.block_on(async move {
+                        let _ = socket.readable().await;
+                    });
+                    break;
+                }
+                Err(e) => return Err(e).context("UdpSource: receive failed"),
+            }
+        }
+
+        sio.output(0).produce(produced / item_size);
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let addr = self
+            .bind
+            .parse::<SocketAddr>()
+            .with_context(|| format!("UdpSource: invalid address {:?}", self.bind))?;
+        let socket = Async::<UdpSocket>::bind(addr)
+            .with_context(|| format!("UdpSource: cannot bind to {}", addr))?;
+        if let Some((group, interface)) = self.multicast {
+            socket
+                .get_ref()
+                .join_multicast_v4(&group, &interface)
+                .with_context(|| format!("UdpSource: cannot join multicast group {}", group))?;
+        }
+        self.socket = Some(Arc::new(socket));
+        Ok(())
+    }
+}
+
+/// Receives samples from UDP datagrams.
+///
+/// The payload of each datagram is appended to the output stream. With
+/// `sequence_header`, each datagram starts with a big-endian `u64` sequence
+/// number, like the ones sent by a [UdpSink](crate::blocks::UdpSink). When
+/// datagrams are lost or reordered, the first sample after the gap is tagged
+/// with `Tag::NamedUsize("discontinuity", n)`, where `n` is the number of
+/// missing datagrams (0 if the sequence number went backwards).
+///
+/// Datagrams larger than the payload size (plus the sequence number) are
+/// truncated.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::UdpSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+/// use std::net::Ipv4Addr;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     UdpSourceBuilder::<Complex<f32>>::new("0.0.0.0:5000")
+///         .sequence_header(true)
+///         .multicast(Ipv4Addr::new(239, 1, 2, 3), Ipv4Addr::UNSPECIFIED)
+///         .build(),
+/// );
+/// ```
+pub struct UdpSourceBuilder<T: Send + 'static> {
+    bind: String,
+    payload_size: usize,
+    sequence: bool,
+    multicast: Option<(Ipv4Addr, Ipv4Addr)>,
+    _type: PhantomData<T>,
+}
+
+impl<T: Send + 'static> UdpSourceBuilder<T> {
+    /// Receive on a local address, e.g., `0.0.0.0:5000`.
+    pub fn new<S: Into<String>>(bind: S) -> UdpSourceBuilder<T> {
+        UdpSourceBuilder {
+            bind: bind.into(),
+            payload_size: UDP_PAYLOAD_SIZE,
+            sequence: false,
+            multicast: None,
+            _type: PhantomData,
+        }
+    }
+
+    /// Maximum payload size of a datagram in bytes.
+    #[must_use]
+    pub fn payload_size(mut self, bytes: usize) -> UdpSourceBuilder<T> {
+        self.payload_size = bytes;
+        self
+    }
+
+    /// Expect a sequence number in front of the payload.
+    #[must_use]
+    pub fn sequence_header(mut self, sequence: bool) -> UdpSourceBuilder<T> {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Join a multicast group on an interface, which can be
+    /// [`Ipv4Addr::UNSPECIFIED`] for the default interface.
+    #[must_use]
+    pub fn multicast(mut self, group: Ipv4Addr, interface: Ipv4Addr) -> UdpSourceBuilder<T> {
+        self.multicast = Some((group, interface));
+        self
+    }
+
+    pub fn build(self) -> Block {
+        UdpSource::<T>::with_config(self.bind, self.payload_size, self.sequence, self.multicast)
+    }
+}
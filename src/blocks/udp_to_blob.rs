@@ -0,0 +1,107 @@
+use async_io::Async;
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::net::UdpSocket;
+use std::sync::Arc;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Receives UDP datagrams and posts them as [`Pmt::Blob`].
+///
+/// This is the counterpart to [BlobToUdp](crate::blocks::BlobToUdp).
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// **Message**: `out`: payload of each received datagram as [`Pmt::Blob`]
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::UdpToBlob;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(UdpToBlob::new("127.0.0.1:5556"));
+/// ```
+pub struct UdpToBlob {
+    bind: String,
+    socket: Option<Arc<Async<UdpSocket>>>,
+    buf: Vec<u8>,
+}
+
+impl UdpToBlob {
+    /// Receive on a local address, e.g., `127.0.0.1:5556`.
+    pub fn new<S>(bind: S) -> Block
+    where
+        S: Into<String>,
+    {
+        Block::new(
+            BlockMetaBuilder::new("UdpToBlob").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            UdpToBlob {
+                bind: bind.into(),
+                socket: None,
+                buf: vec![0; 65536],
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for UdpToBlob {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let socket = self.socket.as_ref().context("no socket")?.clone();
+
+        loop {
+            match socket.get_ref().recv(&mut self.buf) {
+                Ok(n) => mio.post(0, Pmt::Blob(self.buf[..n].to_vec())).await,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    io.block_on(async move {
+                        let _ = socket.readable().await;
+                    });
+                    break;
+                }
+                Err(e) => return Err(e).context("UdpToBlob: receive failed"),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let addr = self
+            .bind
+            .parse::<SocketAddr>()
+            .with_context(|| format!("UdpToBlob: invalid address {:?}", self.bind))?;
+        let socket = Async::<UdpSocket>::bind(addr)
+            .with_context(|| format!("UdpToBlob: cannot bind to {}", addr))?;
+        self.socket = Some(Arc::new(socket));
+        Ok(())
+    }
+}
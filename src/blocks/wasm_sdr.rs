@@ -2,7 +2,6 @@ use futures::channel::mpsc;
 use futures::SinkExt;
 use futures::StreamExt;
 use once_cell::sync::OnceCell;
-use std::mem::size_of;
 use std::sync::Mutex;
 use wasm_bindgen::prelude::*;
 
@@ -50,7 +49,7 @@ impl WasmSdr {
         Block::new(
             BlockMetaBuilder::new("WasmSDR").build(),
             StreamIoBuilder::new()
-                .add_output("out", size_of::<Complex32>())
+                .add_typed_output::<Complex32>("out")
                 .build(),
             MessageIoBuilder::new().build(),
             Self {
@@ -0,0 +1,206 @@
+use async_tungstenite::tungstenite::Message;
+use futures::future;
+use futures::FutureExt;
+use futures::SinkExt;
+
+use crate::anyhow::Context;
+use crate::anyhow::Result;
+use crate::blocks::websocket_source::WsConn;
+use crate::blocks::websocket_source::WsListener;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct WebsocketPmt {
+    port: u32,
+    dropping: bool,
+    listener: Option<WsListener>,
+    conns: Vec<WsConn>,
+}
+
+impl WebsocketPmt {
+    pub fn new(port: u32) -> Block {
+        WebsocketPmtBuilder::new(port).build()
+    }
+
+    fn with_config(port: u32, dropping: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("WebsocketPmt").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut WebsocketPmt,
+                     _mio: &mut MessageIo<WebsocketPmt>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            block.send(&p).await?;
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .add_output("out")
+                .build(),
+            WebsocketPmt {
+                port,
+                dropping,
+                listener: None,
+                conns: Vec::new(),
+            },
+        )
+    }
+
+    /// Sends a message to all clients.
+    async fn send(&mut self, p: &Pmt) -> Result<()> {
+        let json = serde_json::to_string(p)?;
+        let mut n = 0;
+        while n < self.conns.len() {
+            let send = self.conns[n].ws.send(Message::Text(json.clone()));
+            let res = if self.dropping {
+                match send.now_or_never() {
+                    Some(res) => res,
+                    None => {
+                        debug!("WebsocketPmt: client cannot keep up, dropping message");
+                        Ok(())
+                    }
+                }
+            } else {
+                send.await
+            };
+
+            if let Err(e) = res {
+                debug!("WebsocketPmt: client disconnected: {:?}", e);
+                self.conns.swap_remove(n);
+            } else {
+                n += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Kernel for WebsocketPmt {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let listener = self.listener.as_mut().context("no listener")?;
+        while let Some(conn) = listener.accept()? {
+            self.conns.push(conn);
+        }
+
+        let mut n = 0;
+        while n < self.conns.len() {
+            match self.conns[n].try_next() {
+                Ok(Some(Message::Text(s))) => {
+                    match serde_json::from_str::<Pmt>(&s) {
+                        Ok(p) => mio.post(0, p).await,
+                        Err(e) => warn!("WebsocketPmt: dropping invalid message: {:?}", e),
+                    }
+                    io.call_again = true;
+                }
+                Ok(Some(Message::Binary(v))) => {
+                    mio.post(0, Pmt::Blob(v)).await;
+                    io.call_again = true;
+                }
+                Ok(Some(_)) => io.call_again = true,
+                Ok(None) => {}
+                Err(()) => {
+                    debug!("WebsocketPmt: client disconnected");
+                    self.conns.swap_remove(n);
+                    continue;
+                }
+            }
+            n += 1;
+        }
+
+        if !io.call_again {
+            let mut readable = self.listener.as_ref().context("no listener")?.readable();
+            for c in self.conns.iter() {
+                let socket = c.socket.clone();
+                readable.push(async move { socket.readable().await }.boxed());
+            }
+            io.block_on(async move {
+                let _ = future::select_all(readable).await;
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        self.listener = Some(WsListener::bind(self.port)?);
+        Ok(())
+    }
+}
+
+/// Exchanges messages with websocket clients.
+///
+/// Listens on `0.0.0.0:<port>` and serves any number of clients, e.g., the
+/// frontend or remote tools that control a running flowgraph. Messages are
+/// exchanged as text frames with JSON-serialized [`Pmt`]s, e.g.,
+/// `{"Double":100000000.0}`. Binary frames of clients are forwarded as
+/// [`Pmt::Blob`].
+///
+/// By default, sending a message waits for all clients. With `dropping`,
+/// messages are dropped for clients that cannot keep up.
+///
+/// # Inputs
+///
+/// **Message**: `in`: Messages to send to all clients
+///
+/// # Outputs
+///
+/// **Message**: `out`: Messages received from clients
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::WebsocketPmtBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let ws = fg.add_block(WebsocketPmtBuilder::new(9002).dropping(true).build());
+/// ```
+pub struct WebsocketPmtBuilder {
+    port: u32,
+    dropping: bool,
+}
+
+impl WebsocketPmtBuilder {
+    pub fn new(port: u32) -> WebsocketPmtBuilder {
+        WebsocketPmtBuilder {
+            port,
+            dropping: false,
+        }
+    }
+
+    /// Drop messages for clients that cannot keep up, instead of waiting.
+    #[must_use]
+    pub fn dropping(mut self, dropping: bool) -> WebsocketPmtBuilder {
+        self.dropping = dropping;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        WebsocketPmt::with_config(self.port, self.dropping)
+    }
+}
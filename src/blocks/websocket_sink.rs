@@ -1,19 +1,15 @@
-use async_io::Async;
 use async_tungstenite::tungstenite::Message;
-use async_tungstenite::WebSocketStream;
 use futures::future;
-use futures::future::Either;
-use futures::sink::{Sink, SinkExt};
-use futures::Stream;
+use futures::FutureExt;
+use futures::SinkExt;
 use std::marker::PhantomData;
 use std::mem::size_of;
-use std::net::{SocketAddr, TcpListener, TcpStream};
-use std::pin::Pin;
-use std::sync::Arc;
-use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
-use crate::anyhow::Context as _;
+use crate::anyhow::Context;
 use crate::anyhow::Result;
+use crate::blocks::websocket_source::WsConn;
+use crate::blocks::websocket_source::WsListener;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
@@ -25,36 +21,105 @@ use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
 pub enum WebsocketSinkMode {
+    /// Send all samples and wait for all clients.
     Blocking,
+    /// Send frames with the given number of samples and wait for all clients.
     FixedBlocking(usize),
+    /// Send the latest frame with the given number of samples to each client
+    /// that is ready and drop the others.
     FixedDropping(usize),
 }
 
+struct Client {
+    conn: WsConn,
+    /// Time of the last frame sent to the client.
+    last: Option<Instant>,
+}
+
 pub struct WebsocketSink<T> {
     port: u32,
-    listener: Option<Arc<Async<TcpListener>>>,
-    conn: Option<WsStream>,
+    listener: Option<WsListener>,
+    clients: Vec<Client>,
     mode: WebsocketSinkMode,
+    min_interval: Option<Duration>,
     _p: PhantomData<T>,
 }
 
 impl<T: Send + Sync + 'static> WebsocketSink<T> {
     pub fn new(port: u32, mode: WebsocketSinkMode) -> Block {
+        WebsocketSinkBuilder::<T>::new(port).mode(mode).build()
+    }
+
+    fn with_config(port: u32, mode: WebsocketSinkMode, min_interval: Option<Duration>) -> Block {
         Block::new(
             BlockMetaBuilder::new("WebsocketSink").build(),
-            StreamIoBuilder::new()
-                .add_input("in", size_of::<T>())
-                .build(),
+            StreamIoBuilder::new().add_typed_input::<T>("in").build(),
             MessageIoBuilder::<Self>::new().build(),
             WebsocketSink {
                 port,
                 listener: None,
-                conn: None,
+                clients: Vec::new(),
                 mode,
+                min_interval,
                 _p: PhantomData,
             },
         )
     }
+
+    /// Accepts pending clients and drops the ones that disconnected.
+    async fn update_clients(&mut self) -> Result<()> {
+        let listener = self.listener.as_mut().context("no listener")?;
+        while let Some(conn) = listener.accept()? {
+            self.clients.push(Client { conn, last: None });
+        }
+
+        self.clients.retain_mut(|c| loop {
+            match c.conn.try_next() {
+                Ok(Some(_)) => continue,
+                Ok(None) => break true,
+                Err(()) => {
+                    debug!("websocket: client disconnected");
+                    break false;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Sends a frame to all clients that are due, according to the frame rate.
+    /// With `dropping`, clients that are not ready skip the frame.
+    async fn send(&mut self, v: Vec<u8>, dropping: bool) {
+        let now = Instant::now();
+        let mut n = 0;
+        while n < self.clients.len() {
+            let c = &mut self.clients[n];
+            n += 1;
+
+            if let (Some(last), Some(interval)) = (c.last, self.min_interval) {
+                if now.duration_since(last) < interval {
+                    continue;
+                }
+            }
+
+            let send = c.conn.ws.send(Message::Binary(v.clone()));
+            let res = if dropping {
+                match send.now_or_never() {
+                    Some(res) => res,
+                    None => continue,
+                }
+            } else {
+                send.await
+            };
+
+            if let Err(e) = res {
+                debug!("websocket: client disconnected: {:?}", e);
+                n -= 1;
+                self.clients.swap_remove(n);
+            } else {
+                c.last = Some(now);
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -76,77 +141,49 @@ impl<T: Send + Sync + 'static> Kernel for WebsocketSink<T> {
         let item_size = size_of::<T>();
         let items = i.len() / item_size;
 
-        if let Some(ref mut conn) = self.conn {
-            if i.is_empty() {
-                return Ok(());
+        self.update_clients().await?;
+
+        if self.clients.is_empty() {
+            if let WebsocketSinkMode::FixedDropping(block_size) = &self.mode {
+                let n = items / block_size;
+                sio.input(0).consume(n * block_size);
             }
 
-            let mut v = Vec::new();
+            let readable = self.listener.as_ref().context("no listener")?.readable();
+            io.block_on(async move {
+                let _ = future::select_all(readable).await;
+            });
+            return Ok(());
+        }
 
-            match &self.mode {
-                WebsocketSinkMode::Blocking => {
-                    v.extend_from_slice(i);
-                    sio.input(0).consume(i.len() / size_of::<T>());
-                }
-                WebsocketSinkMode::FixedBlocking(block_size) => {
-                    if *block_size <= items {
-                        v.extend_from_slice(&i[0..(block_size * item_size)]);
-                        sio.input(0).consume(*block_size);
-                    }
-                }
-                WebsocketSinkMode::FixedDropping(block_size) => {
-                    let n = items / block_size;
-                    if n != 0 {
-                        v.extend_from_slice(
-                            &i[((n - 1) * block_size * item_size)..(n * block_size * item_size)],
-                        );
-                        sio.input(0).consume(n * block_size);
-                    }
-                }
-            }
+        if i.is_empty() {
+            return Ok(());
+        }
 
-            if !v.is_empty() {
-                let acc = Box::pin(self.listener.as_ref().context("no listener")?.accept());
-                let send = conn.send(Message::Binary(v));
-
-                match future::select(acc, send).await {
-                    Either::Left((a, _)) => {
-                        if let Ok((stream, _)) = a {
-                            self.conn = Some(WsStream {
-                                inner: async_tungstenite::accept_async(stream).await?,
-                            });
-                        }
-                    }
-                    Either::Right((s, _)) => {
-                        if s.is_err() {
-                            debug!("websocket: client disconnected");
-                            self.conn = None;
-                        }
+        match self.mode {
+            WebsocketSinkMode::Blocking => {
+                self.send(i.to_vec(), false).await;
+                sio.input(0).consume(items);
+            }
+            WebsocketSinkMode::FixedBlocking(block_size) => {
+                if block_size <= items {
+                    self.send(i[0..(block_size * item_size)].to_vec(), false)
+                        .await;
+                    sio.input(0).consume(block_size);
+                    if 2 * block_size <= items {
+                        io.call_again = true;
                     }
                 }
             }
-        } else if let Ok((stream, socket)) = self
-            .listener
-            .as_ref()
-            .context("no listener")?
-            .get_ref()
-            .accept()
-        {
-            debug!("Websocket Accepted client: {}", socket);
-            self.conn = Some(WsStream {
-                inner: async_tungstenite::accept_async(Async::new(stream)?).await?,
-            });
-            io.call_again = true;
-        } else {
-            if let WebsocketSinkMode::FixedDropping(block_size) = &self.mode {
+            WebsocketSinkMode::FixedDropping(block_size) => {
                 let n = items / block_size;
-                sio.input(0).consume(n * block_size);
+                if n != 0 {
+                    let v = i[((n - 1) * block_size * item_size)..(n * block_size * item_size)]
+                        .to_vec();
+                    self.send(v, true).await;
+                    sio.input(0).consume(n * block_size);
+                }
             }
-
-            let l = self.listener.as_ref().unwrap().clone();
-            io.block_on(async move {
-                l.readable().await.unwrap();
-            });
         }
 
         Ok(())
@@ -158,16 +195,62 @@ impl<T: Send + Sync + 'static> Kernel for WebsocketSink<T> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        self.listener = Some(Arc::new(Async::<TcpListener>::bind(
-            format!("0.0.0.0:{}", self.port).parse::<SocketAddr>()?,
-        )?));
+        self.listener = Some(WsListener::bind(self.port)?);
+        Ok(())
+    }
+
+    async fn deinit(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        // close the connections without waiting for slow clients
+        for mut c in self.clients.drain(..) {
+            let _ = c.conn.ws.close(None).now_or_never();
+        }
         Ok(())
     }
 }
 
+/// Sends samples to websocket clients.
+///
+/// Listens on `0.0.0.0:<port>` and sends the samples as binary frames to all
+/// connected clients, e.g., several viewers of the same waterfall. Clients can
+/// connect and disconnect at any time.
+///
+/// In the blocking modes, the sink waits for the slowest client and, while no
+/// client is connected, does not consume samples. With
+/// [`WebsocketSinkMode::FixedDropping`], each client only gets the frames it
+/// can keep up with, and samples are dropped, while no client is connected.
+/// With `max_frame_rate`, frames exceeding the rate are not sent to a client.
+///
+/// # Inputs
+///
+/// `in`: Samples to send
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{WebsocketSinkBuilder, WebsocketSinkMode};
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(
+///     WebsocketSinkBuilder::<f32>::new(9001)
+///         .mode(WebsocketSinkMode::FixedDropping(2048))
+///         .max_frame_rate(30.0)
+///         .build(),
+/// );
+/// ```
 pub struct WebsocketSinkBuilder<T> {
     port: u32,
     mode: WebsocketSinkMode,
+    min_interval: Option<Duration>,
     _p: PhantomData<T>,
 }
 
@@ -176,6 +259,7 @@ impl<T: Send + Sync + 'static> WebsocketSinkBuilder<T> {
         WebsocketSinkBuilder {
             port,
             mode: WebsocketSinkMode::Blocking,
+            min_interval: None,
             _p: PhantomData,
         }
     }
@@ -186,39 +270,14 @@ impl<T: Send + Sync + 'static> WebsocketSinkBuilder<T> {
         self
     }
 
-    pub fn build(self) -> Block {
-        WebsocketSink::<T>::new(self.port, self.mode)
-    }
-}
-
-struct WsStream {
-    inner: WebSocketStream<Async<TcpStream>>,
-}
-
-impl Sink<Message> for WsStream {
-    type Error = async_tungstenite::tungstenite::Error;
-
-    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_ready(cx)
-    }
-
-    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
-        Pin::new(&mut self.inner).start_send(item)
-    }
-
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_flush(cx)
-    }
-
-    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_close(cx)
+    /// Maximum number of frames per second for each client.
+    #[must_use]
+    pub fn max_frame_rate(mut self, fps: f64) -> WebsocketSinkBuilder<T> {
+        self.min_interval = Some(Duration::from_secs_f64(1.0 / fps));
+        self
     }
-}
 
-impl Stream for WsStream {
-    type Item = async_tungstenite::tungstenite::Result<Message>;
-
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.inner).poll_next(cx)
+    pub fn build(self) -> Block {
+        WebsocketSink::<T>::with_config(self.port, self.mode, self.min_interval)
     }
 }
@@ -0,0 +1,383 @@
+use async_io::Async;
+use async_io::Timer;
+use async_tungstenite::tungstenite::Error as WsError;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::WebSocketStream;
+use futures::future;
+use futures::future::BoxFuture;
+use futures::io::{AsyncRead, AsyncWrite};
+use futures::FutureExt;
+use futures::StreamExt;
+use std::cmp;
+use std::collections::VecDeque;
+use std::io;
+use std::io::ErrorKind;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use crate::anyhow::Context as _;
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// TCP stream of a websocket, which can still be awaited to become readable.
+pub(crate) struct SharedStream(pub(crate) Arc<Async<TcpStream>>);
+
+impl AsyncRead for SharedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SharedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self.0).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self.0).poll_close(cx)
+    }
+}
+
+/// Websocket connection with its TCP stream, e.g., to wait for new frames.
+pub(crate) struct WsConn {
+    pub(crate) ws: WebSocketStream<SharedStream>,
+    pub(crate) socket: Arc<Async<TcpStream>>,
+}
+
+impl WsConn {
+    /// Returns the next frame, if one was received. `Err` if the client
+    /// disconnected.
+    pub(crate) fn try_next(&mut self) -> std::result::Result<Option<Message>, ()> {
+        match self.ws.next().now_or_never() {
+            None => Ok(None),
+            Some(Some(Ok(Message::Close(_)))) | Some(Some(Err(_))) | Some(None) => Err(()),
+            Some(Some(Ok(m))) => Ok(Some(m)),
+        }
+    }
+}
+
+/// Time a client has to complete the websocket handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Handshake of an accepted client.
+struct Handshake {
+    addr: SocketAddr,
+    socket: Arc<Async<TcpStream>>,
+    ws: BoxFuture<'static, std::result::Result<WebSocketStream<SharedStream>, WsError>>,
+    started: Instant,
+}
+
+/// Listens for websocket clients. Handshakes are continued, when their
+/// clients send data, so that a client that does not complete the handshake
+/// does not stall the block.
+pub(crate) struct WsListener {
+    listener: Arc<Async<TcpListener>>,
+    handshakes: Vec<Handshake>,
+    ready: VecDeque<WsConn>,
+}
+
+impl WsListener {
+    pub(crate) fn bind(port: u32) -> Result<WsListener> {
+        let listener =
+            Async::<TcpListener>::bind(format!("0.0.0.0:{}", port).parse::<SocketAddr>()?)?;
+        Ok(WsListener {
+            listener: Arc::new(listener),
+            handshakes: Vec::new(),
+            ready: VecDeque::new(),
+        })
+    }
+
+    /// Returns a client that completed the handshake, without waiting for
+    /// the next one.
+    pub(crate) fn accept(&mut self) -> Result<Option<WsConn>> {
+        loop {
+            match self.listener.get_ref().accept() {
+                Ok((stream, addr)) => {
+                    debug!("websocket: accepted client {}", addr);
+                    let socket = Arc::new(Async::new(stream)?);
+                    self.handshakes.push(Handshake {
+                        addr,
+                        socket: socket.clone(),
+                        ws: async_tungstenite::accept_async(SharedStream(socket)).boxed(),
+                        started: Instant::now(),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let ready = &mut self.ready;
+        self.handshakes
+            .retain_mut(|h| match (&mut h.ws).now_or_never() {
+                Some(Ok(ws)) => {
+                    ready.push_back(WsConn {
+                        ws,
+                        socket: h.socket.clone(),
+                    });
+                    false
+                }
+                Some(Err(e)) => {
+                    debug!("websocket: handshake with {} failed: {:?}", h.addr, e);
+                    false
+                }
+                None if h.started.elapsed() > HANDSHAKE_TIMEOUT => {
+                    debug!("websocket: handshake with {} timed out", h.addr);
+                    false
+                }
+                None => true,
+            });
+
+        Ok(self.ready.pop_front())
+    }
+
+    /// Futures that complete, when a client connects or continues its
+    /// handshake.
+    pub(crate) fn readable(&self) -> Vec<BoxFuture<'static, io::Result<()>>> {
+        let listener = self.listener.clone();
+        let mut readable = vec![async move { listener.readable().await }.boxed()];
+        for h in self.handshakes.iter() {
+            let socket = h.socket.clone();
+            readable.push(async move { socket.readable().await }.boxed());
+        }
+        if !self.handshakes.is_empty() {
+            readable.push(
+                async move {
+                    Timer::after(HANDSHAKE_TIMEOUT).await;
+                    Ok(())
+                }
+                .boxed(),
+            );
+        }
+        readable
+    }
+}
+
+pub enum WebsocketSourceMode {
+    /// Stop reading from the client, while the output buffer is full.
+    Blocking,
+    /// Like `Blocking`, but only accept frames with the given number of items.
+    FixedBlocking(usize),
+    /// Only accept frames with the given number of items and drop them, if
+    /// they do not fit into the output buffer.
+    FixedDropping(usize),
+}
+
+pub struct WebsocketSource<T> {
+    port: u32,
+    listener: Option<WsListener>,
+    conn: Option<WsConn>,
+    mode: WebsocketSourceMode,
+    /// Frame that is not yet completely copied to the output buffer.
+    frame: Vec<u8>,
+    offset: usize,
+    _p: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static> WebsocketSource<T> {
+    pub fn new(port: u32, mode: WebsocketSourceMode) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("WebsocketSource").build(),
+            StreamIoBuilder::new().add_typed_output::<T>("out").build(),
+            MessageIoBuilder::<Self>::new().build(),
+            WebsocketSource {
+                port,
+                listener: None,
+                conn: None,
+                mode,
+                frame: Vec::new(),
+                offset: 0,
+                _p: PhantomData,
+            },
+        )
+    }
+
+    /// Checks the size of a frame. Returns `false`, if it should be dropped.
+    fn accept_frame(&self, v: &[u8]) -> bool {
+        let item_size = size_of::<T>();
+        match self.mode {
+            WebsocketSourceMode::Blocking => {
+                if !v.len().is_multiple_of(item_size) {
+                    warn!(
+                        "WebsocketSource: frame of {} bytes is not a multiple of the item size",
+                        v.len()
+                    );
+                    return false;
+                }
+            }
+            WebsocketSourceMode::FixedBlocking(n) | WebsocketSourceMode::FixedDropping(n) => {
+                if v.len() != n * item_size {
+                    warn!(
+                        "WebsocketSource: frame of {} bytes, expected {} items",
+                        v.len(),
+                        n
+                    );
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync + 'static> Kernel for WebsocketSource<T> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let item_size = size_of::<T>();
+
+        if self.offset < self.frame.len() {
+            let o = sio.output(0).slice::<u8>();
+            let n = cmp::min(o.len(), self.frame.len() - self.offset);
+            o[..n].copy_from_slice(&self.frame[self.offset..self.offset + n]);
+            self.offset += n;
+            sio.output(0).produce(n / item_size);
+            if self.offset == self.frame.len() {
+                io.call_again = true;
+            }
+            return Ok(());
+        }
+
+        if self.conn.is_none() {
+            let listener = self.listener.as_mut().context("no listener")?;
+            self.conn = listener.accept()?;
+            if self.conn.is_none() {
+                let readable = listener.readable();
+                io.block_on(async move {
+                    let _ = future::select_all(readable).await;
+                });
+                return Ok(());
+            }
+        }
+
+        let conn = self.conn.as_mut().context("no connection")?;
+        match conn.try_next() {
+            Ok(Some(Message::Binary(v))) => {
+                if self.accept_frame(&v) {
+                    if let WebsocketSourceMode::FixedDropping(_) = self.mode {
+                        let o = sio.output(0).slice::<u8>();
+                        if o.len() >= v.len() {
+                            o[..v.len()].copy_from_slice(&v);
+                            sio.output(0).produce(v.len() / item_size);
+                        } else {
+                            debug!("WebsocketSource: output buffer full, dropping frame");
+                        }
+                    } else {
+                        self.frame = v;
+                        self.offset = 0;
+                    }
+                }
+                io.call_again = true;
+            }
+            Ok(Some(_)) => io.call_again = true,
+            Ok(None) => {
+                let socket = conn.socket.clone();
+                io.block_on(async move {
+                    let _ = socket.readable().await;
+                });
+            }
+            Err(()) => {
+                debug!("WebsocketSource: client disconnected");
+                self.conn = None;
+                io.call_again = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        self.listener = Some(WsListener::bind(self.port)?);
+        Ok(())
+    }
+}
+
+/// Receives samples from a websocket client.
+///
+/// Listens on `0.0.0.0:<port>` and reads binary frames of one client at a
+/// time into the output stream. Further clients are served, when the current
+/// one disconnects. Other frames are ignored. Samples are expected in the
+/// native format of the machine.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::{WebsocketSourceBuilder, WebsocketSourceMode};
+/// use futuresdr::runtime::Flowgraph;
+/// use num_complex::Complex;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     WebsocketSourceBuilder::<Complex<f32>>::new(9001)
+///         .mode(WebsocketSourceMode::FixedDropping(2048))
+///         .build(),
+/// );
+/// ```
+pub struct WebsocketSourceBuilder<T> {
+    port: u32,
+    mode: WebsocketSourceMode,
+    _p: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static> WebsocketSourceBuilder<T> {
+    pub fn new(port: u32) -> WebsocketSourceBuilder<T> {
+        WebsocketSourceBuilder {
+            port,
+            mode: WebsocketSourceMode::Blocking,
+            _p: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn mode(mut self, mode: WebsocketSourceMode) -> WebsocketSourceBuilder<T> {
+        self.mode = mode;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        WebsocketSource::<T>::new(self.port, self.mode)
+    }
+}
@@ -1,5 +1,125 @@
+//! ZeroMQ blocks.
+//!
+//! Stream blocks send samples in the native format of the machine as
+//! multipart messages: an optional topic frame (PUB/SUB), an optional header
+//! frame with the stream tags of the samples as JSON, and the samples. Tag
+//! indices in the header are relative to the first sample of the message.
+//! With tags enabled, sink and source have to agree on the setting. Sources
+//! keep the samples of a message that do not fit into the output buffer and
+//! output them first, once there is space.
+//!
+//! PDU blocks send [`Pmt`](crate::runtime::Pmt)s serialized as JSON.
+use std::cmp;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::ItemTag;
+use crate::runtime::StreamOutput;
+
+pub mod pdu_sink;
+pub use pdu_sink::{PduSink, PduSinkBuilder};
+
+pub mod pdu_source;
+pub use pdu_source::{PduSource, PduSourceBuilder};
+
 pub mod pub_sink;
 pub use pub_sink::{PubSink, PubSinkBuilder};
 
+pub mod pull_source;
+pub use pull_source::{PullSource, PullSourceBuilder};
+
+pub mod push_sink;
+pub use push_sink::{PushSink, PushSinkBuilder};
+
+pub mod rep_sink;
+pub use rep_sink::{RepSink, RepSinkBuilder};
+
+pub mod req_source;
+pub use req_source::{ReqSource, ReqSourceBuilder};
+
 pub mod sub_source;
 pub use sub_source::{SubSource, SubSourceBuilder};
+
+/// Sends samples, preceded by the topic, if it is not empty, and the tags, if
+/// they are set.
+fn send_samples(
+    socket: &zmq::Socket,
+    topic: &[u8],
+    tags: Option<Vec<ItemTag>>,
+    data: &[u8],
+) -> Result<()> {
+    if !topic.is_empty() {
+        socket.send(topic, zmq::SNDMORE)?;
+    }
+    if let Some(tags) = tags {
+        socket.send(serde_json::to_vec(&tags)?, zmq::SNDMORE)?;
+    }
+    socket.send(data, 0)?;
+    Ok(())
+}
+
+/// Received samples that did not fit into the output buffer yet.
+#[derive(Default)]
+struct Pending {
+    data: Vec<u8>,
+    /// Tags with their index in the pending samples.
+    tags: Vec<ItemTag>,
+}
+
+impl Pending {
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Copies as many samples as fit into the output buffer and produces
+    /// them. Returns the number of samples.
+    fn output(&mut self, output: &mut StreamOutput, item_size: usize) -> usize {
+        let o = output.slice::<u8>();
+        let n = cmp::min(self.data.len(), o.len()) / item_size;
+        o[..n * item_size].copy_from_slice(&self.data[..n * item_size]);
+        self.data.drain(..n * item_size);
+
+        for t in std::mem::take(&mut self.tags) {
+            if t.index < n {
+                output.add_tag(t.index, t.tag);
+            } else {
+                self.tags.push(ItemTag {
+                    index: t.index - n,
+                    tag: t.tag,
+                });
+            }
+        }
+        output.produce(n);
+        n
+    }
+}
+
+/// Receives a message of [`send_samples`] and appends its samples and, if
+/// `tags` is set, the tags of the header to `pending`.
+fn recv_samples(
+    socket: &zmq::Socket,
+    tags: bool,
+    item_size: usize,
+    pending: &mut Pending,
+) -> Result<()> {
+    let mut frames = socket.recv_multipart(0)?;
+    let mut data = frames.pop().context("empty ZeroMQ message")?;
+    if tags {
+        if let Some(header) = frames.pop() {
+            let tags: Vec<ItemTag> =
+                serde_json::from_slice(&header).context("invalid tag header in ZeroMQ message")?;
+            let offset = pending.data.len() / item_size;
+            pending.tags.extend(tags.into_iter().map(|t| ItemTag {
+                index: t.index + offset,
+                tag: t.tag,
+            }));
+        }
+    }
+
+    let rest = data.len() % item_size;
+    if rest != 0 {
+        warn!("ZeroMQ: dropped {} bytes of an incomplete sample", rest);
+        data.truncate(data.len() - rest);
+    }
+    pending.data.extend_from_slice(&data);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use futures::FutureExt;
+
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+
+pub struct PduSink {
+    address: String,
+    push: bool,
+    topic: Vec<u8>,
+    socket: Option<zmq::Socket>,
+}
+
+impl PduSink {
+    pub fn new(address: &str) -> Block {
+        PduSinkBuilder::new().address(address).build()
+    }
+
+    fn with_config(address: String, push: bool, topic: Vec<u8>) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PduSink").blocking().build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_input(
+                    "in",
+                    |block: &mut PduSink,
+                     _mio: &mut MessageIo<PduSink>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        async move {
+                            let socket = block.socket.as_ref().context("no socket")?;
+                            if !block.topic.is_empty() {
+                                socket.send(&block.topic[..], zmq::SNDMORE)?;
+                            }
+                            socket.send(serde_json::to_vec(&p)?, 0)?;
+                            Ok(Pmt::Null)
+                        }
+                        .boxed()
+                    },
+                )
+                .build(),
+            PduSink {
+                address,
+                push,
+                topic,
+                socket: None,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PduSink {
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let socket = context.socket(if self.push { zmq::PUSH } else { zmq::PUB })?;
+        info!("PduSink Binding to {:?}", self.address);
+        socket.bind(&self.address)?;
+        self.socket = Some(socket);
+
+        Ok(())
+    }
+}
+
+/// Sends messages on a ZeroMQ PUB or PUSH socket.
+///
+/// Messages are serialized as JSON.
+///
+/// # Inputs
+///
+/// **Message**: `in`: Messages to send
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::PduSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // Publish on a topic
+/// let sink = fg.add_block(
+///     PduSinkBuilder::new()
+///         .address("tcp://*:50001")
+///         .topic("frames")
+///         .build(),
+/// );
+///
+/// // Distribute over workers
+/// let sink = fg.add_block(
+///     PduSinkBuilder::new()
+///         .address("tcp://*:50002")
+///         .push(true)
+///         .build(),
+/// );
+/// ```
+pub struct PduSinkBuilder {
+    address: String,
+    push: bool,
+    topic: Vec<u8>,
+}
+
+impl PduSinkBuilder {
+    pub fn new() -> PduSinkBuilder {
+        PduSinkBuilder {
+            address: "tcp://*:5555".into(),
+            push: false,
+            topic: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> PduSinkBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Use a PUSH socket, instead of a PUB socket.
+    #[must_use]
+    pub fn push(mut self, push: bool) -> PduSinkBuilder {
+        self.push = push;
+        self
+    }
+
+    /// Send the topic as first frame of each message, so that subscribers can
+    /// filter for it. Only used with a PUB socket.
+    #[must_use]
+    pub fn topic(mut self, topic: &str) -> PduSinkBuilder {
+        self.topic = topic.as_bytes().to_vec();
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        let topic = if self.push {
+            Vec::new()
+        } else {
+            self.topic.clone()
+        };
+        PduSink::with_config(self.address.clone(), self.push, topic)
+    }
+}
+
+impl Default for PduSinkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,166 @@
+use crate::anyhow::{Context, Result};
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct PduSource {
+    address: String,
+    pull: bool,
+    topic: Vec<u8>,
+    socket: Option<zmq::Socket>,
+}
+
+impl PduSource {
+    pub fn new(address: &str) -> Block {
+        PduSourceBuilder::new().address(address).build()
+    }
+
+    fn with_config(address: String, pull: bool, topic: Vec<u8>) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PduSource").blocking().build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            PduSource {
+                address,
+                pull,
+                topic,
+                socket: None,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PduSource {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut frames = self
+            .socket
+            .as_ref()
+            .context("no socket")?
+            .recv_multipart(0)?;
+        let data = frames.pop().context("empty ZeroMQ message")?;
+        match serde_json::from_slice::<Pmt>(&data) {
+            Ok(p) => mio.post(0, p).await,
+            Err(e) => warn!("PduSource: dropping invalid message: {:?}", e),
+        }
+        io.call_again = true;
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let socket = if self.pull {
+            context.socket(zmq::PULL)?
+        } else {
+            let socket = context.socket(zmq::SUB)?;
+            socket.set_subscribe(&self.topic)?;
+            socket
+        };
+        info!("PduSource Connecting to {:?}", self.address);
+        socket.connect(&self.address)?;
+        self.socket = Some(socket);
+        Ok(())
+    }
+}
+
+/// Receives messages from a ZeroMQ SUB or PULL socket.
+///
+/// Messages are expected to be serialized as JSON, like the ones of a
+/// [`PduSink`](super::PduSink). Invalid messages are dropped.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// **Message**: `out`: Received messages
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::PduSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// // Subscribe to a topic
+/// let source = fg.add_block(
+///     PduSourceBuilder::new()
+///         .address("tcp://127.0.0.1:50001")
+///         .topic("frames")
+///         .build(),
+/// );
+///
+/// // Work on messages of a PUSH socket
+/// let source = fg.add_block(
+///     PduSourceBuilder::new()
+///         .address("tcp://127.0.0.1:50002")
+///         .pull(true)
+///         .build(),
+/// );
+/// ```
+pub struct PduSourceBuilder {
+    address: String,
+    pull: bool,
+    topic: Vec<u8>,
+}
+
+impl PduSourceBuilder {
+    pub fn new() -> PduSourceBuilder {
+        PduSourceBuilder {
+            address: "tcp://127.0.0.1:5555".into(),
+            pull: false,
+            topic: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> PduSourceBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Use a PULL socket, instead of a SUB socket.
+    #[must_use]
+    pub fn pull(mut self, pull: bool) -> PduSourceBuilder {
+        self.pull = pull;
+        self
+    }
+
+    /// Only receive messages, whose first frame starts with `topic`. Only used
+    /// with a SUB socket.
+    #[must_use]
+    pub fn topic(mut self, topic: &str) -> PduSourceBuilder {
+        self.topic = topic.as_bytes().to_vec();
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        PduSource::with_config(self.address.clone(), self.pull, self.topic.clone())
+    }
+}
+
+impl Default for PduSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
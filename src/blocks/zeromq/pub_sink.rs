@@ -1,4 +1,5 @@
 use crate::anyhow::Result;
+use crate::blocks::zeromq::send_samples;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
@@ -12,18 +13,26 @@ use crate::runtime::WorkIo;
 pub struct PubSink {
     item_size: usize,
     address: String,
+    topic: Vec<u8>,
+    tags: bool,
     publisher: Option<zmq::Socket>,
 }
 
 impl PubSink {
     pub fn new(item_size: usize, address: &str) -> Block {
+        PubSinkBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, topic: Vec<u8>, tags: bool) -> Block {
         Block::new(
             BlockMetaBuilder::new("PubSink").blocking().build(),
             StreamIoBuilder::new().add_input("in", item_size).build(),
             MessageIoBuilder::new().build(),
             PubSink {
                 item_size,
-                address: address.to_string(),
+                address,
+                topic,
+                tags,
                 publisher: None,
             },
         )
@@ -44,7 +53,15 @@ impl Kernel for PubSink {
 
         let n = i.len() / self.item_size;
         if n > 0 {
-            self.publisher.as_mut().unwrap().send(i, 0).unwrap();
+            let tags = self.tags.then(|| {
+                sio.input(0)
+                    .tags()
+                    .iter()
+                    .filter(|t| t.index < n)
+                    .cloned()
+                    .collect()
+            });
+            send_samples(self.publisher.as_ref().unwrap(), &self.topic, tags, i)?;
             sio.input(0).consume(n);
         }
 
@@ -63,7 +80,7 @@ impl Kernel for PubSink {
     ) -> Result<()> {
         let context = zmq::Context::new();
         let publisher = context.socket(zmq::PUB)?;
-        info!("SubSource Binding to {:?}", self.address);
+        info!("PubSink Binding to {:?}", self.address);
         publisher.bind(&self.address)?;
         self.publisher = Some(publisher);
 
@@ -71,9 +88,36 @@ impl Kernel for PubSink {
     }
 }
 
+/// Publishes samples on a ZeroMQ PUB socket.
+///
+/// # Inputs
+///
+/// `in`: Samples to publish
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::PubSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(
+///     PubSinkBuilder::new(8)
+///         .address("tcp://*:50001")
+///         .topic("iq")
+///         .tags(true)
+///         .build(),
+/// );
+/// ```
 pub struct PubSinkBuilder {
     item_size: usize,
     address: String,
+    topic: Vec<u8>,
+    tags: bool,
 }
 
 impl PubSinkBuilder {
@@ -81,6 +125,8 @@ impl PubSinkBuilder {
         PubSinkBuilder {
             item_size,
             address: "tcp://*:5555".into(),
+            topic: Vec::new(),
+            tags: false,
         }
     }
 
@@ -90,7 +136,27 @@ impl PubSinkBuilder {
         self
     }
 
+    /// Send the topic as first frame of each message, so that subscribers can
+    /// filter for it.
+    #[must_use]
+    pub fn topic(mut self, topic: &str) -> PubSinkBuilder {
+        self.topic = topic.as_bytes().to_vec();
+        self
+    }
+
+    /// Send the stream tags in a header.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> PubSinkBuilder {
+        self.tags = tags;
+        self
+    }
+
     pub fn build(&mut self) -> Block {
-        PubSink::new(self.item_size, &*self.address)
+        PubSink::with_config(
+            self.item_size,
+            self.address.clone(),
+            self.topic.clone(),
+            self.tags,
+        )
     }
 }
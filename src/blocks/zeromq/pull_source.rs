@@ -0,0 +1,142 @@
+use crate::anyhow::Result;
+use crate::blocks::zeromq::recv_samples;
+use crate::blocks::zeromq::Pending;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct PullSource {
+    item_size: usize,
+    address: String,
+    tags: bool,
+    puller: Option<zmq::Socket>,
+    pending: Pending,
+}
+
+impl PullSource {
+    pub fn new(item_size: usize, address: &str) -> Block {
+        PullSourceBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, tags: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PullSource").blocking().build(),
+            StreamIoBuilder::new().add_output("out", item_size).build(),
+            MessageIoBuilder::new().build(),
+            PullSource {
+                item_size,
+                address,
+                tags,
+                puller: None,
+                pending: Pending::default(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PullSource {
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        if sio.output(0).slice::<u8>().is_empty() {
+            return Ok(());
+        }
+
+        if self.pending.is_empty() {
+            recv_samples(
+                self.puller.as_ref().unwrap(),
+                self.tags,
+                self.item_size,
+                &mut self.pending,
+            )?;
+        }
+        let n = self.pending.output(sio.output(0), self.item_size);
+        debug!("PullSource received {}", n);
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let puller = context.socket(zmq::PULL)?;
+        info!("PullSource Connecting to {:?}", self.address);
+        puller.connect(&self.address)?;
+        self.puller = Some(puller);
+        Ok(())
+    }
+}
+
+/// Receives samples from a ZeroMQ PULL socket.
+///
+/// Several workers can connect to the same [`PushSink`](super::PushSink),
+/// which balances the load between them.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::PullSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     PullSourceBuilder::new(8)
+///         .address("tcp://127.0.0.1:50001")
+///         .build(),
+/// );
+/// ```
+pub struct PullSourceBuilder {
+    item_size: usize,
+    address: String,
+    tags: bool,
+}
+
+impl PullSourceBuilder {
+    pub fn new(item_size: usize) -> PullSourceBuilder {
+        PullSourceBuilder {
+            item_size,
+            address: "tcp://127.0.0.1:5555".into(),
+            tags: false,
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> PullSourceBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Expect a header with stream tags in each message.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> PullSourceBuilder {
+        self.tags = tags;
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        PullSource::with_config(self.item_size, self.address.clone(), self.tags)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::anyhow::Result;
+use crate::blocks::zeromq::send_samples;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct PushSink {
+    item_size: usize,
+    address: String,
+    tags: bool,
+    pusher: Option<zmq::Socket>,
+}
+
+impl PushSink {
+    pub fn new(item_size: usize, address: &str) -> Block {
+        PushSinkBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, tags: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("PushSink").blocking().build(),
+            StreamIoBuilder::new().add_input("in", item_size).build(),
+            MessageIoBuilder::new().build(),
+            PushSink {
+                item_size,
+                address,
+                tags,
+                pusher: None,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for PushSink {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        debug_assert_eq!(i.len() % self.item_size, 0);
+
+        let n = i.len() / self.item_size;
+        if n > 0 {
+            let tags = self.tags.then(|| {
+                sio.input(0)
+                    .tags()
+                    .iter()
+                    .filter(|t| t.index < n)
+                    .cloned()
+                    .collect()
+            });
+            send_samples(self.pusher.as_ref().unwrap(), &[], tags, i)?;
+            sio.input(0).consume(n);
+        }
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let pusher = context.socket(zmq::PUSH)?;
+        info!("PushSink Binding to {:?}", self.address);
+        pusher.bind(&self.address)?;
+        self.pusher = Some(pusher);
+
+        Ok(())
+    }
+}
+
+/// Distributes samples over the workers connected to a ZeroMQ PUSH socket.
+///
+/// Each message goes to one worker, in a round-robin fashion. The sink blocks,
+/// while no worker is connected.
+///
+/// # Inputs
+///
+/// `in`: Samples to distribute
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::PushSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(PushSinkBuilder::new(8).address("tcp://*:50001").build());
+/// ```
+pub struct PushSinkBuilder {
+    item_size: usize,
+    address: String,
+    tags: bool,
+}
+
+impl PushSinkBuilder {
+    pub fn new(item_size: usize) -> PushSinkBuilder {
+        PushSinkBuilder {
+            item_size,
+            address: "tcp://*:5555".into(),
+            tags: false,
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> PushSinkBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Send the stream tags in a header.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> PushSinkBuilder {
+        self.tags = tags;
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        PushSink::with_config(self.item_size, self.address.clone(), self.tags)
+    }
+}
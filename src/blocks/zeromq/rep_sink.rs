@@ -0,0 +1,160 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::blocks::zeromq::send_samples;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct RepSink {
+    item_size: usize,
+    address: String,
+    tags: bool,
+    responder: Option<zmq::Socket>,
+}
+
+impl RepSink {
+    pub fn new(item_size: usize, address: &str) -> Block {
+        RepSinkBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, tags: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("RepSink").blocking().build(),
+            StreamIoBuilder::new().add_input("in", item_size).build(),
+            MessageIoBuilder::new().build(),
+            RepSink {
+                item_size,
+                address,
+                tags,
+                responder: None,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for RepSink {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        debug_assert_eq!(i.len() % self.item_size, 0);
+
+        let n = i.len() / self.item_size;
+        if n > 0 {
+            let responder = self.responder.as_ref().unwrap();
+            let request = responder.recv_bytes(0)?;
+            let max = <[u8; 8]>::try_from(request.as_slice())
+                .map(|r| u64::from_be_bytes(r) as usize)
+                .unwrap_or(usize::MAX);
+            let n = cmp::min(n, max);
+
+            let tags = self.tags.then(|| {
+                sio.input(0)
+                    .tags()
+                    .iter()
+                    .filter(|t| t.index < n)
+                    .cloned()
+                    .collect()
+            });
+            send_samples(responder, &[], tags, &i[..n * self.item_size])?;
+            debug!("RepSink sent {}", n);
+            sio.input(0).consume(n);
+
+            if n * self.item_size < i.len() {
+                io.call_again = true;
+                return Ok(());
+            }
+        }
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let responder = context.socket(zmq::REP)?;
+        info!("RepSink Binding to {:?}", self.address);
+        responder.bind(&self.address)?;
+        self.responder = Some(responder);
+
+        Ok(())
+    }
+}
+
+/// Answers requests of a ZeroMQ REQ socket with samples.
+///
+/// A request of eight bytes is interpreted as the maximum number of items as
+/// big-endian `u64`, e.g., the free space in the output buffer of a
+/// [`ReqSource`](super::ReqSource). Other requests are answered with all
+/// available samples. Requests are only answered, when samples are
+/// available.
+///
+/// # Inputs
+///
+/// `in`: Samples to send
+///
+/// # Outputs
+///
+/// No outputs.
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::RepSinkBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let sink = fg.add_block(RepSinkBuilder::new(8).address("tcp://*:50001").build());
+/// ```
+pub struct RepSinkBuilder {
+    item_size: usize,
+    address: String,
+    tags: bool,
+}
+
+impl RepSinkBuilder {
+    pub fn new(item_size: usize) -> RepSinkBuilder {
+        RepSinkBuilder {
+            item_size,
+            address: "tcp://*:5555".into(),
+            tags: false,
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> RepSinkBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Send the stream tags in a header.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> RepSinkBuilder {
+        self.tags = tags;
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        RepSink::with_config(self.item_size, self.address.clone(), self.tags)
+    }
+}
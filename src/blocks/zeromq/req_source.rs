@@ -0,0 +1,143 @@
+use crate::anyhow::Result;
+use crate::blocks::zeromq::recv_samples;
+use crate::blocks::zeromq::Pending;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::Kernel;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+pub struct ReqSource {
+    item_size: usize,
+    address: String,
+    tags: bool,
+    requester: Option<zmq::Socket>,
+    pending: Pending,
+}
+
+impl ReqSource {
+    pub fn new(item_size: usize, address: &str) -> Block {
+        ReqSourceBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, tags: bool) -> Block {
+        Block::new(
+            BlockMetaBuilder::new("ReqSource").blocking().build(),
+            StreamIoBuilder::new().add_output("out", item_size).build(),
+            MessageIoBuilder::new().build(),
+            ReqSource {
+                item_size,
+                address,
+                tags,
+                requester: None,
+                pending: Pending::default(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl Kernel for ReqSource {
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<u8>();
+        debug_assert_eq!(o.len() % self.item_size, 0);
+        let max = o.len() / self.item_size;
+        if max == 0 {
+            return Ok(());
+        }
+
+        if self.pending.is_empty() {
+            let requester = self.requester.as_ref().unwrap();
+            requester.send(&(max as u64).to_be_bytes()[..], 0)?;
+            recv_samples(requester, self.tags, self.item_size, &mut self.pending)?;
+        }
+        let n = self.pending.output(sio.output(0), self.item_size);
+        debug!("ReqSource received {}", n);
+
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let context = zmq::Context::new();
+        let requester = context.socket(zmq::REQ)?;
+        info!("ReqSource Connecting to {:?}", self.address);
+        requester.connect(&self.address)?;
+        self.requester = Some(requester);
+        Ok(())
+    }
+}
+
+/// Requests samples from a ZeroMQ REP socket.
+///
+/// Each request carries the free space in the output buffer in items as
+/// big-endian `u64`, so that a [`RepSink`](super::RepSink) never sends more
+/// samples than fit.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::ReqSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     ReqSourceBuilder::new(8)
+///         .address("tcp://127.0.0.1:50001")
+///         .build(),
+/// );
+/// ```
+pub struct ReqSourceBuilder {
+    item_size: usize,
+    address: String,
+    tags: bool,
+}
+
+impl ReqSourceBuilder {
+    pub fn new(item_size: usize) -> ReqSourceBuilder {
+        ReqSourceBuilder {
+            item_size,
+            address: "tcp://127.0.0.1:5555".into(),
+            tags: false,
+        }
+    }
+
+    #[must_use]
+    pub fn address(mut self, address: &str) -> ReqSourceBuilder {
+        self.address = address.to_string();
+        self
+    }
+
+    /// Expect a header with stream tags in each reply.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> ReqSourceBuilder {
+        self.tags = tags;
+        self
+    }
+
+    pub fn build(&mut self) -> Block {
+        ReqSource::with_config(self.item_size, self.address.clone(), self.tags)
+    }
+}
@@ -1,4 +1,6 @@
 use crate::anyhow::Result;
+use crate::blocks::zeromq::recv_samples;
+use crate::blocks::zeromq::Pending;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
@@ -12,19 +14,29 @@ use crate::runtime::WorkIo;
 pub struct SubSource {
     item_size: usize,
     address: String,
+    topic: Vec<u8>,
+    tags: bool,
     receiver: Option<zmq::Socket>,
+    pending: Pending,
 }
 
 impl SubSource {
     pub fn new(item_size: usize, address: &str) -> Block {
+        SubSourceBuilder::new(item_size).address(address).build()
+    }
+
+    fn with_config(item_size: usize, address: String, topic: Vec<u8>, tags: bool) -> Block {
         Block::new(
             BlockMetaBuilder::new("SubSource").blocking().build(),
             StreamIoBuilder::new().add_output("out", item_size).build(),
             MessageIoBuilder::new().build(),
             SubSource {
                 item_size,
-                address: address.to_string(),
+                address,
+                topic,
+                tags,
                 receiver: None,
+                pending: Pending::default(),
             },
         )
     }
@@ -39,13 +51,16 @@ impl Kernel for SubSource {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let o = sio.output(0).slice::<u8>();
-        let mut n_bytes = self.receiver.as_mut().unwrap().recv_into(o, 0)?;
-        n_bytes = std::cmp::min(n_bytes, o.len());
-        debug_assert_eq!(o.len() % self.item_size, 0);
-        let n = n_bytes / self.item_size;
+        if self.pending.is_empty() {
+            recv_samples(
+                self.receiver.as_ref().unwrap(),
+                self.tags,
+                self.item_size,
+                &mut self.pending,
+            )?;
+        }
+        let n = self.pending.output(sio.output(0), self.item_size);
         debug!("SubSource received {}", n);
-        sio.output(0).produce(n);
 
         Ok(())
     }
@@ -59,18 +74,45 @@ impl Kernel for SubSource {
         debug!("SubSource Init");
 
         let context = zmq::Context::new();
-        let receiver = context.socket(zmq::SUB).unwrap();
+        let receiver = context.socket(zmq::SUB)?;
         info!("SubSource Connecting to {:?}", self.address);
         receiver.connect(&self.address)?;
-        receiver.set_subscribe(b"")?;
+        receiver.set_subscribe(&self.topic)?;
         self.receiver = Some(receiver);
         Ok(())
     }
 }
 
+/// Receives samples from a ZeroMQ SUB socket.
+///
+/// # Inputs
+///
+/// No inputs.
+///
+/// # Outputs
+///
+/// `out`: Received samples
+///
+/// # Usage
+/// ```no_run
+/// use futuresdr::blocks::zeromq::SubSourceBuilder;
+/// use futuresdr::runtime::Flowgraph;
+///
+/// let mut fg = Flowgraph::new();
+///
+/// let source = fg.add_block(
+///     SubSourceBuilder::new(8)
+///         .address("tcp://127.0.0.1:50001")
+///         .topic("iq")
+///         .tags(true)
+///         .build(),
+/// );
+/// ```
 pub struct SubSourceBuilder {
     item_size: usize,
     address: String,
+    topic: Vec<u8>,
+    tags: bool,
 }
 
 impl SubSourceBuilder {
@@ -78,6 +120,8 @@ impl SubSourceBuilder {
         SubSourceBuilder {
             item_size,
             address: "tcp://*:5555".into(),
+            topic: Vec::new(),
+            tags: false,
         }
     }
 
@@ -87,7 +131,27 @@ impl SubSourceBuilder {
         self
     }
 
+    /// Only receive messages, whose first frame starts with `topic`. By
+    /// default, all messages are received.
+    #[must_use]
+    pub fn topic(mut self, topic: &str) -> SubSourceBuilder {
+        self.topic = topic.as_bytes().to_vec();
+        self
+    }
+
+    /// Expect a header with stream tags in each message.
+    #[must_use]
+    pub fn tags(mut self, tags: bool) -> SubSourceBuilder {
+        self.tags = tags;
+        self
+    }
+
     pub fn build(&mut self) -> Block {
-        SubSource::new(self.item_size, &*self.address)
+        SubSource::with_config(
+            self.item_size,
+            self.address.clone(),
+            self.topic.clone(),
+            self.tags,
+        )
     }
 }
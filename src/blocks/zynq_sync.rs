@@ -47,8 +47,8 @@ where
         Ok(Block::new(
             BlockMetaBuilder::new("ZynqSync").blocking().build(),
             StreamIoBuilder::new()
-                .add_input("in", std::mem::size_of::<I>())
-                .add_output("out", std::mem::size_of::<O>())
+                .add_typed_input::<I>("in")
+                .add_typed_output::<O>("out")
                 .build(),
             MessageIoBuilder::<ZynqSync<I, O>>::new().build(),
             ZynqSync {
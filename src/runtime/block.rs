@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::fmt;
 use std::future::Future;
@@ -11,6 +12,17 @@ use crate::runtime::Pmt;
 use crate::runtime::StreamInput;
 use crate::runtime::StreamIo;
 use crate::runtime::StreamOutput;
+use crate::runtime::StreamPortDescription;
+
+/// Type, instance name, and stream ports of a block, as exposed through the
+/// control port.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockDescription {
+    pub type_name: String,
+    pub instance_name: Option<String>,
+    pub stream_inputs: Vec<StreamPortDescription>,
+    pub stream_outputs: Vec<StreamPortDescription>,
+}
 
 pub struct WorkIo {
     pub call_again: bool,
@@ -271,6 +283,22 @@ impl Block {
     pub fn is_blocking(&self) -> bool {
         self.0.is_blocking()
     }
+    pub fn description(&self) -> BlockDescription {
+        BlockDescription {
+            type_name: self.type_name().to_string(),
+            instance_name: self.instance_name().map(|n| n.to_string()),
+            stream_inputs: self
+                .stream_inputs()
+                .iter()
+                .map(|i| i.description())
+                .collect(),
+            stream_outputs: self
+                .stream_outputs()
+                .iter()
+                .map(|o| o.description())
+                .collect(),
+        }
+    }
 
     // ##### KERNEL
     pub async fn init(&mut self) -> Result<()> {
@@ -13,6 +13,18 @@ pub trait BufferBuilder: Send + Sync + Any {
         writer_inbox: Sender<AsyncMessage>,
         writer_output_id: usize,
     ) -> BufferWriter;
+
+    /// Build a buffer that holds at least `min_items` items, as required by
+    /// vector ports. The default implementation ignores the hint.
+    fn build_with_min_items(
+        &self,
+        item_size: usize,
+        _min_items: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        self.build(item_size, writer_inbox, writer_output_id)
+    }
 }
 
 #[async_trait]
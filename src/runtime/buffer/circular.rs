@@ -1,6 +1,7 @@
 use futures::channel::mpsc::Sender;
 use futures::prelude::*;
 use std::any::Any;
+use std::cmp;
 use std::fmt;
 use vmcircbuffer::generic;
 
@@ -92,6 +93,24 @@ impl BufferBuilder for Circular {
             writer_output_id,
         )))
     }
+
+    fn build_with_min_items(
+        &self,
+        item_size: usize,
+        min_items: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        // room for two vectors, so that the writer can fill one while the
+        // reader processes the other
+        let min_bytes = cmp::max(self.min_bytes, 2 * min_items * item_size);
+        BufferWriter::Host(Box::new(Writer::new(
+            item_size,
+            min_bytes,
+            writer_inbox,
+            writer_output_id,
+        )))
+    }
 }
 
 pub struct Writer {
@@ -1,6 +1,7 @@
 use futures::channel::mpsc::Sender;
 use futures::prelude::*;
 use std::any::Any;
+use std::cmp;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
@@ -78,6 +79,28 @@ impl BufferBuilder for Slab {
             writer_output_id,
         )
     }
+    fn build_with_min_items(
+        &self,
+        item_size: usize,
+        min_items: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        // the reserved items carry partial vectors over to the next buffer and
+        // the buffers hand over whole vectors
+        let reserved_items = cmp::max(self.reserved_items, min_items);
+        let items =
+            cmp::max(self.min_bytes / item_size, reserved_items + min_items) - reserved_items;
+        let items = items.div_ceil(min_items) * min_items;
+        Writer::new(
+            item_size,
+            (reserved_items + items) * item_size,
+            self.n_buffer,
+            reserved_items,
+            writer_inbox,
+            writer_output_id,
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -7,13 +7,16 @@ use futures::channel::mpsc;
 use futures::channel::oneshot;
 use futures::prelude::*;
 use slab::Slab;
+use std::collections::HashMap;
 use std::path;
+use std::sync::Arc;
 use tower_http::add_extension::AddExtensionLayer;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 
 use crate::runtime::config;
 use crate::runtime::AsyncMessage;
+use crate::runtime::BlockDescription;
 use crate::runtime::Pmt;
 
 macro_rules! relative {
@@ -30,6 +33,17 @@ async fn index(Extension(boxes): Extension<Slab<Option<mpsc::Sender<AsyncMessage
     format!("number of Blocks {:?}", boxes.len())
 }
 
+async fn block_description(
+    Path(blk): Path<usize>,
+    Extension(descriptions): Extension<Arc<HashMap<usize, BlockDescription>>>,
+) -> Result<Json<BlockDescription>, (StatusCode, String)> {
+    descriptions
+        .get(&blk)
+        .cloned()
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, "block not found".to_string()))
+}
+
 async fn handler_id(
     Path((blk, handler)): Path<(usize, usize)>,
     Extension(boxes): Extension<Slab<Option<mpsc::Sender<AsyncMessage>>>>,
@@ -79,16 +93,21 @@ async fn handler_id_post(
     format!("{:?}", ret)
 }
 
-pub async fn start_control_port(inboxes: Slab<Option<mpsc::Sender<AsyncMessage>>>) {
+pub async fn start_control_port(
+    inboxes: Slab<Option<mpsc::Sender<AsyncMessage>>>,
+    descriptions: HashMap<usize, BlockDescription>,
+) {
     if !config::config().ctrlport_enable {
         return;
     }
 
     let mut app = Router::new()
         .route("/api/", get(index))
+        .route("/api/block/:blk/", get(block_description))
         .route("/api/block/:blk/call/:handler/", get(handler_id))
         .route("/api/block/:blk/call/:handler/", post(handler_id_post))
         .layer(AddExtensionLayer::new(inboxes))
+        .layer(AddExtensionLayer::new(Arc::new(descriptions)))
         .layer(CorsLayer::permissive());
 
     let frontend = if let Some(ref p) = config::config().frontend_path {
@@ -136,6 +136,13 @@ impl FlowgraphHandle {
         let p = rx.await?;
         Ok(p)
     }
+
+    /// Terminates the flowgraph. All blocks finish, like at the end of their
+    /// streams, and the task of the flowgraph returns.
+    pub async fn terminate(&mut self) -> Result<()> {
+        self.inbox.send(AsyncMessage::Terminate).await?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, Hash)]
@@ -168,4 +175,24 @@ impl BufferBuilder for DefaultBuffer {
     ) -> BufferWriter {
         Slab::new().build(item_size, writer_inbox, writer_output_id)
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_with_min_items(
+        &self,
+        item_size: usize,
+        min_items: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        Circular::new().build_with_min_items(item_size, min_items, writer_inbox, writer_output_id)
+    }
+    #[cfg(target_arch = "wasm32")]
+    fn build_with_min_items(
+        &self,
+        item_size: usize,
+        min_items: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        Slab::new().build_with_min_items(item_size, min_items, writer_inbox, writer_output_id)
+    }
 }
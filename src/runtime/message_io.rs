@@ -91,19 +91,23 @@ impl MessageOutput {
 
     pub async fn notify_finished(&mut self) {
         for (_, sender) in self.handlers.iter_mut() {
-            sender.send(AsyncMessage::Terminate).await.unwrap();
+            // the receiver might have terminated already
+            let _ = sender.send(AsyncMessage::Terminate).await;
         }
     }
 
     pub async fn post(&mut self, p: Pmt) {
         for (port_id, sender) in self.handlers.iter_mut() {
-            sender
+            if sender
                 .send(AsyncMessage::Call {
                     port_id: *port_id,
                     data: p.clone(),
                 })
                 .await
-                .unwrap();
+                .is_err()
+            {
+                debug!("{}: dropping message for terminated block", self.name);
+            }
         }
     }
 }
@@ -41,6 +41,19 @@ impl Mocker {
             .set_reader(BufferReader::Host(Box::new(MockReader::new(data, tags))));
     }
 
+    /// Appends data to an input, e.g., to feed more samples after a message.
+    pub fn append_input<T>(&mut self, id: usize, data: Vec<T>)
+    where
+        T: Debug + Send + 'static,
+    {
+        self.block
+            .stream_input_mut(id)
+            .try_as::<MockReader<T>>()
+            .expect("mocker: append to an input that was not set")
+            .data
+            .extend(data);
+    }
+
     pub fn init_output<T>(&mut self, id: usize, size: usize)
     where
         T: Debug + Send + 'static,
@@ -62,6 +75,39 @@ impl Mocker {
         }
     }
 
+    pub fn output_tags<T>(&mut self, id: usize) -> Vec<ItemTag>
+    where
+        T: Debug + Send + 'static,
+    {
+        let w = self.block.stream_output_mut(id).writer_mut();
+        if let BufferWriter::Host(w) = w {
+            w.as_any()
+                .downcast_mut::<MockWriter<T>>()
+                .unwrap()
+                .get_tags()
+        } else {
+            panic!("mocker: wrong output buffer (expected CPU, got Custom)");
+        }
+    }
+
+    /// Initializes the block, e.g., to open files.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn init(&mut self) {
+        crate::async_io::block_on(self.block.init()).unwrap();
+    }
+
+    /// Deinitializes the block, e.g., to flush files.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn deinit(&mut self) {
+        crate::async_io::block_on(self.block.deinit()).unwrap();
+    }
+
+    /// Calls the handler of a message input.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn call_handler(&mut self, id: usize, p: crate::runtime::Pmt) -> crate::runtime::Pmt {
+        crate::async_io::block_on(self.block.call_handler(id, p)).unwrap()
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn run(&mut self) {
         let mut io = WorkIo {
@@ -73,6 +119,7 @@ impl Mocker {
         crate::async_io::block_on(async move {
             loop {
                 self.block.work(&mut io).await.unwrap();
+                self.block.commit();
                 if !io.call_again {
                     break;
                 } else {
@@ -91,6 +138,7 @@ impl Mocker {
 
         loop {
             self.block.work(&mut io).await.unwrap();
+            self.block.commit();
             if !io.call_again {
                 break;
             } else {
@@ -149,18 +197,24 @@ impl<T: Debug + Send + 'static> BufferReaderHost for MockReader<T> {
 #[derive(Debug)]
 struct MockWriter<T: Debug + Send + 'static> {
     data: Vec<T>,
+    tags: Vec<ItemTag>,
 }
 
 impl<T: Debug + Send + 'static> MockWriter<T> {
     pub fn new(size: usize) -> Self {
         MockWriter::<T> {
             data: Vec::with_capacity(size),
+            tags: Vec::new(),
         }
     }
 
     pub fn get(&mut self) -> Vec<T> {
         std::mem::take(&mut self.data)
     }
+
+    pub fn get_tags(&mut self) -> Vec<ItemTag> {
+        std::mem::take(&mut self.tags)
+    }
 }
 
 #[async_trait]
@@ -176,7 +230,12 @@ impl<T: Debug + Send + 'static> BufferWriterHost for MockWriter<T> {
         self
     }
 
-    fn produce(&mut self, amount: usize, _tags: Vec<ItemTag>) {
+    fn produce(&mut self, amount: usize, tags: Vec<ItemTag>) {
+        let offset = self.data.len();
+        self.tags.extend(tags.into_iter().map(|mut t| {
+            t.index += offset;
+            t
+        }));
         unsafe {
             self.data.set_len(self.data.len() + amount);
         }
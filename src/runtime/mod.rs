@@ -29,6 +29,7 @@ pub mod tag;
 mod topology;
 
 pub use block::Block;
+pub use block::BlockDescription;
 pub use block::Kernel;
 pub use block::WorkIo;
 pub use block_meta::BlockMeta;
@@ -48,6 +49,7 @@ pub use stream_io::StreamInput;
 pub use stream_io::StreamIo;
 pub use stream_io::StreamIoBuilder;
 pub use stream_io::StreamOutput;
+pub use stream_io::StreamPortDescription;
 pub use tag::ItemTag;
 pub use tag::Tag;
 pub use topology::Topology;
@@ -8,6 +8,8 @@ use futures::future::join_all;
 use futures::future::Either;
 use futures::prelude::*;
 use futures::FutureExt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
 #[cfg(target_arch = "wasm32")]
 type Task<T> = crate::runtime::scheduler::wasm::TaskHandle<T>;
 
@@ -22,6 +24,8 @@ use crate::runtime::scheduler::SmolScheduler;
 use crate::runtime::scheduler::WasmScheduler;
 use crate::runtime::AsyncMessage;
 use crate::runtime::Block;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::runtime::BlockDescription;
 use crate::runtime::Flowgraph;
 use crate::runtime::FlowgraphHandle;
 use crate::runtime::WorkIo;
@@ -158,6 +162,15 @@ async fn run_flowgraph<S: Scheduler>(
     let mut topology = fg.topology.take().context("flowgraph not initialized")?;
     topology.validate()?;
 
+    #[cfg(not(target_arch = "wasm32"))]
+    let descriptions: HashMap<usize, BlockDescription> = topology
+        .blocks
+        .iter()
+        .filter_map(|(id, b)| b.as_ref().map(|b| (id, b.description())))
+        .collect();
+
+    let min_items = topology.buffer_min_items();
+
     let mut inboxes = scheduler.run_topology(&mut topology, &main_channel);
 
     debug!("connect stream io");
@@ -166,7 +179,8 @@ async fn run_flowgraph<S: Scheduler>(
         debug_assert!(!v.is_empty());
 
         let src_inbox = inboxes[*src].as_ref().unwrap().clone();
-        let mut writer = buffer_builder.build(src_inbox, *src_port);
+        let n = min_items.get(&(*src, *src_port)).copied().unwrap_or(1);
+        let mut writer = buffer_builder.build(n, src_inbox, *src_port);
 
         for (dst, dst_port) in v.iter() {
             let dst_inbox = inboxes[*dst].as_ref().unwrap().clone();
@@ -258,7 +272,7 @@ async fn run_flowgraph<S: Scheduler>(
 
     // Start Control Port
     #[cfg(not(target_arch = "wasm32"))]
-    ctrl_port::start_control_port(inboxes.clone()).await;
+    ctrl_port::start_control_port(inboxes.clone(), descriptions).await;
 
     initialized
         .send(())
@@ -302,6 +316,15 @@ async fn run_flowgraph<S: Scheduler>(
 
                 active_blocks -= 1;
             }
+            AsyncMessage::Terminate => {
+                for (_, opt) in inboxes.iter_mut() {
+                    if let Some(ref mut chan) = opt {
+                        if chan.send(AsyncMessage::Terminate).await.is_err() {
+                            debug!("runtime wanted to terminate block that already terminated");
+                        }
+                    }
+                }
+            }
             _ => warn!("main loop received unhandled message"),
         }
     }
@@ -1,4 +1,6 @@
 use futures::channel::mpsc::Sender;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
 use std::fmt;
 use std::mem;
 use std::slice;
@@ -17,10 +19,25 @@ struct CurrentInput {
     index: usize,
 }
 
+/// Name, item size, and, if known, item type of a stream port.
+///
+/// For vector ports, `vlen` is the number of items per vector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamPortDescription {
+    pub name: String,
+    pub item_size: usize,
+    pub type_name: Option<String>,
+    pub vlen: usize,
+}
+
 #[derive(Debug)]
 pub struct StreamInput {
     name: String,
     item_size: usize,
+    type_id: Option<TypeId>,
+    type_name: Option<&'static str>,
+    vlen: usize,
+    min_items: usize,
     reader: Option<BufferReader>,
     current: Option<CurrentInput>,
     tags: Vec<ItemTag>,
@@ -29,30 +46,106 @@ pub struct StreamInput {
 unsafe impl Send for StreamInput {}
 
 impl StreamInput {
+    /// Create an untyped input, which only checks the item size on connect.
     pub fn new(name: &str, item_size: usize) -> StreamInput {
         StreamInput {
             name: name.to_string(),
             item_size,
+            type_id: None,
+            type_name: None,
+            vlen: 1,
+            min_items: 1,
             reader: None,
             current: None,
             tags: Vec::new(),
         }
     }
 
+    /// Create an input for items of type `T`.
+    pub fn new_typed<T: 'static>(name: &str) -> StreamInput {
+        StreamInput {
+            type_id: Some(TypeId::of::<T>()),
+            type_name: Some(std::any::type_name::<T>()),
+            ..StreamInput::new(name, mem::size_of::<T>())
+        }
+    }
+
+    /// Create an input for vectors of `vlen` items of type `T`.
+    ///
+    /// The input slice always holds whole vectors and the block has to
+    /// consume whole vectors. A trailing partial vector at the end of the
+    /// stream is dropped.
+    pub fn new_vector<T: 'static>(name: &str, vlen: usize) -> StreamInput {
+        assert!(vlen > 0, "vector length must be positive");
+        StreamInput {
+            vlen,
+            min_items: vlen,
+            ..StreamInput::new_typed::<T>(name)
+        }
+    }
+
+    /// Create an input for items of type `T` that needs at least `min_items`
+    /// contiguous items to make progress.
+    ///
+    /// The buffer of the connected output is sized accordingly, but, unlike
+    /// for vector ports, the block can consume any number of items.
+    pub fn new_min_items<T: 'static>(name: &str, min_items: usize) -> StreamInput {
+        assert!(min_items > 0, "minimum number of items must be positive");
+        StreamInput {
+            min_items,
+            ..StreamInput::new_typed::<T>(name)
+        }
+    }
+
     pub fn item_size(&self) -> usize {
         self.item_size
     }
 
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_id
+    }
+
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Number of items per vector, 1 for scalar ports.
+    pub fn vlen(&self) -> usize {
+        self.vlen
+    }
+
+    /// Number of items the buffer of the connected output has to hold, the
+    /// vector length for vector ports.
+    pub fn min_items(&self) -> usize {
+        self.min_items
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn description(&self) -> StreamPortDescription {
+        StreamPortDescription {
+            name: self.name.clone(),
+            item_size: self.item_size,
+            type_name: self.type_name.map(|t| t.to_string()),
+            vlen: self.vlen,
+        }
+    }
+
     pub fn try_as<T: 'static>(&mut self) -> Option<&mut T> {
         self.reader.as_mut().unwrap().try_as::<T>()
     }
 
     pub fn consume(&mut self, amount: usize) {
         debug_assert!(self.current.is_some());
+        assert!(
+            amount.is_multiple_of(self.vlen),
+            "{}: consumed {} items, which is not a multiple of the vector length {}",
+            self.name,
+            amount,
+            self.vlen
+        );
         debug_assert!(
             amount
                 <= self.current.as_mut().unwrap().len
@@ -72,7 +165,12 @@ impl StreamInput {
         }
 
         let c = self.current.as_ref().unwrap();
-        unsafe { slice::from_raw_parts(c.ptr as *const T, c.len / mem::size_of::<T>()) }
+        let len = c.len - c.len % (self.item_size * self.vlen);
+        // empty buffers may return a null pointer
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(c.ptr as *const T, len / mem::size_of::<T>()) }
     }
 
     /// Returns a mutable slice to the input buffer.
@@ -120,30 +218,80 @@ impl StreamInput {
 pub struct StreamOutput {
     name: String,
     item_size: usize,
+    type_id: Option<TypeId>,
+    type_name: Option<&'static str>,
+    vlen: usize,
     writer: Option<BufferWriter>,
     tags: Vec<ItemTag>,
     offset: usize,
 }
 
 impl StreamOutput {
+    /// Create an untyped output, which only checks the item size on connect.
     pub fn new(name: &str, item_size: usize) -> StreamOutput {
         StreamOutput {
             name: name.to_string(),
             item_size,
+            type_id: None,
+            type_name: None,
+            vlen: 1,
             writer: None,
             tags: Vec::new(),
             offset: 0,
         }
     }
 
+    /// Create an output for items of type `T`.
+    pub fn new_typed<T: 'static>(name: &str) -> StreamOutput {
+        StreamOutput {
+            type_id: Some(TypeId::of::<T>()),
+            type_name: Some(std::any::type_name::<T>()),
+            ..StreamOutput::new(name, mem::size_of::<T>())
+        }
+    }
+
+    /// Create an output for vectors of `vlen` items of type `T`.
+    ///
+    /// The output slice always has space for whole vectors and the block has
+    /// to produce whole vectors.
+    pub fn new_vector<T: 'static>(name: &str, vlen: usize) -> StreamOutput {
+        assert!(vlen > 0, "vector length must be positive");
+        StreamOutput {
+            vlen,
+            ..StreamOutput::new_typed::<T>(name)
+        }
+    }
+
     pub fn item_size(&self) -> usize {
         self.item_size
     }
 
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_id
+    }
+
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Number of items per vector, 1 for scalar ports.
+    pub fn vlen(&self) -> usize {
+        self.vlen
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn description(&self) -> StreamPortDescription {
+        StreamPortDescription {
+            name: self.name.clone(),
+            item_size: self.item_size,
+            type_name: self.type_name.map(|t| t.to_string()),
+            vlen: self.vlen,
+        }
+    }
+
     pub fn init(&mut self, writer: BufferWriter) {
         debug_assert!(self.writer.is_none());
         self.writer = Some(writer);
@@ -173,11 +321,22 @@ impl StreamOutput {
     }
 
     pub fn produce(&mut self, amount: usize) {
+        assert!(
+            amount.is_multiple_of(self.vlen),
+            "{}: produced {} items, which is not a multiple of the vector length {}",
+            self.name,
+            amount,
+            self.vlen
+        );
         self.offset += amount;
     }
 
     pub fn slice<T>(&mut self) -> &'static mut [T] {
         let (ptr, len) = self.writer.as_mut().unwrap().bytes();
+        let len = len - len % (self.item_size * self.vlen);
+        if len == 0 {
+            return &mut [];
+        }
 
         unsafe {
             slice::from_raw_parts_mut(
@@ -354,6 +513,51 @@ impl StreamIoBuilder {
         self
     }
 
+    /// Add an input for items of type `T`, which can only be connected to
+    /// outputs of the same type (or untyped outputs of the same item size).
+    #[must_use]
+    pub fn add_typed_input<T: 'static>(mut self, name: &str) -> StreamIoBuilder {
+        self.inputs.push(StreamInput::new_typed::<T>(name));
+        self
+    }
+
+    /// Add an output for items of type `T`, which can only be connected to
+    /// inputs of the same type (or untyped inputs of the same item size).
+    #[must_use]
+    pub fn add_typed_output<T: 'static>(mut self, name: &str) -> StreamIoBuilder {
+        self.outputs.push(StreamOutput::new_typed::<T>(name));
+        self
+    }
+
+    /// Add an input for vectors of `vlen` items of type `T`, see
+    /// [`StreamInput::new_vector`].
+    #[must_use]
+    pub fn add_vector_input<T: 'static>(mut self, name: &str, vlen: usize) -> StreamIoBuilder {
+        self.inputs.push(StreamInput::new_vector::<T>(name, vlen));
+        self
+    }
+
+    /// Add an input for items of type `T` that needs at least `min_items`
+    /// contiguous items, see [`StreamInput::new_min_items`].
+    #[must_use]
+    pub fn add_min_items_input<T: 'static>(
+        mut self,
+        name: &str,
+        min_items: usize,
+    ) -> StreamIoBuilder {
+        self.inputs
+            .push(StreamInput::new_min_items::<T>(name, min_items));
+        self
+    }
+
+    /// Add an output for vectors of `vlen` items of type `T`, see
+    /// [`StreamOutput::new_vector`].
+    #[must_use]
+    pub fn add_vector_output<T: 'static>(mut self, name: &str, vlen: usize) -> StreamIoBuilder {
+        self.outputs.push(StreamOutput::new_vector::<T>(name, vlen));
+        self
+    }
+
     #[must_use]
     pub fn tag_propagation<F: FnMut(&mut [StreamInput], &mut [StreamOutput]) + Send + 'static>(
         mut self,
@@ -387,5 +591,42 @@ mod tests {
         let o = StreamOutput::new("foo", 4);
         assert_eq!(o.name(), "foo");
         assert_eq!(o.item_size(), 4);
+        assert_eq!(o.type_id(), None);
+    }
+
+    #[test]
+    fn stream_typed() {
+        let i = StreamInput::new_typed::<f64>("in");
+        assert_eq!(i.item_size(), 8);
+        assert_eq!(i.type_id(), Some(TypeId::of::<f64>()));
+        assert_eq!(i.type_name(), Some("f64"));
+
+        let o = StreamOutput::new_typed::<u32>("out");
+        assert_eq!(
+            o.description(),
+            StreamPortDescription {
+                name: "out".to_string(),
+                item_size: 4,
+                type_name: Some("u32".to_string()),
+                vlen: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn stream_vector() {
+        let i = StreamInput::new_vector::<f32>("in", 16);
+        assert_eq!(i.item_size(), 4);
+        assert_eq!(i.vlen(), 16);
+        assert_eq!(i.type_id(), Some(TypeId::of::<f32>()));
+        assert_eq!(i.description().vlen, 16);
+        assert_eq!(i.min_items(), 16);
+    }
+
+    #[test]
+    fn stream_min_items() {
+        let i = StreamInput::new_min_items::<f32>("in", 1024);
+        assert_eq!(i.vlen(), 1);
+        assert_eq!(i.min_items(), 1024);
     }
 }
@@ -1,15 +1,20 @@
+use serde::{Deserialize, Serialize};
+
 use crate::runtime::Pmt;
 use crate::runtime::StreamInput;
 use crate::runtime::StreamOutput;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Tag {
     Id(u64),
     String(String),
     Data(Pmt),
+    NamedF32(String, f32),
+    NamedF64(String, f64),
+    NamedUsize(String, usize),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemTag {
     pub index: usize,
     pub tag: Tag,
@@ -6,6 +6,8 @@ use crate::runtime::buffer::BufferBuilder;
 use crate::runtime::buffer::BufferWriter;
 use crate::runtime::AsyncMessage;
 use crate::runtime::Block;
+use crate::runtime::StreamInput;
+use crate::runtime::StreamOutput;
 use slab::Slab;
 use std::any::{Any, TypeId};
 use std::cmp::{Eq, PartialEq};
@@ -51,12 +53,16 @@ pub struct BufferBuilderEntry {
 impl BufferBuilderEntry {
     pub(crate) fn build(
         &self,
+        min_items: usize,
         writer_inbox: Sender<AsyncMessage>,
         writer_output_id: usize,
     ) -> BufferWriter {
-        self.builder
-            .builder()
-            .build(self.item_size, writer_inbox, writer_output_id)
+        let builder = self.builder.builder();
+        if min_items > 1 {
+            builder.build_with_min_items(self.item_size, min_items, writer_inbox, writer_output_id)
+        } else {
+            builder.build(self.item_size, writer_inbox, writer_output_id)
+        }
     }
 }
 
@@ -194,9 +200,7 @@ impl Topology {
             .stream_input_name_to_id(dst_port)
             .context("invalid dst port name")?;
 
-        if sp.item_size() != dp.item_size() {
-            bail!("item sizes do not match");
-        }
+        Self::check_stream_types(src, sp, dst, dp)?;
 
         let buffer_entry = BufferBuilderEntry {
             item_size: sp.item_size(),
@@ -211,6 +215,34 @@ impl Topology {
         Ok(())
     }
 
+    /// Checks that the item sizes and, if both ports are typed, the item types
+    /// of a stream connection match.
+    fn check_stream_types(
+        src: &Block,
+        output: &StreamOutput,
+        dst: &Block,
+        input: &StreamInput,
+    ) -> Result<()> {
+        let port =
+            |b: &Block, p: &str| format!("{}.{}", b.instance_name().unwrap_or(b.type_name()), p);
+        let ty = |t: Option<&str>, size: usize| {
+            t.map_or(format!("{} byte items", size), |t| t.to_string())
+        };
+
+        let types_differ =
+            matches!((output.type_id(), input.type_id()), (Some(o), Some(i)) if o != i);
+        if output.item_size() != input.item_size() || types_differ {
+            bail!(
+                "stream types do not match: {} produces {}, but {} expects {}",
+                port(src, output.name()),
+                ty(output.type_name(), output.item_size()),
+                port(dst, input.name()),
+                ty(input.type_name(), input.item_size()),
+            );
+        }
+        Ok(())
+    }
+
     pub fn connect_message(
         &mut self,
         src_block: usize,
@@ -286,9 +318,7 @@ impl Topology {
             for (dst, dst_port) in v.iter() {
                 let dst_block = self.block_ref(*dst).expect("dst block not found");
                 let input = dst_block.stream_input(*dst_port);
-                if output.item_size() != input.item_size() {
-                    bail!("item size of stream connection does not match");
-                }
+                Self::check_stream_types(src_block, output, dst_block, input)?;
             }
         }
 
@@ -311,6 +341,25 @@ impl Topology {
         Ok(())
     }
 
+    /// Minimum number of items of the buffer of each stream output, i.e., the
+    /// largest vector length of the output and the minimum number of items of
+    /// its connected inputs.
+    pub(crate) fn buffer_min_items(&self) -> HashMap<(usize, usize), usize> {
+        let mut min_items = HashMap::new();
+        for ((src, src_port, _), v) in self.stream_edges.iter() {
+            let mut n = self
+                .block_ref(*src)
+                .map_or(1, |b| b.stream_output(*src_port).vlen());
+            for (dst, dst_port) in v.iter() {
+                if let Some(b) = self.block_ref(*dst) {
+                    n = n.max(b.stream_input(*dst_port).min_items());
+                }
+            }
+            min_items.insert((*src, *src_port), n);
+        }
+        min_items
+    }
+
     pub fn block_ref(&self, id: usize) -> Option<&Block> {
         self.blocks.get(id).and_then(|v| v.as_ref())
     }
@@ -0,0 +1,42 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Returns a port that is currently free.
+///
+/// Only for blocks that listen on a given port. Another process might take the
+/// port before the block binds it, so prefer to bind the test's socket to port
+/// `0` and pass its address to the block.
+pub fn free_port() -> u32 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port() as u32
+}
+
+/// Connects to a block listening on a local port, waiting for it to start.
+pub fn connect(port: u32) -> TcpStream {
+    for _ in 0..50 {
+        if let Ok(s) = TcpStream::connect(format!("127.0.0.1:{}", port)) {
+            s.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+            return s;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    panic!("cannot connect to port {}", port);
+}
+
+/// Path in the temporary directory that is unique for the test process.
+pub fn tmp(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("futuresdr-{}-{}", name, std::process::id()))
+}
+
+/// `u32` items in native byte order.
+pub fn bytes(items: std::ops::Range<u32>) -> Vec<u8> {
+    items.flat_map(|x| x.to_ne_bytes()).collect()
+}
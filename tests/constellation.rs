@@ -0,0 +1,129 @@
+use futuredsp::firdes;
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::Constellation;
+use futuresdr::blocks::ConstellationDemapperBuilder;
+use futuresdr::blocks::ConstellationMapper;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+use std::iter::repeat_with;
+
+const ALL: [Constellation; 5] = [
+    Constellation::Bpsk,
+    Constellation::Qpsk,
+    Constellation::Psk8,
+    Constellation::Qam16,
+    Constellation::Qam64,
+];
+
+#[test]
+fn constellation_gray() {
+    for c in ALL {
+        let p = c.points();
+        assert_eq!(p.len(), 1 << c.bits_per_symbol());
+
+        let energy = p.iter().map(|x| x.norm_sqr()).sum::<f32>() / p.len() as f32;
+        assert!((energy - 1.0).abs() < 1e-5);
+
+        // nearest neighbors differ in exactly one bit
+        for (a, pa) in p.iter().enumerate() {
+            let d_min = p
+                .iter()
+                .enumerate()
+                .filter(|(b, _)| *b != a)
+                .map(|(_, pb)| (pa - pb).norm())
+                .fold(f32::MAX, f32::min);
+            for (b, pb) in p.iter().enumerate() {
+                if b != a && ((pa - pb).norm() - d_min).abs() < 1e-4 {
+                    assert_eq!((a ^ b).count_ones(), 1, "{:?}: {} {}", c, a, b);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mapper_demapper_loopback() -> Result<()> {
+    for c in ALL {
+        let bits: Vec<u8> = repeat_with(|| rand::random::<u8>() & 1).take(600).collect();
+
+        let mut fg = Flowgraph::new();
+
+        let src = fg.add_block(VectorSourceBuilder::<u8>::new(bits.clone()).build());
+        let mapper = fg.add_block(ConstellationMapper::new(c));
+        let hard = fg.add_block(ConstellationDemapperBuilder::new(c).build_hard());
+        let soft = fg.add_block(ConstellationDemapperBuilder::new(c).build_soft());
+        let hard_snk = fg.add_block(VectorSinkBuilder::<u8>::new().build());
+        let soft_snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+        fg.connect_stream(src, "out", mapper, "in")?;
+        fg.connect_stream(mapper, "out", hard, "in")?;
+        fg.connect_stream(mapper, "out", soft, "in")?;
+        fg.connect_stream(hard, "out", hard_snk, "in")?;
+        fg.connect_stream(soft, "out", soft_snk, "in")?;
+
+        fg = Runtime::new().run(fg)?;
+
+        let hard = fg.kernel::<VectorSink<u8>>(hard_snk).unwrap().items();
+        assert_eq!(hard, &bits);
+
+        let soft = fg.kernel::<VectorSink<f32>>(soft_snk).unwrap().items();
+        assert_eq!(soft.len(), bits.len());
+        for (llr, b) in soft.iter().zip(bits.iter()) {
+            assert_eq!(*llr < 0.0, *b == 1);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rrc_modem() -> Result<()> {
+    let sps = 4;
+    let span = 8;
+    let c = Constellation::Qam16;
+
+    let bits: Vec<u8> = repeat_with(|| rand::random::<u8>() & 1)
+        .take(4000)
+        .collect();
+
+    let taps = firdes::root_raised_cosine::<f32>(span, sps, 0.35);
+    let rx_taps: Vec<f32> = taps.iter().map(|x| x / (sps as f32).sqrt()).collect();
+    let mut tx_taps: Vec<f32> = taps.iter().map(|x| x * (sps as f32).sqrt()).collect();
+    // the interpolator needs a multiple of `sps` taps
+    tx_taps.resize(taps.len() + sps - taps.len() % sps, 0.0);
+
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<u8>::new(bits.clone()).build());
+    let mapper = fg.add_block(ConstellationMapper::new(c));
+    let tx = fg.add_block(FirBuilder::new_resampling_with_taps::<Complex32, f32, _>(
+        sps, 1, tx_taps,
+    ));
+    let rx = fg.add_block(FirBuilder::new_resampling_with_taps::<Complex32, f32, _>(
+        1, sps, rx_taps,
+    ));
+    let demapper = fg.add_block(ConstellationDemapperBuilder::new(c).build_hard());
+    let snk = fg.add_block(VectorSinkBuilder::<u8>::new().build());
+
+    fg.connect_stream(src, "out", mapper, "in")?;
+    fg.connect_stream(mapper, "out", tx, "in")?;
+    fg.connect_stream(tx, "out", rx, "in")?;
+    fg.connect_stream(rx, "out", demapper, "in")?;
+    fg.connect_stream(demapper, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let rx_bits = fg.kernel::<VectorSink<u8>>(snk).unwrap().items();
+
+    // the filters consume the first `span` symbols as history
+    let skip = span * c.bits_per_symbol();
+    assert!(rx_bits.len() > 3000);
+    assert_eq!(&rx_bits[..], &bits[skip..skip + rx_bits.len()]);
+
+    Ok(())
+}
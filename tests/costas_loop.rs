@@ -0,0 +1,86 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::CostasLoopBuilder;
+use futuresdr::blocks::CostasOrder;
+use futuresdr::blocks::PllCarrierTrackingBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+fn run(input: Vec<Complex32>, block: futuresdr::runtime::Block) -> Result<Vec<Complex32>> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input).build());
+    let blk = fg.add_block(block);
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+
+    fg.connect_stream(src, "out", blk, "in")?;
+    fg.connect_stream(blk, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<Complex32>>(snk).unwrap();
+    Ok(snk.items().clone())
+}
+
+#[test]
+fn costas_bpsk() -> Result<()> {
+    let n = 5000;
+    let input: Vec<Complex32> = (0..n)
+        .map(|i| {
+            let bit = if (i * 7 + i / 3) % 5 < 2 { 1.0 } else { -1.0 };
+            Complex32::new(bit, 0.0) * Complex32::from_polar(1.0, 0.7 + 0.002 * i as f32)
+        })
+        .collect();
+
+    let v = run(input, CostasLoopBuilder::new(CostasOrder::Bpsk).build())?;
+
+    assert_eq!(v.len(), n);
+    for s in &v[n - 500..] {
+        assert!((s.re.abs() - 1.0).abs() < 0.1);
+        assert!(s.im.abs() < 0.1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn costas_qpsk() -> Result<()> {
+    let n = 5000;
+    let input: Vec<Complex32> = (0..n)
+        .map(|i| {
+            let sym = Complex32::from_polar(1.0, std::f32::consts::FRAC_PI_4)
+                * Complex32::new(0.0, 1.0).powi(((i * 13 + i / 7) % 4) as i32);
+            sym * Complex32::from_polar(1.0, 0.3 + 0.001 * i as f32)
+        })
+        .collect();
+
+    let v = run(input, CostasLoopBuilder::new(CostasOrder::Qpsk).build())?;
+
+    assert_eq!(v.len(), n);
+    for s in &v[n - 500..] {
+        assert!((s.re.abs() - s.im.abs()).abs() < 0.1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn pll_tone() -> Result<()> {
+    let n = 5000;
+    let input: Vec<Complex32> = (0..n)
+        .map(|i| Complex32::from_polar(1.0, 0.05 * i as f32))
+        .collect();
+
+    let v = run(input, PllCarrierTrackingBuilder::new().build())?;
+
+    assert_eq!(v.len(), n);
+    for s in &v[n - 500..] {
+        assert!((s.re - 1.0).abs() < 0.05);
+        assert!(s.im.abs() < 0.05);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::CfarBuilder;
+use futuresdr::blocks::EnergyDetectorBuilder;
+use futuresdr::blocks::MessageSink;
+use futuresdr::blocks::PowerSquelchBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::Tag;
+
+/// Silence, a burst with unit power from 1000 to 2000, silence.
+fn bursts() -> Vec<Complex32> {
+    (0..3000)
+        .map(|i| {
+            if (1000..2000).contains(&i) {
+                Complex32::from_polar(1.0, 0.1 * i as f32)
+            } else {
+                Complex32::new(0.001, 0.0)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn power_squelch() {
+    let input = bursts();
+
+    let mut mocker = Mocker::new(PowerSquelchBuilder::new(-10.0).alpha(0.1).build());
+    mocker.input(0, input.clone());
+    mocker.init_output::<Complex32>(0, input.len());
+    mocker.run();
+    let output = mocker.output::<Complex32>(0);
+    assert_eq!(output.len(), input.len());
+    assert!(output[..1000].iter().all(|x| x.norm() == 0.0));
+    assert_eq!(output[1100..2000], input[1100..2000]);
+    assert!(output[2100..].iter().all(|x| x.norm() == 0.0));
+
+    let mut mocker = Mocker::new(
+        PowerSquelchBuilder::new(-10.0)
+            .alpha(0.1)
+            .gate(true)
+            .build(),
+    );
+    mocker.input(0, input.clone());
+    mocker.init_output::<Complex32>(0, input.len());
+    mocker.run();
+    let output = mocker.output::<Complex32>(0);
+    // opens after one sample, closes when the average decayed by the
+    // threshold plus the hysteresis, i.e., 0.9^n < 0.05 after 29 samples
+    assert!(
+        output.len() > 1020 && output.len() < 1040,
+        "{}",
+        output.len()
+    );
+    assert_eq!(output[0], input[1000]);
+}
+
+#[test]
+fn energy_detector() {
+    let input = bursts();
+
+    let mut mocker = Mocker::new(EnergyDetectorBuilder::new(-10.0).alpha(0.1).build());
+    mocker.input(0, input.clone());
+    mocker.init_output::<Complex32>(0, input.len());
+    mocker.run();
+    assert_eq!(mocker.output::<Complex32>(0), input);
+
+    let tags: Vec<(usize, Tag)> = mocker
+        .output_tags::<Complex32>(0)
+        .into_iter()
+        .map(|t| (t.index, t.tag))
+        .collect();
+    assert_eq!(tags.len(), 2);
+    match &tags[0] {
+        (1000, Tag::NamedF32(name, power)) => {
+            assert_eq!(name, "burst_start");
+            assert!(*power > -10.0);
+        }
+        t => panic!("unexpected tag {:?}", t),
+    }
+    match &tags[1] {
+        (i, Tag::NamedUsize(name, len)) => {
+            assert_eq!(name, "burst_end");
+            assert_eq!(*i, 1000 + len);
+            assert!((2020..2040).contains(i), "{}", i);
+        }
+        t => panic!("unexpected tag {:?}", t),
+    }
+}
+
+#[test]
+fn cfar() -> Result<()> {
+    let fft_size = 256;
+    let frames = 10;
+
+    // exponentially distributed noise with two strong bins
+    let mut input = Vec::new();
+    for _ in 0..frames {
+        let mut frame: Vec<f32> = (0..fft_size)
+            .map(|_| -rand::random::<f32>().max(f32::MIN_POSITIVE).ln())
+            .collect();
+        frame[40] = 1e4;
+        frame[200] = 1e3;
+        frame[201] = 2e3;
+        input.extend(frame);
+    }
+
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(input).build());
+    let cfar = fg.add_block(CfarBuilder::new(fft_size).pfa(1e-9).build());
+    let snk = fg.add_block(MessageSink::new());
+
+    fg.connect_stream(src, "out", cfar, "in")?;
+    fg.connect_message(cfar, "detections", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    // adjacent bins are merged into one detection
+    assert_eq!(
+        fg.kernel::<MessageSink>(snk).unwrap().received(),
+        2 * frames as u64
+    );
+    Ok(())
+}
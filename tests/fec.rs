@@ -0,0 +1,72 @@
+use futuredsp::fec::{ConvolutionalCode, Crc, ReedSolomon};
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::fec::{
+    ConvolutionalEncoder, CrcChecker, CrcGenerator, ReedSolomonDecoder, ReedSolomonEncoder,
+};
+use futuresdr::blocks::MessageBurst;
+use futuresdr::blocks::MessageSink;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+
+#[test]
+fn crc_loopback() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(MessageBurst::new(Pmt::Blob(b"futuresdr".to_vec()), 10));
+    let gen = fg.add_block(CrcGenerator::new(Crc::crc32()));
+    let check = fg.add_block(CrcChecker::new(Crc::crc32()));
+    let snk = fg.add_block(MessageSink::new());
+
+    fg.connect_message(src, "out", gen, "in")?;
+    fg.connect_message(gen, "out", check, "in")?;
+    fg.connect_message(check, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    assert_eq!(fg.kernel::<MessageSink>(snk).unwrap().received(), 10);
+    Ok(())
+}
+
+#[test]
+fn crc_drop_invalid() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let mut frame = b"futuresdr".to_vec();
+    Crc::crc16_ccitt().append(&mut frame);
+    frame[0] ^= 1;
+
+    let src = fg.add_block(MessageBurst::new(Pmt::Blob(frame), 10));
+    let check = fg.add_block(CrcChecker::new(Crc::crc16_ccitt()));
+    let snk = fg.add_block(MessageSink::new());
+
+    fg.connect_message(src, "out", check, "in")?;
+    fg.connect_message(check, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    assert_eq!(fg.kernel::<MessageSink>(snk).unwrap().received(), 0);
+    Ok(())
+}
+
+#[test]
+fn reed_solomon_loopback() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let data: Vec<u8> = (0..500).map(|i| i as u8).collect();
+    let src = fg.add_block(MessageBurst::new(Pmt::Blob(data), 5));
+    let enc = fg.add_block(ReedSolomonEncoder::new(ReedSolomon::rs_255_223()));
+    let dec = fg.add_block(ReedSolomonDecoder::new(ReedSolomon::rs_255_223()));
+    let conv = fg.add_block(ConvolutionalEncoder::new(ConvolutionalCode::k7_rate_1_2()));
+    let snk = fg.add_block(MessageSink::new());
+
+    fg.connect_message(src, "out", enc, "in")?;
+    fg.connect_message(enc, "out", dec, "in")?;
+    fg.connect_message(dec, "out", conv, "in")?;
+    fg.connect_message(conv, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    assert_eq!(fg.kernel::<MessageSink>(snk).unwrap().received(), 5);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::Fft;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::buffer::circular::Circular;
+use futuresdr::runtime::buffer::slab::Slab;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Runtime;
+
+/// Three frames of ones plus a partial frame, which is dropped.
+fn input() -> Vec<Complex32> {
+    vec![Complex32::new(1.0, 0.0); 3 * 2048 + 100]
+}
+
+fn check(output: &[Complex32]) {
+    assert_eq!(output.len(), 3 * 2048);
+    for frame in output.chunks_exact(2048) {
+        assert!((frame[0].re - 2048.0).abs() < 1e-2);
+        assert!(frame[1..].iter().all(|x| x.norm() < 1e-2));
+    }
+}
+
+#[test]
+fn fft_mocker() {
+    let mut mocker = Mocker::new(Fft::new());
+    mocker.input(0, input());
+    mocker.init_output::<Complex32>(0, 4 * 2048);
+    mocker.run();
+    check(&mocker.output::<Complex32>(0));
+}
+
+#[test]
+fn fft_small_circular_buffer() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input()).build());
+    let fft = fg.add_block(Fft::new());
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+
+    // buffers are smaller than a frame and have to grow
+    fg.connect_stream_with_type(src, "out", fft, "in", Circular::with_size(4096))?;
+    fg.connect_stream_with_type(fft, "out", snk, "in", Circular::with_size(4096))?;
+
+    fg = Runtime::new().run(fg)?;
+
+    check(fg.kernel::<VectorSink<Complex32>>(snk).unwrap().items());
+    Ok(())
+}
+
+#[test]
+fn fft_small_slab_buffer() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input()).build());
+    let fft = fg.add_block(Fft::new());
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+
+    fg.connect_stream_with_type(src, "out", fft, "in", Slab::with_size(4096))?;
+    fg.connect_stream_with_type(fft, "out", snk, "in", Slab::with_size(4096))?;
+
+    fg = Runtime::new().run(fg)?;
+
+    check(fg.kernel::<VectorSink<Complex32>>(snk).unwrap().items());
+    Ok(())
+}
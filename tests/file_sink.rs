@@ -0,0 +1,138 @@
+mod common;
+use common::tmp;
+
+use futuresdr::blocks::FileSink;
+use futuresdr::blocks::FileSinkBuilder;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use std::path::PathBuf;
+
+fn tmp_dir(name: &str) -> PathBuf {
+    let dir = tmp(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Reads the `u32` items of all files in a directory, ordered by name.
+fn read_dir(dir: &PathBuf) -> Vec<Vec<u32>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .map(|f| {
+            std::fs::read(f)
+                .unwrap()
+                .chunks_exact(4)
+                .map(|b| u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn file_sink() {
+    let dir = tmp_dir("file-sink");
+    let path = dir.join("out.bin");
+
+    let mut mocker = Mocker::new(FileSink::<u32>::new(path.to_str().unwrap()));
+    mocker.input(0, (0..100u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+    mocker.deinit();
+
+    assert!(path.exists());
+    assert_eq!(read_dir(&dir), vec![(0..100).collect::<Vec<u32>>()]);
+}
+
+#[test]
+fn file_sink_max_size() {
+    let dir = tmp_dir("file-sink-max-size");
+    let sink = FileSinkBuilder::<u32>::new(dir.join("out.bin").to_str().unwrap())
+        .max_size(400)
+        .build();
+
+    let mut mocker = Mocker::new(sink);
+    mocker.input(0, (0..250u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+    mocker.deinit();
+
+    let files = read_dir(&dir);
+    assert_eq!(
+        files,
+        vec![
+            (0..100).collect::<Vec<u32>>(),
+            (100..200).collect(),
+            (200..250).collect(),
+        ]
+    );
+
+    let name = std::fs::read_dir(&dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .file_name();
+    let name = name.to_str().unwrap();
+    assert!(name.starts_with("out_20"));
+    assert!(name.ends_with(".bin"));
+}
+
+#[test]
+fn file_sink_triggered() {
+    let dir = tmp_dir("file-sink-triggered");
+    let sink = FileSinkBuilder::<u32>::new(dir.join("out.bin").to_str().unwrap())
+        .triggered(true)
+        .pre_trigger(10)
+        .post_trigger(20)
+        .build();
+
+    let mut mocker = Mocker::new(sink);
+    mocker.input(0, (0..50u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+    assert!(read_dir(&dir).is_empty());
+
+    // detection event
+    mocker.call_handler(0, Pmt::VecF32(vec![1.0]));
+    mocker.append_input(0, (50..100u32).collect::<Vec<u32>>());
+    mocker.run();
+
+    mocker.call_handler(0, Pmt::Null);
+    mocker.append_input(0, (100..150u32).collect::<Vec<u32>>());
+    mocker.run();
+    mocker.deinit();
+
+    assert_eq!(
+        read_dir(&dir),
+        vec![(40..70).collect::<Vec<u32>>(), (90..120).collect()]
+    );
+}
+
+#[test]
+fn file_sink_start_stop() {
+    let dir = tmp_dir("file-sink-start-stop");
+    let sink = FileSinkBuilder::<u32>::new(dir.join("out.bin").to_str().unwrap())
+        .triggered(true)
+        .build();
+
+    let mut mocker = Mocker::new(sink);
+    mocker.input(0, (0..10u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+
+    mocker.call_handler(0, Pmt::Null);
+    mocker.append_input(0, (10..20u32).collect::<Vec<u32>>());
+    mocker.run();
+
+    mocker.call_handler(1, Pmt::Null);
+    mocker.append_input(0, (20..30u32).collect::<Vec<u32>>());
+    mocker.run();
+    mocker.deinit();
+
+    assert_eq!(read_dir(&dir), vec![(10..20).collect::<Vec<u32>>()]);
+}
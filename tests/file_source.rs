@@ -0,0 +1,115 @@
+mod common;
+use common::tmp;
+
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::FileSource;
+use futuresdr::blocks::FileSourceBuilder;
+use futuresdr::blocks::SampleFormat;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+
+/// Writes the numbers 0..100 as `u32` to a temporary file.
+fn counter_file(name: &str) -> String {
+    let path = tmp(name);
+    let data: Vec<u8> = (0..100u32).flat_map(|x| x.to_ne_bytes()).collect();
+    std::fs::write(&path, data).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn run(mut mocker: Mocker, n: usize) -> Vec<u32> {
+    mocker.init_output::<u32>(0, n);
+    mocker.init();
+    mocker.run();
+    mocker.output::<u32>(0)
+}
+
+#[test]
+fn file_source() {
+    let path = counter_file("file-source");
+    let output = run(Mocker::new(FileSource::<u32>::new(&path)), 200);
+    assert_eq!(output, (0..100).collect::<Vec<u32>>());
+}
+
+#[test]
+fn file_source_offset_length() {
+    let path = counter_file("file-source-offset-length");
+    let src = FileSourceBuilder::<u32>::new(&path)
+        .offset(10)
+        .length(20)
+        .build();
+    let output = run(Mocker::new(src), 200);
+    assert_eq!(output, (10..30).collect::<Vec<u32>>());
+}
+
+#[test]
+fn file_source_repeat() {
+    let path = counter_file("file-source-repeat");
+    let src = FileSourceBuilder::<u32>::new(&path)
+        .offset(95)
+        .repeat(true)
+        .build();
+    let output = run(Mocker::new(src), 12);
+    assert_eq!(output, vec![95, 96, 97, 98, 99, 95, 96, 97, 98, 99, 95, 96]);
+}
+
+#[test]
+fn file_source_seek() {
+    let path = counter_file("file-source-seek");
+    let mut mocker = Mocker::new(FileSource::<u32>::new(&path));
+    mocker.init_output::<u32>(0, 10);
+    mocker.init();
+    assert_eq!(mocker.call_handler(0, Pmt::U64(50)), Pmt::U64(50));
+    mocker.run();
+    assert_eq!(mocker.output::<u32>(0), (50..60).collect::<Vec<u32>>());
+    assert_eq!(mocker.call_handler(0, Pmt::Null), Pmt::U64(60));
+}
+
+#[test]
+fn file_source_cu8() {
+    let path = tmp("file-source-cu8");
+    std::fs::write(&path, [0u8, 255, 128, 127, 0]).unwrap();
+
+    let src = FileSourceBuilder::<Complex32>::new(path.to_str().unwrap())
+        .format(SampleFormat::Cu8)
+        .build();
+    let mut mocker = Mocker::new(src);
+    mocker.init_output::<Complex32>(0, 10);
+    mocker.init();
+    mocker.run();
+
+    // the trailing partial sample is dropped
+    let output = mocker.output::<Complex32>(0);
+    assert_eq!(output.len(), 2);
+    assert_eq!(output[0], Complex32::new(-1.0, 1.0));
+    assert!((output[1].re - 0.5 / 127.5).abs() < 1e-6);
+    assert!((output[1].im + 0.5 / 127.5).abs() < 1e-6);
+}
+
+#[test]
+fn file_source_ci16() {
+    let path = tmp("file-source-ci16");
+    let data: Vec<u8> = [16384i16, -32768]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect();
+    std::fs::write(&path, data).unwrap();
+
+    let src = FileSourceBuilder::<Complex32>::new(path.to_str().unwrap())
+        .format(SampleFormat::Ci16)
+        .build();
+    let mut mocker = Mocker::new(src);
+    mocker.init_output::<Complex32>(0, 10);
+    mocker.init();
+    mocker.run();
+    assert_eq!(
+        mocker.output::<Complex32>(0),
+        vec![Complex32::new(0.5, -1.0)]
+    );
+}
+
+#[test]
+fn file_source_missing_file() {
+    let mut src = FileSource::<u32>::new(tmp("file-source-missing").to_str().unwrap());
+    assert!(block_on(src.init()).is_err());
+}
@@ -1,4 +1,5 @@
 use futuresdr::anyhow::Result;
+use futuresdr::blocks::ArbitraryResamplerBuilder;
 use futuresdr::blocks::FirBuilder;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
@@ -33,3 +34,65 @@ fn fir_f32() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn fir_overlap_save() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let orig: Vec<f32> = (0..10000).map(|i| (i as f32 * 0.1).sin()).collect();
+    let taps: Vec<f32> = (0..500).map(|i| 1.0 / (1.0 + i as f32)).collect();
+
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(orig.clone()).build());
+    let fir = fg.add_block(FirBuilder::new::<f32, f32, _>(taps.clone()));
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(src, "out", fir, "in")?;
+    fg.connect_stream(fir, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<f32>>(snk).unwrap();
+    let v = snk.items();
+
+    assert_eq!(v.len(), orig.len() - taps.len() + 1);
+    for (k, have) in v.iter().enumerate() {
+        let want: f32 = (0..taps.len())
+            .map(|t| orig[k + t] * taps[taps.len() - 1 - t])
+            .sum();
+        assert!((have - want).abs() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn arbitrary_resampler() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let rate = 44.1e3 / 1e6;
+    let n = 100_000;
+    let orig: Vec<f32> = (0..n)
+        .map(|i| (2.0 * std::f32::consts::PI * 0.001 * i as f32).sin())
+        .collect();
+
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(orig).build());
+    let resamp = fg.add_block(ArbitraryResamplerBuilder::new(rate).build::<f32>());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(src, "out", resamp, "in")?;
+    fg.connect_stream(resamp, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<f32>>(snk).unwrap();
+    let v = snk.items();
+
+    // the filter delay is lost at the end of the stream
+    let expected = n as f64 * rate;
+    assert!((v.len() as f64 - expected).abs() < 50.0, "len {}", v.len());
+    // tone with unit amplitude at the new rate
+    let peak = v[v.len() / 4..].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+    assert!((peak - 1.0).abs() < 0.01, "peak {}", peak);
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use futuresdr::anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::AccessCodeDetectorBuilder;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::MessageSink;
+use futuresdr::blocks::PduToTaggedStream;
+use futuresdr::blocks::PreambleCorrelatorBuilder;
+use futuresdr::blocks::TaggedStreamToPduBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::Tag;
+
+const PREAMBLE_LEN: usize = 64;
+const PAYLOAD_LEN: usize = 100;
+
+fn preamble() -> Vec<Complex32> {
+    // Zadoff-Chu sequence
+    (0..PREAMBLE_LEN)
+        .map(|n| {
+            let n = n as f32;
+            Complex32::from_polar(
+                1.0,
+                -std::f32::consts::PI * 7.0 * n * n / PREAMBLE_LEN as f32,
+            )
+        })
+        .collect()
+}
+
+fn burst(phase: f32, freq: f32) -> Vec<Complex32> {
+    let mut v = preamble();
+    v.extend((0..PAYLOAD_LEN).map(|i| {
+        let i = i as f32;
+        Complex32::new((i * 0.37).sin(), (i * 1.3).cos())
+    }));
+    v.iter()
+        .enumerate()
+        .map(|(i, x)| x * Complex32::from_polar(1.0, phase + freq * i as f32))
+        .collect()
+}
+
+fn named_f32(tags: &[(usize, Tag)], name: &str) -> Vec<(usize, f32)> {
+    tags.iter()
+        .filter_map(|(i, t)| match t {
+            Tag::NamedF32(n, v) if n == name => Some((*i, *v)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn preamble_correlator() {
+    let mut input = vec![Complex32::new(0.0, 0.0); 100];
+    input.extend(burst(0.5, 0.01));
+    input.extend(vec![Complex32::new(0.0, 0.0); 100]);
+
+    let mut mocker = Mocker::new(PreambleCorrelatorBuilder::new(preamble()).build());
+    mocker.input(0, input.clone());
+    mocker.init_output::<Complex32>(0, input.len());
+    mocker.run();
+
+    let output = mocker.output::<Complex32>(0);
+    assert_eq!(output, input);
+
+    let tags: Vec<(usize, Tag)> = mocker
+        .output_tags::<Complex32>(0)
+        .into_iter()
+        .map(|t| (t.index, t.tag))
+        .collect();
+
+    let corr = named_f32(&tags, "corr_est");
+    assert_eq!(corr.len(), 1);
+    assert_eq!(corr[0].0, 100);
+    assert!(corr[0].1 > 0.95);
+
+    let time = named_f32(&tags, "time_est");
+    assert!(time[0].1.abs() < 0.5);
+    let phase = named_f32(&tags, "phase_est");
+    assert!((phase[0].1 - 0.5).abs() < 0.05);
+    let freq = named_f32(&tags, "freq_est");
+    assert!((freq[0].1 - 0.01).abs() < 1e-3);
+}
+
+#[test]
+fn access_code_detector() {
+    let code = vec![1, 1, 1, 0, 1, 0, 1, 1, 1, 0, 0, 1, 0, 0, 0, 0];
+    let mut input: Vec<u8> = (0..200).map(|i| ((i * 7 + i / 5) % 3 == 0) as u8).collect();
+    let mut corrupted = code.clone();
+    corrupted[3] ^= 1;
+    input.splice(50..50 + code.len(), corrupted);
+
+    let mut mocker = Mocker::new(
+        AccessCodeDetectorBuilder::new(code.clone())
+            .max_errors(1)
+            .build(),
+    );
+    mocker.input(0, input.clone());
+    mocker.init_output::<u8>(0, input.len());
+    mocker.run();
+
+    assert_eq!(mocker.output::<u8>(0), input);
+    let tags = mocker.output_tags::<u8>(0);
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].index, 50 + code.len());
+    assert!(matches!(&tags[0].tag, Tag::NamedUsize(n, 1) if n == "access_code"));
+}
+
+#[test]
+fn tagged_stream_to_pdu() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let mut input = Vec::new();
+    for _ in 0..3 {
+        input.extend(vec![Complex32::new(0.0, 0.0); 50]);
+        input.extend(burst(-1.0, 0.0));
+    }
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input).build());
+    let corr = fg.add_block(PreambleCorrelatorBuilder::new(preamble()).build());
+    let pdu = fg.add_block(
+        TaggedStreamToPduBuilder::<Complex32>::new("corr_est")
+            .fixed_length(PREAMBLE_LEN + PAYLOAD_LEN)
+            .build(),
+    );
+    let snk = fg.add_block(MessageSink::new());
+
+    fg.connect_stream(src, "out", corr, "in")?;
+    fg.connect_stream(corr, "out", pdu, "in")?;
+    fg.connect_message(pdu, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    assert_eq!(fg.kernel::<MessageSink>(snk).unwrap().received(), 3);
+    Ok(())
+}
+
+#[test]
+fn pdu_to_tagged_stream() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(PduToTaggedStream::<u32>::new("packet_len"));
+    let head = fg.add_block(Head::<u32>::new(30));
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+
+    fg.connect_stream(src, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    fg = block_on(async {
+        let (task, mut handle) = rt.start(fg).await;
+        for i in 0..3u32 {
+            let bytes: Vec<u8> = (0..10u32)
+                .flat_map(|x| (10 * i + x).to_ne_bytes())
+                .collect();
+            handle.call(src, 0, Pmt::Blob(bytes)).await?;
+        }
+        task.await
+    })?;
+
+    let snk = fg.kernel::<VectorSink<u32>>(snk).unwrap();
+    assert_eq!(snk.items(), &(0..30).collect::<Vec<u32>>());
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use futuredsp::iirdes;
+use futuredsp::iirdes::BandType;
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::IirBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+#[test]
+fn iir_sos_lowpass() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    // tone in the passband plus tone in the stopband
+    let n = 4000;
+    let orig: Vec<f32> = (0..n)
+        .map(|i| {
+            let i = i as f32;
+            (2.0 * std::f32::consts::PI * 0.02 * i).sin()
+                + (2.0 * std::f32::consts::PI * 0.3 * i).sin()
+        })
+        .collect();
+    let sections = iirdes::elliptic(6, 0.1, 80.0, BandType::Lowpass(0.05));
+
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(orig).build());
+    let iir = fg.add_block(IirBuilder::new_sos::<f32>(&sections));
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(src, "out", iir, "in")?;
+    fg.connect_stream(iir, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<f32>>(snk).unwrap();
+    let v = snk.items();
+    assert_eq!(v.len(), n);
+
+    // after settling, only the passband tone with unit amplitude is left
+    let peak = v[n / 2..].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+    assert!((peak - 1.0).abs() < 0.02, "peak {}", peak);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+mod common;
+use common::{connect, free_port};
+
+use futuresdr::anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::BlobToTcp;
+use futuresdr::blocks::BlobToUdp;
+use futuresdr::blocks::TcpToBlob;
+use futuresdr::blocks::TcpToBlobBuilder;
+use futuresdr::blocks::UdpToBlob;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+use std::io::Read;
+use std::io::Write;
+use std::net::UdpSocket;
+use std::time::Duration;
+
+fn frame(data: &[u8]) -> Vec<u8> {
+    let mut f = (data.len() as u32).to_be_bytes().to_vec();
+    f.extend_from_slice(data);
+    f
+}
+
+#[test]
+fn udp_to_blob_to_udp() -> Result<()> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    socket.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let port = free_port();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(UdpToBlob::new(format!("127.0.0.1:{}", port)));
+    let snk = fg.add_block(BlobToUdp::new(socket.local_addr()?.to_string()));
+    fg.connect_message(src, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = block_on(rt.start(fg));
+
+    let tx = UdpSocket::bind("127.0.0.1:0")?;
+    let mut buf = [0u8; 100];
+    for payload in [&b"hello"[..], &[1, 2, 3]] {
+        tx.send_to(payload, format!("127.0.0.1:{}", port))?;
+        let n = socket.recv(&mut buf)?;
+        assert_eq!(&buf[..n], payload);
+    }
+
+    block_on(handle.terminate())?;
+    block_on(task)?;
+    Ok(())
+}
+
+#[test]
+fn tcp_to_blob_to_tcp() -> Result<()> {
+    let src_port = free_port();
+    let snk_port = free_port();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(TcpToBlob::new(format!("127.0.0.1:{}", src_port)));
+    let snk = fg.add_block(BlobToTcp::new(format!("127.0.0.1:{}", snk_port)));
+    fg.connect_message(src, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = block_on(rt.start(fg));
+
+    let mut rx = connect(snk_port);
+    // wait for the sink to accept the client
+    std::thread::sleep(Duration::from_millis(200));
+
+    let mut tx = connect(src_port);
+    // a PDU split over two writes and two PDUs in one write
+    let mut data = frame(b"hello");
+    tx.write_all(&data[..3])?;
+    tx.flush()?;
+    std::thread::sleep(Duration::from_millis(50));
+    tx.write_all(&data[3..])?;
+    data = frame(&[1, 2, 3]);
+    data.extend(frame(&[]));
+    tx.write_all(&data)?;
+
+    let mut expected = frame(b"hello");
+    expected.extend(frame(&[1, 2, 3]));
+    expected.extend(frame(&[]));
+    let mut received = vec![0; expected.len()];
+    rx.read_exact(&mut received)?;
+    assert_eq!(received, expected);
+
+    // the source waits for the next client
+    drop(tx);
+    let mut tx = connect(src_port);
+    tx.write_all(&frame(b"again"))?;
+    let mut received = vec![0; 9];
+    rx.read_exact(&mut received)?;
+    assert_eq!(received, frame(b"again"));
+
+    block_on(handle.terminate())?;
+    block_on(task)?;
+    Ok(())
+}
+
+#[test]
+fn tcp_to_blob_max_pdu_size() -> Result<()> {
+    let src_port = free_port();
+    let snk_port = free_port();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(
+        TcpToBlobBuilder::new(format!("127.0.0.1:{}", src_port))
+            .max_pdu_size(8)
+            .build(),
+    );
+    let snk = fg.add_block(BlobToTcp::new(format!("127.0.0.1:{}", snk_port)));
+    fg.connect_message(src, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = block_on(rt.start(fg));
+
+    let mut rx = connect(snk_port);
+    // wait for the sink to accept the client
+    std::thread::sleep(Duration::from_millis(200));
+
+    // the connection is dropped, without waiting for the 4 GiB PDU
+    let mut tx = connect(src_port);
+    tx.write_all(&u32::MAX.to_be_bytes())?;
+    let mut buf = [0u8; 1];
+    assert!(matches!(tx.read(&mut buf), Ok(0) | Err(_)));
+
+    let mut tx = connect(src_port);
+    tx.write_all(&frame(b"8 bytes!"))?;
+    let mut received = vec![0; 12];
+    rx.read_exact(&mut received)?;
+    assert_eq!(received, frame(b"8 bytes!"));
+
+    block_on(handle.terminate())?;
+    block_on(task)?;
+    Ok(())
+}
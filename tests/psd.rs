@@ -0,0 +1,117 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::PsdAveraging;
+use futuresdr::blocks::PsdBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+fn run(orig: Vec<Complex32>, psd: PsdBuilder) -> Result<Vec<f32>> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(orig).build());
+    let psd = fg.add_block(psd.build());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(src, "out", psd, "in")?;
+    fg.connect_stream(psd, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<f32>>(snk).unwrap();
+    Ok(snk.items().clone())
+}
+
+fn tone(n: usize, bin: f32, fft_size: usize) -> Vec<Complex32> {
+    (0..n)
+        .map(|i| {
+            Complex32::from_polar(
+                1.0,
+                2.0 * std::f32::consts::PI * bin * i as f32 / fft_size as f32,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn psd_tone() -> Result<()> {
+    let fft_size = 256;
+    // 50% overlap: 9 frames, averaged in groups of 4
+    let v = run(
+        tone(10 * fft_size / 2, 32.0, fft_size),
+        PsdBuilder::new(fft_size)
+            .averaging(PsdAveraging::Linear)
+            .decimation(4),
+    )?;
+    assert_eq!(v.len(), 2 * fft_size);
+
+    for frame in v.chunks(fft_size) {
+        // centered at DC, unit power in dB
+        let peak = frame.iter().enumerate().fold(
+            (0, f32::MIN),
+            |m, (i, x)| if *x > m.1 { (i, *x) } else { m },
+        );
+        assert_eq!(peak.0, fft_size / 2 + 32);
+        assert!(peak.1.abs() < 0.1, "peak {}", peak.1);
+        assert!(frame[fft_size / 2 - 32] < -100.0);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn psd_max_hold_waterfall() -> Result<()> {
+    let fft_size = 256;
+    // tone jumps from bin 10 to bin -40 halfway through
+    let mut orig = tone(8 * fft_size, 10.0, fft_size);
+    orig.extend(tone(8 * fft_size, -40.0, fft_size));
+    let v = run(
+        orig,
+        PsdBuilder::new(fft_size)
+            .overlap(0)
+            .averaging(PsdAveraging::MaxHold)
+            .db(false)
+            .waterfall(64),
+    )?;
+    assert_eq!(v.len(), 16 * 64);
+
+    // four bins per output bin, DC at 32
+    let last = &v[15 * 64..];
+    assert!((last[(128 + 10) / 4] - 1.0).abs() < 1e-3);
+    assert!((last[(128 - 40) / 4] - 1.0).abs() < 1e-3);
+    let first = &v[..64];
+    assert!(first[(128 - 40) / 4] < 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn psd_silence_is_finite() -> Result<()> {
+    let fft_size = 64;
+    let v = run(
+        vec![Complex32::new(0.0, 0.0); 4 * fft_size],
+        PsdBuilder::new(fft_size).overlap(0),
+    )?;
+    assert_eq!(v.len(), 4 * fft_size);
+    assert!(v.iter().all(|x| x.is_finite()));
+    Ok(())
+}
+
+#[test]
+fn psd_fft_size_larger_than_buffer() -> Result<()> {
+    // 64 KiB frames, larger than the default 32 KiB buffer
+    let fft_size = 8192;
+    let v = run(
+        tone(4 * fft_size, 100.0, fft_size),
+        PsdBuilder::new(fft_size).overlap(0),
+    )?;
+    assert_eq!(v.len(), 4 * fft_size);
+
+    for frame in v.chunks(fft_size) {
+        assert!(frame[fft_size / 2 + 100].abs() < 0.1);
+    }
+
+    Ok(())
+}
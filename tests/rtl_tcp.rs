@@ -0,0 +1,101 @@
+use futuresdr::blocks::RtlTcpSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Tag;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::time::Duration;
+
+fn command(s: &mut TcpStream) -> (u8, u32) {
+    let mut c = [0u8; 5];
+    s.read_exact(&mut c).unwrap();
+    (c[0], u32::from_be_bytes([c[1], c[2], c[3], c[4]]))
+}
+
+#[test]
+fn rtl_tcp_source() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // stand-in for rtl_tcp with an R820T tuner
+    let server = std::thread::spawn(move || {
+        let (mut s, _) = listener.accept().unwrap();
+        s.write_all(b"RTL0\x00\x00\x00\x05\x00\x00\x00\x1d")
+            .unwrap();
+        let mut commands: Vec<(u8, u32)> = (0..4).map(|_| command(&mut s)).collect();
+        // a sample split over two writes
+        s.write_all(&[0, 255, 255]).unwrap();
+        s.flush().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        s.write_all(&[0]).unwrap();
+        commands.push(command(&mut s));
+        commands
+    });
+
+    let mut mocker = Mocker::new(
+        RtlTcpSourceBuilder::new(addr.to_string())
+            .frequency(100e6)
+            .sample_rate(2.4e6)
+            .gain(49.6)
+            .build(),
+    );
+    mocker.init_output::<Complex32>(0, 10);
+    mocker.init();
+    std::thread::sleep(Duration::from_millis(20));
+    mocker.run();
+    std::thread::sleep(Duration::from_millis(100));
+    mocker.run();
+
+    assert_eq!(
+        mocker.output::<Complex32>(0),
+        vec![Complex32::new(-1.0, 1.0), Complex32::new(1.0, -1.0)]
+    );
+    let tags: Vec<(usize, Tag)> = mocker
+        .output_tags::<Complex32>(0)
+        .into_iter()
+        .map(|t| (t.index, t.tag))
+        .collect();
+    assert!(matches!(&tags[..], [
+        (0, Tag::NamedF64(r, rate)),
+        (0, Tag::NamedF64(f, freq)),
+    ] if r == "sample_rate" && *rate == 2.4e6 && f == "freq" && *freq == 100e6));
+
+    assert_eq!(
+        mocker.call_handler(0, Pmt::U32(101_000_000)),
+        Pmt::Double(101e6)
+    );
+    assert_eq!(
+        mocker.call_handler(3, Pmt::Null),
+        Pmt::String("R820t".to_string())
+    );
+    assert_eq!(mocker.call_handler(4, Pmt::Null), Pmt::U32(29));
+
+    let commands = server.join().unwrap();
+    assert_eq!(
+        commands,
+        vec![
+            (0x02, 2_400_000),
+            (0x01, 100_000_000),
+            (0x03, 1),
+            (0x04, 496),
+            (0x01, 101_000_000),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn rtl_tcp_source_wrong_header() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut s, _) = listener.accept().unwrap();
+        s.write_all(b"HTTP/1.1 200 OK\r\n").unwrap();
+    });
+
+    let mut mocker = Mocker::new(RtlTcpSourceBuilder::new(addr.to_string()).build());
+    mocker.init();
+}
@@ -1,14 +1,23 @@
 use std::iter::repeat_with;
+use std::time::Duration;
 
 use futuresdr::anyhow::Result;
+use futuresdr::async_io::block_on;
 use futuresdr::blocks::Copy;
 use futuresdr::blocks::Head;
+use futuresdr::blocks::MessageSink;
+use futuresdr::blocks::MessageSinkBuilder;
+use futuresdr::blocks::MessageSourceBuilder;
+use futuresdr::blocks::NullSink;
 use futuresdr::blocks::NullSource;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
 use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
 use futuresdr::runtime::Runtime;
+use futuresdr::runtime::StreamIoBuilder;
 
 #[test]
 fn flowgraph() -> Result<()> {
@@ -109,3 +118,58 @@ fn fg_rand_vec_multi_snk() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn stream_type_mismatch() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(NullSource::<Complex32>::new());
+    let f64_snk = fg.add_block(NullSink::<f64>::new());
+    let u64_snk = fg.add_block(NullSink::<u64>::new());
+    let c32_snk = fg.add_block(NullSink::<Complex32>::new());
+
+    // same item size, but different types
+    let err = fg.connect_stream(src, "out", f64_snk, "in").unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("Complex<f32>"), "{}", msg);
+    assert!(msg.contains("f64"), "{}", msg);
+    assert!(fg.connect_stream(src, "out", u64_snk, "in").is_err());
+    fg.connect_stream(src, "out", c32_snk, "in")?;
+
+    Ok(())
+}
+
+#[test]
+fn stream_untyped_port() {
+    let sio = StreamIoBuilder::new()
+        .add_input("in", 8)
+        .add_typed_output::<f64>("out")
+        .build();
+    assert_eq!(sio.input_ref(0).type_name(), None);
+    assert_eq!(sio.output_ref(0).type_name(), Some("f64"));
+    assert_eq!(sio.output_ref(0).description().item_size, 8);
+}
+
+#[test]
+fn terminate() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    // neither the stream nor the message source ever finishes
+    let src = fg.add_block(NullSource::<f32>::new());
+    let snk = fg.add_block(NullSink::<f32>::new());
+    fg.connect_stream(src, "out", snk, "in")?;
+    let msg_src =
+        fg.add_block(MessageSourceBuilder::new(Pmt::Null, Duration::from_millis(10)).build());
+    let msg_snk = fg.add_block(MessageSinkBuilder::new().build());
+    fg.connect_message(msg_src, "out", msg_snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = block_on(rt.start(fg));
+    std::thread::sleep(Duration::from_millis(50));
+    block_on(handle.terminate())?;
+    let fg = block_on(task)?;
+
+    let snk = fg.kernel::<MessageSink>(msg_snk).unwrap();
+    assert!(snk.received() > 0);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+mod common;
+use common::tmp;
+
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::sigmf::Annotation;
+use futuresdr::blocks::sigmf::Capture;
+use futuresdr::blocks::sigmf::Datatype;
+use futuresdr::blocks::sigmf::Meta;
+use futuresdr::blocks::sigmf::SigMfSinkBuilder;
+use futuresdr::blocks::sigmf::SigMfSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::ItemTag;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Tag;
+use std::path::PathBuf;
+
+fn samples() -> Vec<Complex32> {
+    (0..1000)
+        .map(|i| Complex32::new(i as f32, -i as f32))
+        .collect()
+}
+
+fn record(path: &PathBuf) {
+    let mut mocker = Mocker::new(
+        SigMfSinkBuilder::<Complex32>::new(path)
+            .frequency(100e6)
+            .description("test")
+            .build(),
+    );
+    mocker.input_with_tags(
+        0,
+        samples(),
+        vec![
+            ItemTag {
+                index: 0,
+                tag: Tag::NamedF64("sample_rate".to_string(), 2e6),
+            },
+            ItemTag {
+                index: 500,
+                tag: Tag::NamedF64("freq".to_string(), 101e6),
+            },
+        ],
+    );
+    mocker.init();
+    mocker.call_handler(
+        0,
+        Pmt::String(
+            r#"{"core:sample_start": 600, "core:sample_count": 50, "core:label": "burst"}"#
+                .to_string(),
+        ),
+    );
+    mocker.run();
+    mocker.call_handler(0, Pmt::String("end".to_string()));
+    mocker.deinit();
+}
+
+#[test]
+fn sigmf_sink() {
+    let path = tmp("sigmf-sink");
+    record(&path);
+
+    let data = std::fs::read(path.with_extension("sigmf-data")).unwrap();
+    assert_eq!(data.len(), 1000 * 8);
+
+    let json = std::fs::read_to_string(path.with_extension("sigmf-meta")).unwrap();
+    let meta: Meta = serde_json::from_str(&json).unwrap();
+    assert_eq!(meta.global.datatype, Complex32::datatype());
+    assert_eq!(meta.global.sample_rate, Some(2e6));
+    assert_eq!(meta.global.description.as_deref(), Some("test"));
+    assert_eq!(
+        meta.captures,
+        vec![
+            Capture {
+                sample_start: 0,
+                frequency: Some(100e6),
+                datetime: None,
+            },
+            Capture {
+                sample_start: 500,
+                frequency: Some(101e6),
+                datetime: None,
+            },
+        ]
+    );
+    assert_eq!(
+        meta.annotations,
+        vec![
+            Annotation {
+                sample_start: 600,
+                sample_count: Some(50),
+                label: Some("burst".to_string()),
+                ..Annotation::default()
+            },
+            Annotation {
+                sample_start: 1000,
+                comment: Some("end".to_string()),
+                ..Annotation::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn sigmf_source() {
+    let path = tmp("sigmf-source");
+    record(&path);
+
+    let mut mocker = Mocker::new(SigMfSourceBuilder::<Complex32>::new(&path).build());
+    mocker.init_output::<Complex32>(0, 2000);
+    mocker.init();
+    mocker.run();
+
+    assert_eq!(mocker.output::<Complex32>(0), samples());
+
+    let tags: Vec<(usize, String, f64)> = mocker
+        .output_tags::<Complex32>(0)
+        .into_iter()
+        .map(|t| match t.tag {
+            Tag::NamedF64(k, v) => (t.index, k, v),
+            t => panic!("unexpected tag {:?}", t),
+        })
+        .collect();
+    assert_eq!(
+        tags,
+        vec![
+            (0, "sample_rate".to_string(), 2e6),
+            (0, "freq".to_string(), 100e6),
+            (500, "freq".to_string(), 101e6),
+        ]
+    );
+}
+
+#[test]
+fn sigmf_source_wrong_type() {
+    let path = tmp("sigmf-wrong-type");
+    record(&path);
+
+    let mut src = SigMfSourceBuilder::<f32>::new(&path).build();
+    let res = block_on(src.init());
+    assert!(res.is_err());
+}
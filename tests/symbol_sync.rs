@@ -0,0 +1,188 @@
+use futuredsp::firdes;
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::SymbolSyncBuilder;
+use futuresdr::blocks::TimingErrorDetector;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::Tag;
+use std::f32::consts::PI;
+
+fn bits(n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| if (i * 7 + i / 3) % 5 < 2 { 1.0 } else { -1.0 })
+        .collect()
+}
+
+fn raised_cosine(t: f32) -> f32 {
+    let beta = 0.5;
+    if t == 0.0 {
+        return 1.0;
+    }
+    let d = 1.0 - (2.0 * beta * t).powi(2);
+    if d.abs() < 1e-4 {
+        return PI / 4.0 * (PI * t).sin() / (PI * t);
+    }
+    (PI * t).sin() / (PI * t) * (PI * beta * t).cos() / d
+}
+
+/// Pulse shapes the symbols with a raised cosine at `sps` samples per symbol, starting at `tau`.
+fn shape(symbols: &[f32], sps: f32, tau: f32) -> Vec<f32> {
+    let n = (symbols.len() as f32 * sps) as usize;
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / sps - tau;
+            let k = t.round() as isize;
+            (k - 8..=k + 8)
+                .filter(|k| *k >= 0 && (*k as usize) < symbols.len())
+                .map(|k| symbols[k as usize] * raised_cosine(t - k as f32))
+                .sum()
+        })
+        .collect()
+}
+
+fn run<T: futuresdr::blocks::SymbolSyncSample + std::fmt::Debug + Sync>(
+    input: Vec<T>,
+    sps: f32,
+    ted: TimingErrorDetector,
+) -> Result<Vec<T>> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<T>::new(input).build());
+    let sync = fg.add_block(SymbolSyncBuilder::new(sps, ted).build::<T>());
+    let snk = fg.add_block(VectorSinkBuilder::<T>::new().build());
+
+    fg.connect_stream(src, "out", sync, "in")?;
+    fg.connect_stream(sync, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.kernel::<VectorSink<T>>(snk).unwrap();
+    Ok(snk.items().clone())
+}
+
+#[test]
+fn gardner_f32() -> Result<()> {
+    let n = 2000;
+    let v = run(shape(&bits(n), 3.3, 0.4), 3.3, TimingErrorDetector::Gardner)?;
+
+    assert!((v.len() as isize - n as isize).abs() < 5);
+    for s in &v[v.len() - 200..] {
+        assert!((s.abs() - 1.0).abs() < 0.15);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn mueller_muller_f32() -> Result<()> {
+    let n = 2000;
+    let v = run(
+        shape(&bits(n), 4.0, 0.3),
+        4.0,
+        TimingErrorDetector::MuellerMuller,
+    )?;
+
+    assert!((v.len() as isize - n as isize).abs() < 5);
+    for s in &v[v.len() - 200..] {
+        assert!((s.abs() - 1.0).abs() < 0.15);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn gardner_complex() -> Result<()> {
+    let n = 2000;
+    let b = bits(n);
+    let q: Vec<f32> = b.iter().rev().cloned().collect();
+    let input: Vec<Complex32> = shape(&b, 2.5, 0.2)
+        .iter()
+        .zip(shape(&q, 2.5, 0.2))
+        .map(|(i, q)| Complex32::new(*i, q))
+        .collect();
+
+    let v = run(input, 2.5, TimingErrorDetector::Gardner)?;
+
+    assert!((v.len() as isize - n as isize).abs() < 5);
+    for s in &v[v.len() - 200..] {
+        assert!((s.re.abs() - 1.0).abs() < 0.15);
+        assert!((s.im.abs() - 1.0).abs() < 0.15);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn polyphase_complex() -> Result<()> {
+    let n = 2000;
+    let sps = 4;
+    let nfilts = 32;
+    let taps = firdes::root_raised_cosine::<f32>(8, sps * nfilts, 0.35);
+    let b = bits(n);
+
+    // pulse shape at the oversampled rate and pick a fractional offset
+    let mut input = Vec::new();
+    let offset = 11;
+    for i in 0..n * sps {
+        let t = i * nfilts + offset;
+        let mut s = 0.0;
+        for (k, b) in b.iter().enumerate() {
+            let idx = t as isize - (k * sps * nfilts) as isize;
+            if idx >= 0 && (idx as usize) < taps.len() {
+                s += b * taps[idx as usize];
+            }
+        }
+        input.push(Complex32::new(s, 0.0));
+    }
+
+    let v = run(
+        input,
+        sps as f32,
+        TimingErrorDetector::Polyphase { taps, nfilts },
+    )?;
+
+    assert!((v.len() as isize - n as isize).abs() < 10);
+    let tail = &v[v.len() - 200..];
+    let mean = tail.iter().map(|x| x.re.abs()).sum::<f32>() / tail.len() as f32;
+    for s in tail {
+        assert!((s.re.abs() - mean).abs() < 0.15 * mean);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn symbol_tags() {
+    let n = 2000;
+    let sps = 4.0;
+    let tau = 0.3;
+
+    let mut mocker =
+        Mocker::new(SymbolSyncBuilder::new(sps, TimingErrorDetector::MuellerMuller).build::<f32>());
+    mocker.input(0, shape(&bits(n), sps, tau));
+    mocker.init_output::<f32>(0, n + 10);
+    mocker.init();
+    mocker.run();
+
+    let v = mocker.output::<f32>(0);
+    let tags = mocker.output_tags::<f32>(0);
+    assert_eq!(tags.len(), v.len());
+
+    for (k, t) in tags.iter().enumerate() {
+        assert_eq!(t.index, k);
+        let t = match &t.tag {
+            Tag::Data(Pmt::Double(t)) => *t as f32,
+            t => panic!("unexpected tag {:?}", t),
+        };
+        // once locked, symbol k is sampled at (k + tau) * sps in the input
+        if k >= v.len() - 200 {
+            assert!((t - (k as f32 + tau) * sps).abs() < 0.05 * sps);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+mod common;
+use common::{bytes, connect, free_port};
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::TcpSinkBuilder;
+use futuresdr::blocks::TcpSourceBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Runtime;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::time::Duration;
+
+#[test]
+fn tcp_source_client_reconnect() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let server = std::thread::spawn(move || {
+        // the first connection ends in the middle of an item, which is dropped
+        let (mut s, _) = listener.accept().unwrap();
+        let mut data = bytes(0..50);
+        data.extend([1, 2]);
+        s.write_all(&data[..101]).unwrap();
+        s.flush().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        s.write_all(&data[101..]).unwrap();
+        drop(s);
+
+        let (mut s, _) = listener.accept().unwrap();
+        s.write_all(&bytes(50..100)).unwrap();
+    });
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(
+        TcpSourceBuilder::<u32>::client(addr.to_string())
+            .backoff(Duration::from_millis(10), Duration::from_millis(50))
+            .build(),
+    );
+    let head = fg.add_block(Head::<u32>::new(100));
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+    server.join().unwrap();
+
+    let snk = fg.kernel::<VectorSink<u32>>(snk).unwrap();
+    assert_eq!(snk.items(), &(0..100).collect::<Vec<u32>>());
+    Ok(())
+}
+
+#[test]
+fn tcp_sink_client() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+
+    let mut mocker =
+        Mocker::new(TcpSinkBuilder::<u32>::client(listener.local_addr()?.to_string()).build());
+    mocker.input(0, (0..100u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+
+    let (mut s, _) = listener.accept()?;
+    let mut data = vec![0; 400];
+    s.read_exact(&mut data)?;
+    assert_eq!(data, bytes(0..100));
+    Ok(())
+}
+
+#[test]
+fn tcp_sink_multi_client() -> Result<()> {
+    let port = free_port();
+
+    let mut mocker = Mocker::new(TcpSinkBuilder::<u32>::new(port).multi_client(true).build());
+    mocker.init();
+
+    let mut clients: Vec<TcpStream> = (0..2).map(|_| connect(port)).collect();
+    std::thread::sleep(Duration::from_millis(100));
+
+    mocker.input(0, (0..100u32).collect::<Vec<u32>>());
+    mocker.run();
+
+    for c in clients.iter_mut() {
+        let mut data = vec![0; 400];
+        c.read_exact(&mut data)?;
+        assert_eq!(data, bytes(0..100));
+    }
+    Ok(())
+}
+
+#[test]
+fn tcp_sink_multi_client_stalled() -> Result<()> {
+    let port = free_port();
+    let chunk = 16384u32;
+    let chunks = 256u32;
+
+    let mut mocker = Mocker::new(TcpSinkBuilder::<u32>::new(port).multi_client(true).build());
+    mocker.input(0, Vec::<u32>::new());
+    mocker.init();
+
+    // never reads, so that its socket buffer fills up
+    let _stalled = connect(port);
+    let mut reader = connect(port);
+    std::thread::sleep(Duration::from_millis(100));
+    let reader = std::thread::spawn(move || {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        data
+    });
+
+    for i in 0..chunks {
+        mocker.append_input(0, (i * chunk..(i + 1) * chunk).collect::<Vec<u32>>());
+        mocker.run();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    drop(mocker);
+
+    assert_eq!(reader.join().unwrap(), bytes(0..chunks * chunk));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+#[should_panic(expected = "cannot read from")]
+fn tcp_source_socket_error() {
+    use std::os::unix::io::AsRawFd;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        // closing with a zero linger timeout resets the connection, once the
+        // source is connected and reading
+        let (s, _) = listener.accept().unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let linger = libc::linger {
+            l_onoff: 1,
+            l_linger: 0,
+        };
+        unsafe {
+            libc::setsockopt(
+                s.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_LINGER,
+                &linger as *const libc::linger as *const libc::c_void,
+                std::mem::size_of::<libc::linger>() as libc::socklen_t,
+            );
+        }
+    });
+
+    let mut mocker = Mocker::new(
+        TcpSourceBuilder::<u32>::client(addr.to_string())
+            .reconnect(false)
+            .build(),
+    );
+    mocker.init_output::<u32>(0, 10);
+    mocker.init();
+    mocker.run();
+    server.join().unwrap();
+}
@@ -0,0 +1,89 @@
+use futuresdr::blocks::UdpSinkBuilder;
+use futuresdr::blocks::UdpSourceBuilder;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Tag;
+use std::net::UdpSocket;
+use std::time::Duration;
+
+fn datagram(seq: u64, data: &[u32]) -> Vec<u8> {
+    let mut d = seq.to_be_bytes().to_vec();
+    d.extend(data.iter().flat_map(|x| x.to_ne_bytes()));
+    d
+}
+
+#[test]
+fn udp_sink() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let addr = socket.local_addr().unwrap();
+
+    let sink = UdpSinkBuilder::<u32>::new(addr.to_string())
+        .payload_size(42)
+        .sequence_header(true)
+        .build();
+    let mut mocker = Mocker::new(sink);
+    mocker.input(0, (0..25u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+
+    let mut buf = [0u8; 1500];
+    for (seq, data) in [(0, 0..10u32), (1, 10..20), (2, 20..25)] {
+        let n = socket.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], datagram(seq, &data.collect::<Vec<u32>>()));
+    }
+}
+
+#[test]
+fn udp_sink_receiver_down() {
+    // nobody listens on the port, so the datagrams are refused
+    let addr = UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let sink = UdpSinkBuilder::<u32>::new(addr.to_string())
+        .payload_size(40)
+        .build();
+    let mut mocker = Mocker::new(sink);
+    mocker.input(0, (0..100u32).collect::<Vec<u32>>());
+    mocker.init();
+    mocker.run();
+    std::thread::sleep(Duration::from_millis(10));
+    mocker.append_input(0, (0..100u32).collect::<Vec<u32>>());
+    mocker.run();
+}
+
+#[test]
+fn udp_source() {
+    // find a free port
+    let port = UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let src = UdpSourceBuilder::<u32>::new(format!("127.0.0.1:{}", port))
+        .sequence_header(true)
+        .build();
+    let mut mocker = Mocker::new(src);
+    mocker.init_output::<u32>(0, 100);
+    mocker.init();
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.connect(("127.0.0.1", port)).unwrap();
+    socket.send(&datagram(7, &[0, 1, 2, 3])).unwrap();
+    socket.send(&datagram(8, &[4, 5, 6, 7])).unwrap();
+    // datagram 9 is lost
+    socket.send(&datagram(10, &[8, 9, 10, 11])).unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+
+    mocker.run();
+
+    assert_eq!(mocker.output::<u32>(0), (0..12).collect::<Vec<u32>>());
+    let tags = mocker.output_tags::<u32>(0);
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].index, 8);
+    assert!(matches!(&tags[0].tag, Tag::NamedUsize(k, 1) if k == "discontinuity"));
+}
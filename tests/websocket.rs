@@ -0,0 +1,186 @@
+mod common;
+use common::{bytes, connect, free_port};
+
+use async_tungstenite::tungstenite::client;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::tungstenite::WebSocket;
+use futuresdr::anyhow::Result;
+use futuresdr::async_io::block_on;
+use futuresdr::blocks::Head;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::WebsocketPmtBuilder;
+use futuresdr::blocks::WebsocketSinkBuilder;
+use futuresdr::blocks::WebsocketSinkMode;
+use futuresdr::blocks::WebsocketSourceBuilder;
+use futuresdr::blocks::WebsocketSourceMode;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use std::net::TcpStream;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+fn handshake(port: u32, s: TcpStream) -> WebSocket<TcpStream> {
+    client(format!("ws://127.0.0.1:{}", port), s).unwrap().0
+}
+
+/// Runs the block until the clients completed their handshakes.
+fn run_until_finished<T>(mocker: &mut Mocker, clients: &[JoinHandle<T>]) {
+    while !clients.iter().all(|c| c.is_finished()) {
+        mocker.run();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn websocket_sink_multi_client() -> Result<()> {
+    let port = free_port();
+
+    let mut mocker = Mocker::new(
+        WebsocketSinkBuilder::<u32>::new(port)
+            .mode(WebsocketSinkMode::FixedDropping(4))
+            .max_frame_rate(1.0)
+            .build(),
+    );
+    mocker.input(0, Vec::<u32>::new());
+    mocker.init();
+
+    let clients: Vec<_> = (0..2)
+        .map(|_| {
+            let s = connect(port);
+            std::thread::spawn(move || handshake(port, s))
+        })
+        .collect();
+    run_until_finished(&mut mocker, &clients);
+    mocker.append_input(0, (0..10u32).collect::<Vec<u32>>());
+    mocker.run();
+    let mut clients: Vec<WebSocket<TcpStream>> =
+        clients.into_iter().map(|c| c.join().unwrap()).collect();
+
+    // only the latest frame is sent
+    for c in clients.iter_mut() {
+        assert_eq!(c.read_message()?, Message::Binary(bytes(4..8)));
+    }
+
+    // a client leaves, the next frame exceeds the frame rate
+    let mut viewer = clients.pop().unwrap();
+    clients[0].close(None)?;
+    drop(clients);
+    mocker.append_input(0, (10..14u32).collect::<Vec<u32>>());
+    mocker.run();
+
+    std::thread::sleep(Duration::from_millis(1100));
+    mocker.append_input(0, (14..18u32).collect::<Vec<u32>>());
+    mocker.run();
+    assert_eq!(viewer.read_message()?, Message::Binary(bytes(12..16)));
+    Ok(())
+}
+
+#[test]
+fn websocket_sink_stalled_handshake() -> Result<()> {
+    let port = free_port();
+
+    let mut mocker = Mocker::new(WebsocketSinkBuilder::<u32>::new(port).build());
+    mocker.input(0, Vec::<u32>::new());
+    mocker.init();
+
+    // connects, but never upgrades to a websocket
+    let _stalled = connect(port);
+    let s = connect(port);
+    let client = std::thread::spawn(move || handshake(port, s));
+    run_until_finished(&mut mocker, std::slice::from_ref(&client));
+    let mut ws = client.join().unwrap();
+
+    mocker.append_input(0, (0..4u32).collect::<Vec<u32>>());
+    mocker.run();
+    assert_eq!(ws.read_message()?, Message::Binary(bytes(0..4)));
+    Ok(())
+}
+
+#[test]
+fn websocket_source() -> Result<()> {
+    let port = free_port();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(WebsocketSourceBuilder::<u32>::new(port).build());
+    let head = fg.add_block(Head::<u32>::new(100));
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+    let rt = std::thread::spawn(move || Runtime::new().run(fg));
+
+    let mut ws = handshake(port, connect(port));
+    ws.write_message(Message::Binary(bytes(0..50)))?;
+    // not a multiple of the item size
+    ws.write_message(Message::Binary(vec![1, 2, 3]))?;
+    ws.write_message(Message::Text("ignored".to_string()))?;
+    ws.write_message(Message::Binary(bytes(50..100)))?;
+
+    let fg = rt.join().unwrap()?;
+    let snk = fg.kernel::<VectorSink<u32>>(snk).unwrap();
+    assert_eq!(snk.items(), &(0..100).collect::<Vec<u32>>());
+    Ok(())
+}
+
+#[test]
+fn websocket_source_dropping() -> Result<()> {
+    let port = free_port();
+
+    let mut mocker = Mocker::new(
+        WebsocketSourceBuilder::<u32>::new(port)
+            .mode(WebsocketSourceMode::FixedDropping(4))
+            .build(),
+    );
+    mocker.init_output::<u32>(0, 10);
+    mocker.init();
+
+    let s = connect(port);
+    let client = std::thread::spawn(move || {
+        let mut ws = handshake(port, s);
+        for i in 0..3 {
+            ws.write_message(Message::Binary(bytes(i * 4..(i + 1) * 4)))
+                .unwrap();
+        }
+        ws
+    });
+    run_until_finished(&mut mocker, std::slice::from_ref(&client));
+    let _ws = client.join().unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    mocker.run();
+
+    // the third frame does not fit
+    assert_eq!(mocker.output::<u32>(0), (0..8).collect::<Vec<u32>>());
+    Ok(())
+}
+
+#[test]
+fn websocket_pmt() -> Result<()> {
+    let rx_port = free_port();
+    let tx_port = free_port();
+
+    let mut fg = Flowgraph::new();
+    let rx = fg.add_block(WebsocketPmtBuilder::new(rx_port).build());
+    let tx = fg.add_block(WebsocketPmtBuilder::new(tx_port).build());
+    fg.connect_message(rx, "out", tx, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = block_on(rt.start(fg));
+
+    let mut viewer = handshake(tx_port, connect(tx_port));
+    let mut control = handshake(rx_port, connect(rx_port));
+    control.write_message(Message::Text(r#"{"Double":100000000.0}"#.to_string()))?;
+    control.write_message(Message::Binary(vec![1, 2, 3]))?;
+
+    for pmt in [Pmt::Double(100e6), Pmt::Blob(vec![1, 2, 3])] {
+        match viewer.read_message()? {
+            Message::Text(s) => assert_eq!(serde_json::from_str::<Pmt>(&s)?, pmt),
+            m => panic!("unexpected message {:?}", m),
+        }
+    }
+
+    block_on(handle.terminate())?;
+    block_on(task)?;
+    Ok(())
+}
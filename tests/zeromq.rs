@@ -0,0 +1,154 @@
+#![cfg(feature = "zeromq")]
+mod common;
+use common::free_port;
+
+use futuresdr::blocks::zeromq::PduSinkBuilder;
+use futuresdr::blocks::zeromq::PullSourceBuilder;
+use futuresdr::blocks::zeromq::PushSinkBuilder;
+use futuresdr::blocks::zeromq::RepSinkBuilder;
+use futuresdr::blocks::zeromq::ReqSourceBuilder;
+use futuresdr::runtime::ItemTag;
+use futuresdr::runtime::Mocker;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Tag;
+use std::time::Duration;
+
+#[test]
+fn push_pull_tags() {
+    let port = free_port();
+
+    let mut snk = Mocker::new(
+        PushSinkBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .tags(true)
+            .build(),
+    );
+    let mut src = Mocker::new(
+        PullSourceBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .tags(true)
+            .build(),
+    );
+
+    snk.input_with_tags(
+        0,
+        (0..100u32).collect::<Vec<u32>>(),
+        vec![ItemTag {
+            index: 42,
+            tag: Tag::NamedF64("freq".to_string(), 2.4e9),
+        }],
+    );
+    src.init_output::<u32>(0, 100);
+    snk.init();
+    src.init();
+    std::thread::sleep(Duration::from_millis(100));
+
+    snk.run();
+    src.run();
+
+    assert_eq!(src.output::<u32>(0), (0..100u32).collect::<Vec<u32>>());
+    let tags = src.output_tags::<u32>(0);
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].index, 42);
+    assert!(matches!(&tags[0].tag, Tag::NamedF64(n, f) if n == "freq" && *f == 2.4e9));
+}
+
+#[test]
+fn push_pull_large_message() {
+    let port = free_port();
+
+    let mut snk = Mocker::new(
+        PushSinkBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .tags(true)
+            .build(),
+    );
+    let mut src = Mocker::new(
+        PullSourceBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .tags(true)
+            .build(),
+    );
+
+    snk.input_with_tags(
+        0,
+        (0..100u32).collect::<Vec<u32>>(),
+        vec![ItemTag {
+            index: 42,
+            tag: Tag::NamedF64("freq".to_string(), 2.4e9),
+        }],
+    );
+    snk.init();
+    src.init();
+    std::thread::sleep(Duration::from_millis(100));
+    snk.run();
+
+    // the message does not fit into the output buffer
+    src.init_output::<u32>(0, 30);
+    src.run();
+    assert_eq!(src.output::<u32>(0), (0..30u32).collect::<Vec<u32>>());
+    assert!(src.output_tags::<u32>(0).is_empty());
+
+    // the rest is output on the next call
+    src.init_output::<u32>(0, 100);
+    src.run();
+    assert_eq!(src.output::<u32>(0), (30..100u32).collect::<Vec<u32>>());
+    let tags = src.output_tags::<u32>(0);
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].index, 12);
+}
+
+#[test]
+fn req_rep() {
+    let port = free_port();
+
+    let mut snk = Mocker::new(
+        RepSinkBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .build(),
+    );
+    snk.input(0, (0..10u32).collect::<Vec<u32>>());
+    snk.init();
+    let server = std::thread::spawn(move || snk.run());
+
+    // asks for at most ten samples
+    let mut src = Mocker::new(
+        ReqSourceBuilder::new(4)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .build(),
+    );
+    src.init_output::<u32>(0, 10);
+    src.init();
+    src.run();
+    server.join().unwrap();
+
+    assert_eq!(src.output::<u32>(0), (0..10u32).collect::<Vec<u32>>());
+}
+
+#[test]
+fn pdu_push() {
+    let port = free_port();
+
+    let mut snk = Mocker::new(
+        PduSinkBuilder::new()
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .push(true)
+            .build(),
+    );
+    let mut src = Mocker::new(
+        PullSourceBuilder::new(1)
+            .address(&format!("tcp://127.0.0.1:{}", port))
+            .build(),
+    );
+    src.init_output::<u8>(0, 1024);
+    snk.init();
+    src.init();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let pmt = Pmt::Blob(vec![1, 2, 3]);
+    snk.call_handler(0, pmt.clone());
+    src.run();
+
+    let json = src.output::<u8>(0);
+    assert_eq!(serde_json::from_slice::<Pmt>(&json).unwrap(), pmt);
+}